@@ -0,0 +1,276 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::css::{Declaration, DeclarationValue};
+use crate::values::{Float, Unit};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum TimingFunction {
+    Ease,
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    StepStart,
+    StepEnd,
+    CubicBezier(Float, Float, Float, Float),
+    Steps(u32),
+}
+
+impl TimingFunction {
+    pub fn cubic_bezier(
+        x1: impl Into<Float>,
+        y1: impl Into<Float>,
+        x2: impl Into<Float>,
+        y2: impl Into<Float>,
+    ) -> Self {
+        TimingFunction::CubicBezier(x1.into(), y1.into(), x2.into(), y2.into())
+    }
+
+    pub fn as_keyword(&self) -> Option<&'static str> {
+        match self {
+            TimingFunction::Ease => Some("ease"),
+            TimingFunction::Linear => Some("linear"),
+            TimingFunction::EaseIn => Some("ease-in"),
+            TimingFunction::EaseOut => Some("ease-out"),
+            TimingFunction::EaseInOut => Some("ease-in-out"),
+            TimingFunction::StepStart => Some("step-start"),
+            TimingFunction::StepEnd => Some("step-end"),
+            TimingFunction::CubicBezier(..) | TimingFunction::Steps(_) => None,
+        }
+    }
+
+    pub fn to_value(&self) -> DeclarationValue {
+        match self {
+            TimingFunction::CubicBezier(x1, y1, x2, y2) => DeclarationValue::Function(
+                "cubic-bezier".to_string(),
+                [x1, y1, x2, y2].iter().map(|v| v.to_string()).collect(),
+            ),
+            TimingFunction::Steps(count) => {
+                DeclarationValue::Function("steps".to_string(), vec![count.to_string()])
+            }
+            keyword => DeclarationValue::keyword(keyword.as_keyword().unwrap_or_default()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum IterationCount {
+    Times(Float),
+    Infinite,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum FillMode {
+    None,
+    Forwards,
+    Backwards,
+    Both,
+}
+
+impl FillMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FillMode::None => "none",
+            FillMode::Forwards => "forwards",
+            FillMode::Backwards => "backwards",
+            FillMode::Both => "both",
+        }
+    }
+}
+
+fn milliseconds(value: Float) -> DeclarationValue {
+    DeclarationValue::Length(value, Unit::Ms)
+}
+
+// a single item is written without the comma list around it
+fn shorthand(property: &str, mut items: Vec<DeclarationValue>) -> Declaration {
+    let value = match items.len() {
+        1 => items.remove(0),
+        _ => DeclarationValue::comma_list(items),
+    };
+    Declaration::new(property.to_string(), value)
+}
+
+// one item of the transition shorthand, times are in milliseconds
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct Transition {
+    property: String,
+    duration: Float,
+    timing: Option<TimingFunction>,
+    delay: Option<Float>,
+}
+
+impl Transition {
+    pub fn new(property: impl Into<String>, duration: impl Into<Float>) -> Self {
+        Self {
+            property: property.into(),
+            duration: duration.into(),
+            timing: None,
+            delay: None,
+        }
+    }
+
+    pub fn timing(mut self, timing: TimingFunction) -> Self {
+        self.timing = Some(timing);
+        self
+    }
+
+    pub fn delay(mut self, delay: impl Into<Float>) -> Self {
+        self.delay = Some(delay.into());
+        self
+    }
+
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    // the duration is always written since the first time is read as the duration
+    pub fn to_value(&self) -> DeclarationValue {
+        let mut values = vec![
+            DeclarationValue::keyword(self.property.as_str()),
+            milliseconds(self.duration),
+        ];
+        values.extend(self.timing.map(|timing| timing.to_value()));
+        values.extend(self.delay.map(milliseconds));
+        DeclarationValue::space_list(values)
+    }
+
+    // transition:opacity 200ms,transform 300ms ease-out
+    pub fn shorthand(transitions: &[Transition]) -> Declaration {
+        shorthand(
+            "transition",
+            transitions.iter().map(Transition::to_value).collect(),
+        )
+    }
+}
+
+// one item of the animation shorthand naming a @keyframes rule, times are in milliseconds
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct Animation {
+    name: String,
+    duration: Float,
+    timing: Option<TimingFunction>,
+    delay: Option<Float>,
+    iterations: Option<IterationCount>,
+    fill_mode: Option<FillMode>,
+}
+
+impl Animation {
+    pub fn new(name: impl Into<String>, duration: impl Into<Float>) -> Self {
+        Self {
+            name: name.into(),
+            duration: duration.into(),
+            timing: None,
+            delay: None,
+            iterations: None,
+            fill_mode: None,
+        }
+    }
+
+    pub fn timing(mut self, timing: TimingFunction) -> Self {
+        self.timing = Some(timing);
+        self
+    }
+
+    pub fn delay(mut self, delay: impl Into<Float>) -> Self {
+        self.delay = Some(delay.into());
+        self
+    }
+
+    pub fn iterations(mut self, iterations: IterationCount) -> Self {
+        self.iterations = Some(iterations);
+        self
+    }
+
+    pub fn fill_mode(mut self, fill_mode: FillMode) -> Self {
+        self.fill_mode = Some(fill_mode);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // a name that is also a keyword of the shorthand, like ease or both, is read as the keyword
+    pub fn to_value(&self) -> DeclarationValue {
+        let mut values = vec![milliseconds(self.duration)];
+        values.extend(self.timing.map(|timing| timing.to_value()));
+        values.extend(self.delay.map(milliseconds));
+        values.extend(self.iterations.map(|iterations| match iterations {
+            IterationCount::Times(count) => DeclarationValue::Number(count),
+            IterationCount::Infinite => DeclarationValue::keyword("infinite"),
+        }));
+        values.extend(
+            self.fill_mode
+                .map(|fill_mode| DeclarationValue::keyword(fill_mode.as_str())),
+        );
+        values.push(DeclarationValue::keyword(self.name.as_str()));
+        DeclarationValue::space_list(values)
+    }
+
+    // animation:300ms ease-out both fade-in,1000ms infinite spin
+    pub fn shorthand(animations: &[Animation]) -> Declaration {
+        shorthand(
+            "animation",
+            animations.iter().map(Animation::to_value).collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use crate::animation::{Animation, FillMode, IterationCount, TimingFunction, Transition};
+    use crate::css::RuleSet;
+
+    #[test]
+    fn transitions() {
+        let declaration = Transition::shorthand(&[
+            Transition::new("opacity", 200),
+            Transition::new("transform", 300)
+                .timing(TimingFunction::cubic_bezier(0.4, 0, 0.2, 1))
+                .delay(50),
+        ]);
+
+        assert_eq!(
+            declaration.to_string(),
+            "transition:opacity 200ms,transform 300ms cubic-bezier(0.4,0,0.2,1) 50ms;"
+        );
+        assert_eq!(declaration.estimated_len(), declaration.to_string().len());
+        let parsed = RuleSet::parse(&["a{", &declaration.to_string(), "}"].concat()).unwrap();
+        assert_eq!(parsed.rules()[0].declarations(), vec![declaration]);
+        assert_eq!(
+            Transition::shorthand(
+                &[Transition::new("color", 150).timing(TimingFunction::Steps(4))]
+            )
+            .to_string(),
+            "transition:color 150ms steps(4);"
+        );
+    }
+
+    #[test]
+    fn animations() {
+        let declaration = Animation::shorthand(&[
+            Animation::new("fade-in", 300)
+                .timing(TimingFunction::EaseOut)
+                .fill_mode(FillMode::Both),
+            Animation::new("spin", 1000)
+                .iterations(IterationCount::Infinite)
+                .timing(TimingFunction::Linear),
+            Animation::new("pulse", 0)
+                .delay(100)
+                .iterations(IterationCount::Times(2.5.into())),
+        ]);
+
+        assert_eq!(
+            declaration.to_string(),
+            "animation:300ms ease-out both fade-in,1000ms linear infinite spin,0ms 100ms 2.5 pulse;"
+        );
+        assert_eq!(declaration.estimated_len(), declaration.to_string().len());
+    }
+}
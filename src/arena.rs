@@ -0,0 +1,197 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::html::Node;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct ArenaNode {
+    node: Node, // elements are stored without children, those are tracked by id below
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+}
+
+// flat storage for a node tree where nodes reference each other by id
+// detached nodes stay allocated until the arena is dropped
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct NodeArena {
+    nodes: Vec<ArenaNode>,
+}
+
+impl NodeArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            nodes: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn from_node(node: &Node) -> (Self, NodeId) {
+        let mut arena = Self::new();
+        let root = arena.insert_tree(node);
+        (arena, root)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    // adds a single detached node, any element children of the given node are dropped
+    pub fn add(&mut self, mut node: Node) -> NodeId {
+        if let Some(children) = node.children_mut() {
+            children.clear();
+        }
+
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(ArenaNode {
+            node,
+            parent: None,
+            children: vec![],
+        });
+        id
+    }
+
+    // adds a detached copy of the node and all its descendants
+    pub fn insert_tree(&mut self, node: &Node) -> NodeId {
+        let id = match node {
+            Node::Element {
+                tag,
+                attributes,
+                self_closing,
+                ..
+            } => self.add(Node::Element {
+                tag: tag.clone(),
+                attributes: attributes.clone(),
+                children: vec![],
+                self_closing: *self_closing,
+            }),
+            _ => self.add(node.clone()),
+        };
+
+        for child in node.children() {
+            let child_id = self.insert_tree(child);
+            self.append_child(id, child_id);
+        }
+
+        id
+    }
+
+    // element children of the returned node are always empty, use children() instead
+    pub fn get(&self, id: NodeId) -> &Node {
+        &self.nodes[id.0].node
+    }
+
+    pub fn get_mut(&mut self, id: NodeId) -> &mut Node {
+        &mut self.nodes[id.0].node
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id.0].parent
+    }
+
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        &self.nodes[id.0].children
+    }
+
+    pub fn append_child(&mut self, parent: NodeId, child: NodeId) {
+        self.insert_child(parent, usize::MAX, child);
+    }
+
+    // moves the child from its current parent if it has one, index is clamped to the child count
+    pub fn insert_child(&mut self, parent: NodeId, index: usize, child: NodeId) {
+        self.detach(child);
+        self.nodes[child.0].parent = Some(parent);
+        let children = &mut self.nodes[parent.0].children;
+        children.insert(index.min(children.len()), child);
+    }
+
+    pub fn detach(&mut self, id: NodeId) {
+        if let Some(parent) = self.nodes[id.0].parent.take() {
+            self.nodes[parent.0].children.retain(|c| *c != id);
+        }
+    }
+
+    pub fn to_node(&self, id: NodeId) -> Node {
+        let mut node = self.nodes[id.0].node.clone();
+        if let Some(children) = node.children_mut() {
+            *children = self.nodes[id.0]
+                .children
+                .iter()
+                .map(|child| self.to_node(*child))
+                .collect();
+        }
+        node
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::arena::NodeArena;
+    use crate::html::{Attribute, Node};
+
+    fn make_tree() -> Node {
+        Node::element(
+            "body".to_string(),
+            vec![Attribute::new("class".to_string(), "main".to_string())],
+            vec![
+                Node::element(
+                    "h1".to_string(),
+                    vec![],
+                    vec![Node::text("Heading".to_string())],
+                ),
+                Node::comment("Some comments".to_string()),
+                Node::text("Some text".to_string()),
+            ],
+        )
+    }
+
+    #[test]
+    fn round_trip() {
+        let tree = make_tree();
+        let (arena, root) = NodeArena::from_node(&tree);
+
+        assert_eq!(arena.len(), 5);
+        assert_eq!(arena.to_node(root), tree);
+    }
+
+    #[test]
+    fn parent_and_children() {
+        let (arena, root) = NodeArena::from_node(&make_tree());
+        let heading = arena.children(root)[0];
+
+        assert_eq!(arena.parent(heading), Some(root));
+        assert_eq!(arena.parent(root), None);
+        assert_eq!(
+            arena.get(arena.children(heading)[0]),
+            &Node::text("Heading".to_string())
+        );
+    }
+
+    #[test]
+    fn move_and_detach() {
+        let (mut arena, root) = NodeArena::from_node(&make_tree());
+        let heading = arena.children(root)[0];
+        let comment = arena.children(root)[1];
+        let text = arena.children(root)[2];
+
+        arena.append_child(heading, text);
+        arena.append_child(root, heading);
+        arena.detach(comment);
+        let footer = arena.add(Node::element("footer".to_string(), vec![], vec![]));
+        arena.insert_child(root, 0, footer);
+
+        assert_eq!(
+            arena.to_node(root).to_string(),
+            "<body class=\"main\"><footer></footer><h1>HeadingSome text</h1></body>"
+        );
+    }
+}
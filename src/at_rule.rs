@@ -0,0 +1,1146 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write;
+use serde::{Deserialize, Serialize};
+
+use crate::css::{
+    css_escaped_len, write_css_escaped, write_declarations, CssFormat, Declaration,
+    DeclarationValue, MediaCondition, RuleSet,
+};
+use crate::values::{display_len, Float};
+
+// a point of an animation, from is 0% and to is 100%
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum KeyframeSelector {
+    From,
+    To,
+    Percentage(Float),
+}
+
+impl KeyframeSelector {
+    pub fn percentage(value: impl Into<Float>) -> Self {
+        KeyframeSelector::Percentage(value.into())
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        match self {
+            KeyframeSelector::From => 4,
+            KeyframeSelector::To => 2,
+            KeyframeSelector::Percentage(value) => display_len(value) + 1,
+        }
+    }
+
+    fn render<W: Write>(&self, output: &mut W) -> fmt::Result {
+        match self {
+            KeyframeSelector::From => output.write_str("from"),
+            KeyframeSelector::To => output.write_str("to"),
+            KeyframeSelector::Percentage(value) => write!(output, "{}%", value),
+        }
+    }
+}
+
+impl fmt::Display for KeyframeSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Keyframe {
+    selectors: Vec<KeyframeSelector>,
+    declarations: Vec<Declaration>,
+}
+
+impl Keyframe {
+    pub fn new(selectors: Vec<KeyframeSelector>, declarations: Vec<Declaration>) -> Self {
+        Self {
+            selectors,
+            declarations,
+        }
+    }
+
+    pub fn selectors(&self) -> &[KeyframeSelector] {
+        &self.selectors
+    }
+
+    pub fn declarations(&self) -> &[Declaration] {
+        &self.declarations
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        self.selectors
+            .iter()
+            .map(KeyframeSelector::estimated_len)
+            .sum::<usize>()
+            + self.selectors.len().saturating_sub(1)
+            + self
+                .declarations
+                .iter()
+                .map(Declaration::estimated_len)
+                .sum::<usize>()
+            + 2
+    }
+
+    fn render_formatted<W: Write>(
+        &self,
+        output: &mut W,
+        format: &CssFormat,
+        depth: usize,
+    ) -> fmt::Result {
+        format.write_indent(output, depth)?;
+        for (i, selector) in self.selectors.iter().enumerate() {
+            if i > 0 {
+                output.write_char(',')?;
+            }
+            selector.render(output)?;
+        }
+        output.write_str(format.pick("{", " {\n"))?;
+        write_declarations(output, &self.declarations, format, depth + 1)?;
+        format.write_indent(output, depth)?;
+        output.write_str(format.pick("}", "}\n"))
+    }
+}
+
+// @keyframes name{from{...}50%{...}to{...}}
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Keyframes {
+    name: String,
+    keyframes: Vec<Keyframe>,
+}
+
+impl Keyframes {
+    pub fn new(name: String, keyframes: Vec<Keyframe>) -> Self {
+        Self { name, keyframes }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        11 + css_escaped_len(&self.name, false)
+            + self
+                .keyframes
+                .iter()
+                .map(Keyframe::estimated_len)
+                .sum::<usize>()
+            + 2
+    }
+
+    fn render_formatted<W: Write>(
+        &self,
+        output: &mut W,
+        format: &CssFormat,
+        depth: usize,
+    ) -> fmt::Result {
+        format.write_indent(output, depth)?;
+        output.write_str("@keyframes ")?;
+        write_css_escaped(output, &self.name, false)?;
+        output.write_str(format.pick("{", " {\n"))?;
+        for keyframe in &self.keyframes {
+            keyframe.render_formatted(output, format, depth + 1)?;
+        }
+        format.write_indent(output, depth)?;
+        output.write_str(format.pick("}", "}\n"))
+    }
+}
+
+impl fmt::Display for Keyframes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render_formatted(f, &CssFormat::compact(), 0)
+    }
+}
+
+fn quoted(text: &str) -> String {
+    let mut output = String::with_capacity(css_escaped_len(text, true));
+    // writing to a String can not fail
+    let _ = write_css_escaped(&mut output, text, true);
+    output
+}
+
+// one entry of the src list of a font face
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum FontSource {
+    Url(String, Option<String>), // (url, format hint like woff2)
+    Local(String),               // a font installed on the device
+}
+
+impl FontSource {
+    pub fn url(url: impl Into<String>) -> Self {
+        FontSource::Url(url.into(), None)
+    }
+
+    pub fn url_with_format(url: impl Into<String>, format: impl Into<String>) -> Self {
+        FontSource::Url(url.into(), Some(format.into()))
+    }
+
+    pub fn local(name: impl Into<String>) -> Self {
+        FontSource::Local(name.into())
+    }
+
+    fn value(&self) -> DeclarationValue {
+        match self {
+            FontSource::Url(url, None) => DeclarationValue::url(url.as_str()),
+            FontSource::Url(url, Some(format)) => DeclarationValue::space_list(vec![
+                DeclarationValue::url(url.as_str()),
+                DeclarationValue::Function("format".to_string(), vec![quoted(format)]),
+            ]),
+            FontSource::Local(name) => {
+                DeclarationValue::Function("local".to_string(), vec![quoted(name)])
+            }
+        }
+    }
+}
+
+// @font-face{font-family:...;src:...} with the common descriptors as fields
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct FontFace {
+    family: String,
+    sources: Vec<FontSource>,
+    #[serde(default)]
+    weight: Option<DeclarationValue>,
+    #[serde(default)]
+    style: Option<DeclarationValue>,
+    #[serde(default)]
+    display: Option<DeclarationValue>,
+    #[serde(default)]
+    descriptors: Vec<Declaration>,
+}
+
+impl FontFace {
+    pub fn new(family: String, sources: Vec<FontSource>) -> Self {
+        Self {
+            family,
+            sources,
+            weight: None,
+            style: None,
+            display: None,
+            descriptors: vec![],
+        }
+    }
+
+    // e.g. 700 or a range like 100 900 for variable fonts
+    pub fn weight(mut self, weight: DeclarationValue) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    pub fn style(mut self, style: DeclarationValue) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    // e.g. swap so text stays visible while the font loads
+    pub fn display(mut self, display: DeclarationValue) -> Self {
+        self.display = Some(display);
+        self
+    }
+
+    // any other descriptor, e.g. unicode-range
+    pub fn descriptor(mut self, descriptor: Declaration) -> Self {
+        self.descriptors.push(descriptor);
+        self
+    }
+
+    pub fn family(&self) -> &str {
+        &self.family
+    }
+
+    pub fn sources(&self) -> &[FontSource] {
+        &self.sources
+    }
+
+    // every descriptor in the order they are written
+    pub fn declarations(&self) -> Vec<Declaration> {
+        let mut declarations = vec![
+            Declaration::new(
+                "font-family".to_string(),
+                DeclarationValue::Basic(self.family.clone()),
+            ),
+            Declaration::new(
+                "src".to_string(),
+                DeclarationValue::comma_list(self.sources.iter().map(FontSource::value).collect()),
+            ),
+        ];
+        for (property, value) in [
+            ("font-weight", &self.weight),
+            ("font-style", &self.style),
+            ("font-display", &self.display),
+        ] {
+            if let Some(value) = value {
+                declarations.push(Declaration::new(property.to_string(), value.clone()));
+            }
+        }
+        declarations.extend(self.descriptors.iter().cloned());
+        declarations
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        12 + self
+            .declarations()
+            .iter()
+            .map(Declaration::estimated_len)
+            .sum::<usize>()
+    }
+
+    fn render_formatted<W: Write>(
+        &self,
+        output: &mut W,
+        format: &CssFormat,
+        depth: usize,
+    ) -> fmt::Result {
+        format.write_indent(output, depth)?;
+        output.write_str("@font-face")?;
+        output.write_str(format.pick("{", " {\n"))?;
+        write_declarations(output, &self.declarations(), format, depth + 1)?;
+        format.write_indent(output, depth)?;
+        output.write_str(format.pick("}", "}\n"))
+    }
+}
+
+impl fmt::Display for FontFace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render_formatted(f, &CssFormat::compact(), 0)
+    }
+}
+
+// @container [name] (condition) and (condition), size conditions use the media feature syntax
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ContainerQuery {
+    #[serde(default)]
+    name: Option<String>,
+    conditions: Vec<MediaCondition>,
+}
+
+impl ContainerQuery {
+    pub fn new(name: Option<String>, conditions: Vec<MediaCondition>) -> Self {
+        Self { name, conditions }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn conditions(&self) -> &[MediaCondition] {
+        &self.conditions
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        // a space before the first condition and " and " before the others
+        let separators_len = match self.conditions.len() {
+            0 => 0,
+            len => 1 + (len - 1) * 5,
+        };
+        10 + self
+            .name
+            .as_ref()
+            .map_or(0, |name| css_escaped_len(name, false) + 1)
+            + self
+                .conditions
+                .iter()
+                .map(|condition| condition.operand_len(true))
+                .sum::<usize>()
+            + separators_len
+    }
+
+    fn render<W: Write>(&self, output: &mut W) -> fmt::Result {
+        output.write_str("@container")?;
+        if let Some(name) = &self.name {
+            output.write_char(' ')?;
+            write_css_escaped(output, name, false)?;
+        }
+        for (i, condition) in self.conditions.iter().enumerate() {
+            output.write_str(if i == 0 { " " } else { " and " })?;
+            condition.render_operand(output, true)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ContainerQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ContainerType {
+    Normal,
+    Size,
+    InlineSize,
+}
+
+impl ContainerType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContainerType::Normal => "normal",
+            ContainerType::Size => "size",
+            ContainerType::InlineSize => "inline-size",
+        }
+    }
+}
+
+impl Declaration {
+    // container-type:inline-size makes an element a query container for its width
+    pub fn container_type(container_type: ContainerType) -> Self {
+        Declaration::new(
+            "container-type".to_string(),
+            DeclarationValue::keyword(container_type.as_str()),
+        )
+    }
+
+    // names that @container queries can refer to
+    pub fn container_name<S: Into<String>>(names: impl IntoIterator<Item = S>) -> Self {
+        let mut names: Vec<DeclarationValue> =
+            names.into_iter().map(DeclarationValue::keyword).collect();
+        Declaration::new(
+            "container-name".to_string(),
+            match names.len() {
+                1 => names.remove(0),
+                _ => DeclarationValue::space_list(names),
+            },
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum PagePseudoClass {
+    First,
+    Left,
+    Right,
+    Last,
+    Blank,
+}
+
+impl PagePseudoClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PagePseudoClass::First => ":first",
+            PagePseudoClass::Left => ":left",
+            PagePseudoClass::Right => ":right",
+            PagePseudoClass::Last => ":last",
+            PagePseudoClass::Blank => ":blank",
+        }
+    }
+}
+
+// a named page type and/or pseudo classes, e.g. cover:first
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct PageSelector {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    pseudo_classes: Vec<PagePseudoClass>,
+}
+
+impl PageSelector {
+    pub fn new(name: Option<String>, pseudo_classes: Vec<PagePseudoClass>) -> Self {
+        Self {
+            name,
+            pseudo_classes,
+        }
+    }
+
+    pub fn pseudo(pseudo_class: PagePseudoClass) -> Self {
+        Self::new(None, vec![pseudo_class])
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn pseudo_classes(&self) -> &[PagePseudoClass] {
+        &self.pseudo_classes
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        self.name
+            .as_ref()
+            .map_or(0, |name| css_escaped_len(name, false))
+            + self
+                .pseudo_classes
+                .iter()
+                .map(|p| p.as_str().len())
+                .sum::<usize>()
+    }
+
+    fn render<W: Write>(&self, output: &mut W) -> fmt::Result {
+        if let Some(name) = &self.name {
+            write_css_escaped(output, name, false)?;
+        }
+        for pseudo_class in &self.pseudo_classes {
+            output.write_str(pseudo_class.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! margin_boxes {
+    ($($variant:ident => $name:literal,)*) => {
+        // the boxes in the page margin that hold running headers, footers and page numbers
+        #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+        pub enum MarginBox {
+            $($variant,)*
+        }
+
+        impl MarginBox {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(MarginBox::$variant => $name,)*
+                }
+            }
+
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name.to_ascii_lowercase().as_str() {
+                    $($name => Some(MarginBox::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+#[rustfmt::skip]
+margin_boxes! {
+    TopLeftCorner => "top-left-corner", TopLeft => "top-left", TopCenter => "top-center",
+    TopRight => "top-right", TopRightCorner => "top-right-corner",
+    BottomLeftCorner => "bottom-left-corner", BottomLeft => "bottom-left",
+    BottomCenter => "bottom-center", BottomRight => "bottom-right",
+    BottomRightCorner => "bottom-right-corner", LeftTop => "left-top",
+    LeftMiddle => "left-middle", LeftBottom => "left-bottom", RightTop => "right-top",
+    RightMiddle => "right-middle", RightBottom => "right-bottom",
+}
+
+fn declarations_len(declarations: &[Declaration]) -> usize {
+    declarations.iter().map(Declaration::estimated_len).sum()
+}
+
+// @page :first{margin:1in;@bottom-center{content:counter(page);}}
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct PageRule {
+    #[serde(default)]
+    selectors: Vec<PageSelector>,
+    declarations: Vec<Declaration>,
+    #[serde(default)]
+    margin_boxes: Vec<(MarginBox, Vec<Declaration>)>,
+}
+
+impl PageRule {
+    pub fn new(selectors: Vec<PageSelector>, declarations: Vec<Declaration>) -> Self {
+        Self {
+            selectors,
+            declarations,
+            margin_boxes: vec![],
+        }
+    }
+
+    pub fn margin_box(mut self, margin_box: MarginBox, declarations: Vec<Declaration>) -> Self {
+        self.margin_boxes.push((margin_box, declarations));
+        self
+    }
+
+    pub fn selectors(&self) -> &[PageSelector] {
+        &self.selectors
+    }
+
+    pub fn declarations(&self) -> &[Declaration] {
+        &self.declarations
+    }
+
+    pub fn margin_boxes(&self) -> &[(MarginBox, Vec<Declaration>)] {
+        &self.margin_boxes
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        let selectors_len = match self.selectors.len() {
+            0 => 0,
+            len => {
+                len + self
+                    .selectors
+                    .iter()
+                    .map(PageSelector::estimated_len)
+                    .sum::<usize>()
+            }
+        };
+        7 + selectors_len
+            + declarations_len(&self.declarations)
+            + self
+                .margin_boxes
+                .iter()
+                .map(|(margin_box, declarations)| {
+                    3 + margin_box.as_str().len() + declarations_len(declarations)
+                })
+                .sum::<usize>()
+    }
+
+    fn render_formatted<W: Write>(
+        &self,
+        output: &mut W,
+        format: &CssFormat,
+        depth: usize,
+    ) -> fmt::Result {
+        format.write_indent(output, depth)?;
+        output.write_str("@page")?;
+        for (i, selector) in self.selectors.iter().enumerate() {
+            output.write_char(if i == 0 { ' ' } else { ',' })?;
+            selector.render(output)?;
+        }
+        output.write_str(format.pick("{", " {\n"))?;
+        match self.margin_boxes.is_empty() {
+            true => write_declarations(output, &self.declarations, format, depth + 1)?,
+            // the last declaration still needs its semicolon before a margin box
+            false => self.declarations.iter().try_for_each(|declaration| {
+                declaration.render_formatted(output, format, depth + 1)
+            })?,
+        }
+        for (margin_box, declarations) in &self.margin_boxes {
+            format.write_indent(output, depth + 1)?;
+            output.write_char('@')?;
+            output.write_str(margin_box.as_str())?;
+            output.write_str(format.pick("{", " {\n"))?;
+            write_declarations(output, declarations, format, depth + 2)?;
+            format.write_indent(output, depth + 1)?;
+            output.write_str(format.pick("}", "}\n"))?;
+        }
+        format.write_indent(output, depth)?;
+        output.write_str(format.pick("}", "}\n"))
+    }
+}
+
+impl fmt::Display for PageRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render_formatted(f, &CssFormat::compact(), 0)
+    }
+}
+
+// the contents of an at-rule the crate has no dedicated type for
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum AtRuleBlock {
+    Declarations(Vec<Declaration>), // e.g. @property
+    Rules(RuleSet),                 // e.g. @scope
+}
+
+impl AtRuleBlock {
+    pub fn estimated_len(&self) -> usize {
+        2 + match self {
+            AtRuleBlock::Declarations(declarations) => declarations_len(declarations),
+            AtRuleBlock::Rules(set) => set.estimated_len(),
+        }
+    }
+
+    fn render_formatted<W: Write>(
+        &self,
+        output: &mut W,
+        format: &CssFormat,
+        depth: usize,
+    ) -> fmt::Result {
+        output.write_str(format.pick("{", " {\n"))?;
+        match self {
+            AtRuleBlock::Declarations(declarations) => {
+                write_declarations(output, declarations, format, depth + 1)?
+            }
+            AtRuleBlock::Rules(set) => set.render_formatted(output, format, depth + 1)?,
+        }
+        format.write_indent(output, depth)?;
+        output.write_str(format.pick("}", "}\n"))
+    }
+}
+
+// layer names may be dotted for sub layers, e.g. framework.base
+fn layer_name_len(name: &str) -> usize {
+    name.split('.')
+        .map(|part| css_escaped_len(part, false) + 1)
+        .sum::<usize>()
+        - 1
+}
+
+fn write_layer_name<W: Write>(output: &mut W, name: &str) -> fmt::Result {
+    for (i, part) in name.split('.').enumerate() {
+        if i > 0 {
+            output.write_char('.')?;
+        }
+        write_css_escaped(output, part, false)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn layer_order_len(names: &[String]) -> usize {
+    8 + names.iter().map(|n| layer_name_len(n)).sum::<usize>() + names.len().saturating_sub(1)
+}
+
+pub(crate) fn write_layer_order<W: Write>(
+    output: &mut W,
+    names: &[String],
+    format: &CssFormat,
+    depth: usize,
+) -> fmt::Result {
+    format.write_indent(output, depth)?;
+    output.write_str("@layer ")?;
+    for (i, name) in names.iter().enumerate() {
+        if i > 0 {
+            output.write_char(',')?;
+        }
+        write_layer_name(output, name)?;
+    }
+    output.write_str(format.pick(";", ";\n"))
+}
+
+// at-rules other than @media, which rule sets model themselves
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum AtRule {
+    Keyframes(Keyframes),
+    FontFace(FontFace),
+    LayerOrder(Vec<String>), // @layer reset,components; fixes the order of the layers
+    Layer(Option<String>, RuleSet), // rules in a named or anonymous layer
+    Container(ContainerQuery, RuleSet),
+    Page(PageRule),
+    // written as is, the prelude is not validated
+    Generic {
+        name: String,
+        prelude: String,
+        block: Option<AtRuleBlock>,
+    },
+}
+
+impl AtRule {
+    pub fn estimated_len(&self) -> usize {
+        match self {
+            AtRule::Keyframes(keyframes) => keyframes.estimated_len(),
+            AtRule::FontFace(font_face) => font_face.estimated_len(),
+            AtRule::LayerOrder(names) => layer_order_len(names),
+            AtRule::Container(query, set) => query.estimated_len() + set.estimated_len() + 2,
+            AtRule::Page(page) => page.estimated_len(),
+            AtRule::Generic {
+                name,
+                prelude,
+                block,
+            } => {
+                1 + css_escaped_len(name, false)
+                    + if prelude.is_empty() {
+                        0
+                    } else {
+                        prelude.len() + 1
+                    }
+                    + block.as_ref().map_or(1, AtRuleBlock::estimated_len)
+            }
+            AtRule::Layer(name, set) => {
+                8 + name.as_ref().map_or(0, |n| layer_name_len(n) + 1) + set.estimated_len()
+            }
+        }
+    }
+
+    pub(crate) fn render_formatted<W: Write>(
+        &self,
+        output: &mut W,
+        format: &CssFormat,
+        depth: usize,
+    ) -> fmt::Result {
+        match self {
+            AtRule::Keyframes(keyframes) => keyframes.render_formatted(output, format, depth),
+            AtRule::FontFace(font_face) => font_face.render_formatted(output, format, depth),
+            AtRule::LayerOrder(names) => write_layer_order(output, names, format, depth),
+            AtRule::Page(page) => page.render_formatted(output, format, depth),
+            AtRule::Generic {
+                name,
+                prelude,
+                block,
+            } => {
+                format.write_indent(output, depth)?;
+                output.write_char('@')?;
+                write_css_escaped(output, name, false)?;
+                if !prelude.is_empty() {
+                    output.write_char(' ')?;
+                    output.write_str(prelude)?;
+                }
+                match block {
+                    Some(block) => block.render_formatted(output, format, depth),
+                    None => output.write_str(format.pick(";", ";\n")),
+                }
+            }
+            AtRule::Container(query, set) => {
+                format.write_indent(output, depth)?;
+                query.render(output)?;
+                output.write_str(format.pick("{", " {\n"))?;
+                set.render_formatted(output, format, depth + 1)?;
+                format.write_indent(output, depth)?;
+                output.write_str(format.pick("}", "}\n"))
+            }
+            AtRule::Layer(name, set) => {
+                format.write_indent(output, depth)?;
+                output.write_str("@layer")?;
+                if let Some(name) = name {
+                    output.write_char(' ')?;
+                    write_layer_name(output, name)?;
+                }
+                output.write_str(format.pick("{", " {\n"))?;
+                set.render_formatted(output, format, depth + 1)?;
+                format.write_indent(output, depth)?;
+                output.write_str(format.pick("}", "}\n"))
+            }
+        }
+    }
+}
+
+impl From<Keyframes> for AtRule {
+    fn from(keyframes: Keyframes) -> Self {
+        AtRule::Keyframes(keyframes)
+    }
+}
+
+impl AtRule {
+    pub fn layer_order<S: Into<String>>(names: impl IntoIterator<Item = S>) -> Self {
+        AtRule::LayerOrder(names.into_iter().map(Into::into).collect())
+    }
+
+    pub fn layer(name: impl Into<String>, set: RuleSet) -> Self {
+        AtRule::Layer(Some(name.into()), set)
+    }
+
+    pub fn container(query: ContainerQuery, set: RuleSet) -> Self {
+        AtRule::Container(query, set)
+    }
+
+    pub fn generic(
+        name: impl Into<String>,
+        prelude: impl Into<String>,
+        block: Option<AtRuleBlock>,
+    ) -> Self {
+        AtRule::Generic {
+            name: name.into(),
+            prelude: prelude.into(),
+            block,
+        }
+    }
+
+    // an at-rule without a block, e.g. @namespace svg url(...);
+    pub fn statement(name: impl Into<String>, prelude: impl Into<String>) -> Self {
+        Self::generic(name, prelude, None)
+    }
+}
+
+impl From<FontFace> for AtRule {
+    fn from(font_face: FontFace) -> Self {
+        AtRule::FontFace(font_face)
+    }
+}
+
+impl From<PageRule> for AtRule {
+    fn from(page: PageRule) -> Self {
+        AtRule::Page(page)
+    }
+}
+
+impl fmt::Display for AtRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render_formatted(f, &CssFormat::compact(), 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::at_rule::{
+        AtRule, AtRuleBlock, ContainerQuery, ContainerType, FontFace, FontSource, Keyframe,
+        KeyframeSelector, Keyframes, MarginBox, PagePseudoClass, PageRule, PageSelector,
+    };
+    use crate::css::{
+        CssFormat, Declaration, DeclarationValue, MediaCondition, MediaFeature, Rule, RuleSet,
+        Selector,
+    };
+    use crate::values::Unit;
+
+    fn fade() -> Keyframes {
+        Keyframes::new(
+            "fade".to_string(),
+            vec![
+                Keyframe::new(
+                    vec![KeyframeSelector::From],
+                    vec![Declaration::new(
+                        "opacity".to_string(),
+                        DeclarationValue::number(0),
+                    )],
+                ),
+                Keyframe::new(
+                    vec![KeyframeSelector::percentage(37.5), KeyframeSelector::To],
+                    vec![Declaration::new(
+                        "opacity".to_string(),
+                        DeclarationValue::number(1),
+                    )],
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn keyframes() {
+        let keyframes = AtRule::from(fade());
+
+        assert_eq!(
+            keyframes.to_string(),
+            "@keyframes fade{from{opacity:0;}37.5%,to{opacity:1;}}"
+        );
+        assert_eq!(keyframes.estimated_len(), keyframes.to_string().len());
+    }
+
+    #[test]
+    fn keyframes_in_rule_set() {
+        let set = RuleSet::new(
+            vec![Rule::new(
+                Selector::Class("toast".to_string()),
+                vec![Declaration::new(
+                    "animation-name".to_string(),
+                    DeclarationValue::keyword("fade"),
+                )],
+                vec![],
+            )],
+            vec![],
+            None,
+        )
+        .at_rule(fade());
+
+        assert_eq!(
+            set.to_string(),
+            "@keyframes fade{from{opacity:0;}37.5%,to{opacity:1;}}.toast{animation-name:fade;}"
+        );
+        assert_eq!(set.estimated_len(), set.to_string().len());
+        assert_eq!(
+            set.to_string_formatted(&CssFormat::expanded()),
+            "@keyframes fade {\n  from {\n    opacity: 0;\n  }\n  37.5%,to {\n    opacity: 1;\n  }\n}\n.toast {\n  animation-name: fade;\n}\n"
+        );
+    }
+
+    #[test]
+    fn font_face() {
+        let font_face = AtRule::from(
+            FontFace::new(
+                "Fira Sans".to_string(),
+                vec![
+                    FontSource::local("Fira Sans"),
+                    FontSource::url_with_format("/fonts/fira.woff2", "woff2"),
+                    FontSource::url("/fonts/fira.ttf"),
+                ],
+            )
+            .weight(DeclarationValue::space_list(vec![
+                DeclarationValue::number(100),
+                DeclarationValue::number(900),
+            ]))
+            .style(DeclarationValue::keyword("normal"))
+            .display(DeclarationValue::keyword("swap"))
+            .descriptor(Declaration::new(
+                "unicode-range".to_string(),
+                DeclarationValue::Basic("U+0000-00FF".to_string()),
+            )),
+        );
+
+        assert_eq!(
+            font_face.to_string(),
+            "@font-face{font-family:\"Fira Sans\";src:local(\"Fira Sans\"),url(\"/fonts/fira.woff2\") format(\"woff2\"),url(\"/fonts/fira.ttf\");font-weight:100 900;font-style:normal;font-display:swap;unicode-range:U+0000-00FF;}"
+        );
+        assert_eq!(font_face.estimated_len(), font_face.to_string().len());
+    }
+
+    #[test]
+    fn layers() {
+        let set = RuleSet::new(vec![], vec![], None)
+            .at_rule(AtRule::layer_order(["reset", "ui.base"]))
+            .at_rule(AtRule::layer(
+                "ui.base",
+                RuleSet::new(
+                    vec![Rule::new(
+                        Selector::Tag("button".to_string()),
+                        vec![Declaration::new(
+                            "cursor".to_string(),
+                            DeclarationValue::keyword("pointer"),
+                        )],
+                        vec![],
+                    )],
+                    vec![],
+                    None,
+                ),
+            ))
+            .at_rule(AtRule::Layer(None, RuleSet::new(vec![], vec![], None)));
+
+        assert_eq!(
+            set.to_string(),
+            "@layer reset,ui.base;@layer ui.base{button{cursor:pointer;}}@layer{}"
+        );
+        assert_eq!(set.estimated_len(), set.to_string().len());
+        assert_eq!(
+            set.to_string_formatted(&CssFormat::expanded()),
+            "@layer reset,ui.base;\n@layer ui.base {\n  button {\n    cursor: pointer;\n  }\n}\n@layer {\n}\n"
+        );
+    }
+
+    #[test]
+    fn container_queries() {
+        let card = RuleSet::new(
+            vec![Rule::new(
+                Selector::Class("card".to_string()),
+                vec![Declaration::new(
+                    "display".to_string(),
+                    DeclarationValue::keyword("grid"),
+                )],
+                vec![],
+            )],
+            vec![],
+            None,
+        );
+        let wide = MediaFeature::new("min-width".to_string(), "400px".to_string());
+        let tall = MediaFeature::new("min-height".to_string(), "200px".to_string());
+        let set = RuleSet::new(
+            vec![Rule::new(
+                Selector::Class("sidebar".to_string()),
+                vec![
+                    Declaration::container_type(ContainerType::InlineSize),
+                    Declaration::container_name(["sidebar"]),
+                ],
+                vec![],
+            )],
+            vec![],
+            None,
+        )
+        .at_rule(AtRule::container(
+            ContainerQuery::new(
+                Some("sidebar".to_string()),
+                vec![
+                    MediaCondition::Lone(wide.clone()),
+                    MediaCondition::Lone(tall.clone()),
+                ],
+            ),
+            card.clone(),
+        ))
+        .at_rule(AtRule::container(
+            ContainerQuery::new(
+                None,
+                vec![MediaCondition::or(vec![
+                    MediaCondition::Lone(wide),
+                    MediaCondition::negate(MediaCondition::Lone(tall)),
+                ])],
+            ),
+            card,
+        ));
+
+        assert_eq!(
+            set.to_string(),
+            "@container sidebar (min-width:400px) and (min-height:200px){.card{display:grid;}}@container ((min-width:400px) or (not (min-height:200px))){.card{display:grid;}}.sidebar{container-type:inline-size;container-name:sidebar;}"
+        );
+        assert_eq!(set.estimated_len(), set.to_string().len());
+        assert_eq!(
+            Declaration::container_name(["a", "b"]).to_string(),
+            "container-name:a b;"
+        );
+    }
+
+    #[test]
+    fn pages() {
+        let page = AtRule::from(
+            PageRule::new(
+                vec![
+                    PageSelector::pseudo(PagePseudoClass::First),
+                    PageSelector::new(Some("cover".to_string()), vec![PagePseudoClass::Left]),
+                ],
+                vec![Declaration::new(
+                    "margin".to_string(),
+                    DeclarationValue::length(1, Unit::In),
+                )],
+            )
+            .margin_box(
+                MarginBox::BottomCenter,
+                vec![Declaration::new(
+                    "content".to_string(),
+                    DeclarationValue::Function("counter".to_string(), vec!["page".to_string()]),
+                )],
+            ),
+        );
+
+        assert_eq!(
+            page.to_string(),
+            "@page :first,cover:left{margin:1in;@bottom-center{content:counter(page);}}"
+        );
+        assert_eq!(page.estimated_len(), page.to_string().len());
+        let set = RuleSet::new(vec![], vec![], None).at_rule(page);
+        assert_eq!(
+            set.to_string_formatted(&CssFormat::expanded()),
+            "@page :first,cover:left {\n  margin: 1in;\n  @bottom-center {\n    content: counter(page);\n  }\n}\n"
+        );
+        assert_eq!(
+            set.to_string_formatted(&CssFormat::minified()),
+            "@page :first,cover:left{margin:1in;@bottom-center{content:counter(page)}}"
+        );
+
+        let plain = PageRule::new(vec![], vec![]);
+        assert_eq!(plain.to_string(), "@page{}");
+        assert_eq!(AtRule::from(plain).estimated_len(), 7);
+    }
+
+    #[test]
+    fn generic_at_rules() {
+        let property = AtRule::generic(
+            "property",
+            "--angle",
+            Some(AtRuleBlock::Declarations(vec![
+                Declaration::new(
+                    "syntax".to_string(),
+                    DeclarationValue::Basic("\"<angle>\"".to_string()),
+                ),
+                Declaration::new("inherits".to_string(), DeclarationValue::keyword("false")),
+            ])),
+        );
+        let scope = AtRule::generic(
+            "scope",
+            "(.card) to (.content)",
+            Some(AtRuleBlock::Rules(RuleSet::new(
+                vec![Rule::new(
+                    Selector::Tag("img".to_string()),
+                    vec![Declaration::new(
+                        "border".to_string(),
+                        DeclarationValue::number(0),
+                    )],
+                    vec![],
+                )],
+                vec![],
+                None,
+            ))),
+        );
+        let set = RuleSet::new(vec![], vec![], None)
+            .at_rule(AtRule::statement(
+                "namespace",
+                "svg url(\"http://www.w3.org/2000/svg\")",
+            ))
+            .at_rule(property)
+            .at_rule(scope)
+            .at_rule(AtRule::generic(
+                "starting-style",
+                "",
+                Some(AtRuleBlock::Rules(RuleSet::new(vec![], vec![], None))),
+            ));
+
+        assert_eq!(
+            set.to_string(),
+            "@namespace svg url(\"http://www.w3.org/2000/svg\");@property --angle{syntax:\"<angle>\";inherits:false;}@scope (.card) to (.content){img{border:0;}}@starting-style{}"
+        );
+        assert_eq!(set.estimated_len(), set.to_string().len());
+        assert_eq!(
+            set.to_string_formatted(&CssFormat::expanded()),
+            "@namespace svg url(\"http://www.w3.org/2000/svg\");\n@property --angle {\n  syntax: \"<angle>\";\n  inherits: false;\n}\n@scope (.card) to (.content) {\n  img {\n    border: 0;\n  }\n}\n@starting-style {\n}\n"
+        );
+    }
+}
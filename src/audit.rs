@@ -0,0 +1,511 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::html::{Attribute, Node};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Finding {
+    severity: Severity,
+    code: &'static str,
+    message: String,
+    location: String,
+}
+
+impl Finding {
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    // path of tags from the root to the offending element (e.g. html>body>img)
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct AuditReport {
+    findings: Vec<Finding>,
+}
+
+impl AuditReport {
+    pub fn findings(&self) -> &[Finding] {
+        &self.findings
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == severity)
+            .count()
+    }
+
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.findings.iter().map(|f| f.severity).max()
+    }
+
+    // true if any finding is at or above the threshold, for failing CI builds
+    pub fn fails(&self, threshold: Severity) -> bool {
+        self.findings.iter().any(|f| f.severity >= threshold)
+    }
+
+    fn add(&mut self, severity: Severity, code: &'static str, message: String, location: &str) {
+        self.findings.push(Finding {
+            severity,
+            code,
+            message,
+            location: location.to_string(),
+        });
+    }
+}
+
+fn find_attribute<'a>(attributes: &'a [Attribute], name: &str) -> Option<&'a Attribute> {
+    attributes
+        .iter()
+        .find(|a| a.name().eq_ignore_ascii_case(name))
+}
+
+fn attribute_value<'a>(attributes: &'a [Attribute], name: &str) -> Option<&'a str> {
+    find_attribute(attributes, name).and_then(Attribute::value)
+}
+
+fn has_attribute(attributes: &[Attribute], name: &str) -> bool {
+    find_attribute(attributes, name).is_some()
+}
+
+fn text_content(node: &Node, output: &mut String) {
+    match node {
+        Node::Text(text) => output.push_str(text),
+        Node::Element {
+            tag,
+            attributes,
+            children,
+            ..
+        } => match tag.eq_ignore_ascii_case("img") {
+            true => output.push_str(attribute_value(attributes, "alt").unwrap_or("")),
+            false => children.iter().for_each(|c| text_content(c, output)),
+        },
+        Node::Fragment(children) => children.iter().for_each(|c| text_content(c, output)),
+        _ => (),
+    }
+}
+
+fn has_accessible_name(attributes: &[Attribute], children: &[Node]) -> bool {
+    if has_attribute(attributes, "aria-label") || has_attribute(attributes, "aria-labelledby") {
+        return true;
+    }
+
+    let mut text = String::new();
+    children.iter().for_each(|c| text_content(c, &mut text));
+    !text.trim().is_empty()
+}
+
+fn heading_level(tag: &str) -> Option<u8> {
+    match tag.to_ascii_lowercase().as_str() {
+        "h1" => Some(1),
+        "h2" => Some(2),
+        "h3" => Some(3),
+        "h4" => Some(4),
+        "h5" => Some(5),
+        "h6" => Some(6),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct AuditState {
+    report: AuditReport,
+    last_heading: Option<u8>,
+    h1_count: usize,
+    has_html: bool,
+    has_title: bool,
+    has_description: bool,
+    has_canonical: bool,
+    label_targets: Vec<String>,
+    labelable_inputs: Vec<(Option<String>, String, String)>, // (id, tag, location)
+}
+
+// audits a full page rooted at the html element
+pub fn audit(root: &Node) -> AuditReport {
+    let mut state = AuditState::default();
+    visit(root, "", &mut state);
+
+    if !state.has_html {
+        state.report.add(
+            Severity::Warning,
+            "document-root",
+            "no html element found".to_string(),
+            "",
+        );
+    }
+
+    if !state.has_title {
+        state.report.add(
+            Severity::Error,
+            "document-title",
+            "missing <title>".to_string(),
+            "",
+        );
+    }
+
+    if !state.has_description {
+        state.report.add(
+            Severity::Warning,
+            "meta-description",
+            "missing <meta name=\"description\">".to_string(),
+            "",
+        );
+    }
+
+    if !state.has_canonical {
+        state.report.add(
+            Severity::Info,
+            "canonical-link",
+            "missing <link rel=\"canonical\">".to_string(),
+            "",
+        );
+    }
+
+    match state.h1_count {
+        0 => state.report.add(
+            Severity::Warning,
+            "heading-h1",
+            "page has no <h1>".to_string(),
+            "",
+        ),
+        1 => (),
+        n => state.report.add(
+            Severity::Warning,
+            "heading-h1",
+            format!("page has {} <h1> elements", n),
+            "",
+        ),
+    }
+
+    for (id, tag, location) in state.labelable_inputs {
+        match id {
+            Some(id) if state.label_targets.contains(&id) => (),
+            Some(id) => state.report.add(
+                Severity::Error,
+                "input-label",
+                format!("form control #{} has no associated label", id),
+                &location,
+            ),
+            None => state.report.add(
+                Severity::Error,
+                "input-label",
+                format!("<{}> has no id or aria-label to associate a label", tag),
+                &location,
+            ),
+        }
+    }
+
+    state.report
+}
+
+fn visit(node: &Node, parent_location: &str, state: &mut AuditState) {
+    let (tag, attributes, children) = match node {
+        Node::Element {
+            tag,
+            attributes,
+            children,
+            ..
+        } => (tag.to_ascii_lowercase(), attributes, children),
+        Node::Fragment(children) => {
+            children
+                .iter()
+                .for_each(|c| visit(c, parent_location, state));
+            return;
+        }
+        _ => return,
+    };
+
+    let location = match parent_location.is_empty() {
+        true => tag.clone(),
+        false => format!("{}>{}", parent_location, tag),
+    };
+    let report = &mut state.report;
+
+    match tag.as_str() {
+        "html" => {
+            state.has_html = true;
+            if !has_attribute(attributes, "lang") {
+                report.add(
+                    Severity::Warning,
+                    "html-lang",
+                    "<html> is missing a lang attribute".to_string(),
+                    &location,
+                );
+            }
+        }
+        "title" => state.has_title = true,
+        "meta" => {
+            state.has_description |= attribute_value(attributes, "name")
+                .is_some_and(|n| n.eq_ignore_ascii_case("description"))
+        }
+        "link" => {
+            state.has_canonical |= attribute_value(attributes, "rel")
+                .is_some_and(|r| r.eq_ignore_ascii_case("canonical"))
+        }
+        "img" => {
+            if !has_attribute(attributes, "alt") {
+                report.add(
+                    Severity::Error,
+                    "img-alt",
+                    "<img> is missing an alt attribute".to_string(),
+                    &location,
+                );
+            }
+            if !has_attribute(attributes, "width") || !has_attribute(attributes, "height") {
+                report.add(
+                    Severity::Info,
+                    "img-dimensions",
+                    "<img> without width and height causes layout shift".to_string(),
+                    &location,
+                );
+            }
+            if !has_attribute(attributes, "loading") {
+                report.add(
+                    Severity::Info,
+                    "img-loading",
+                    "<img> without a loading attribute, consider loading=\"lazy\"".to_string(),
+                    &location,
+                );
+            }
+            if let Some(src) = attribute_value(attributes, "src") {
+                let src = src.to_ascii_lowercase();
+                if src.ends_with(".bmp") || src.ends_with(".tif") || src.ends_with(".tiff") {
+                    report.add(
+                        Severity::Warning,
+                        "img-format",
+                        format!("<img> uses an uncompressed format ({})", src),
+                        &location,
+                    );
+                }
+            }
+        }
+        "a" => {
+            match attribute_value(attributes, "href").map(str::trim) {
+                None => (),
+                Some("") | Some("#") => report.add(
+                    Severity::Warning,
+                    "link-href",
+                    "link has an empty href".to_string(),
+                    &location,
+                ),
+                Some(href) if href.to_ascii_lowercase().starts_with("javascript:") => report.add(
+                    Severity::Warning,
+                    "link-href",
+                    "link uses a javascript: url".to_string(),
+                    &location,
+                ),
+                Some(_) => (),
+            }
+            if attribute_value(attributes, "target") == Some("_blank") {
+                let rel = attribute_value(attributes, "rel").unwrap_or("");
+                if !rel
+                    .split_whitespace()
+                    .any(|r| r == "noopener" || r == "noreferrer")
+                {
+                    report.add(
+                        Severity::Warning,
+                        "link-target-blank",
+                        "target=\"_blank\" link without rel=\"noopener\"".to_string(),
+                        &location,
+                    );
+                }
+            }
+            if !has_accessible_name(attributes, children) {
+                report.add(
+                    Severity::Error,
+                    "link-name",
+                    "link has no discernible text".to_string(),
+                    &location,
+                );
+            }
+        }
+        "button" if !has_accessible_name(attributes, children) => report.add(
+            Severity::Error,
+            "button-name",
+            "button has no discernible text".to_string(),
+            &location,
+        ),
+        "label" => {
+            if let Some(target) = attribute_value(attributes, "for") {
+                state.label_targets.push(target.to_string());
+            }
+        }
+        "input" | "select" | "textarea" => {
+            let input_type = attribute_value(attributes, "type").unwrap_or("text");
+            let labelled = has_attribute(attributes, "aria-label")
+                || has_attribute(attributes, "aria-labelledby");
+            let needs_label = !matches!(input_type, "hidden" | "submit" | "button" | "reset");
+
+            if needs_label && !labelled {
+                state.labelable_inputs.push((
+                    attribute_value(attributes, "id").map(str::to_string),
+                    tag.clone(),
+                    location.clone(),
+                ));
+            }
+        }
+        _ => (),
+    }
+
+    if let Some(level) = heading_level(&tag) {
+        if level == 1 {
+            state.h1_count += 1;
+        }
+        if let Some(last) = state.last_heading {
+            if level > last + 1 {
+                state.report.add(
+                    Severity::Warning,
+                    "heading-order",
+                    format!("heading level skipped from h{} to h{}", last, level),
+                    &location,
+                );
+            }
+        }
+        state.last_heading = Some(level);
+    }
+
+    // labels wrapping their control count as a label for it
+    let wraps_control = tag == "label";
+    let pending = state.labelable_inputs.len();
+
+    for child in children {
+        visit(child, &location, state);
+    }
+
+    if wraps_control {
+        state.labelable_inputs.truncate(pending);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::audit::{audit, Severity};
+    use crate::html::Node;
+
+    fn page(body: Node) -> Node {
+        crate::html! {
+            html lang="en" {
+                head {
+                    title { "Title" }
+                    meta name="description" content="About";
+                    link rel="canonical" href="https://x.test/";
+                }
+                body { (body) }
+            }
+        }
+    }
+
+    fn codes(root: &Node) -> Vec<&'static str> {
+        audit(root).findings().iter().map(|f| f.code()).collect()
+    }
+
+    #[test]
+    fn clean_page() {
+        let root = page(crate::html! {
+            h1 { "Heading" }
+            a href="/about" { "About" }
+        });
+
+        assert_eq!(audit(&root).findings(), &[]);
+    }
+
+    #[test]
+    fn missing_head_metadata() {
+        let root = crate::html! { html { h1 { "Heading" } } };
+
+        assert_eq!(
+            codes(&root),
+            vec![
+                "html-lang",
+                "document-title",
+                "meta-description",
+                "canonical-link"
+            ]
+        );
+        assert!(audit(&root).fails(Severity::Error));
+    }
+
+    #[test]
+    fn image_checks() {
+        let root = page(crate::html! {
+            h1 { "Heading" }
+            img src="photo.bmp";
+        });
+
+        let report = audit(&root);
+        assert_eq!(
+            codes(&root),
+            vec!["img-alt", "img-dimensions", "img-loading", "img-format"]
+        );
+        assert_eq!(report.findings()[0].location(), "html>body>img");
+        assert_eq!(report.max_severity(), Some(Severity::Error));
+    }
+
+    #[test]
+    fn heading_order() {
+        let root = page(crate::html! {
+            h1 { "One" }
+            h3 { "Three" }
+            h1 { "Another one" }
+        });
+
+        assert_eq!(codes(&root), vec!["heading-order", "heading-h1"]);
+    }
+
+    #[test]
+    fn link_issues() {
+        let root = page(crate::html! {
+            h1 { "Heading" }
+            a href="#" { "Empty" }
+            a href="javascript:void(0)" { "Script" }
+            a href="https://x.test" target="_blank" { "New tab" }
+            a href="/icon" {}
+        });
+
+        assert_eq!(
+            codes(&root),
+            vec!["link-href", "link-href", "link-target-blank", "link-name"]
+        );
+        assert_eq!(audit(&root).count(Severity::Warning), 3);
+    }
+
+    #[test]
+    fn form_labels() {
+        let root = page(crate::html! {
+            h1 { "Heading" }
+            label for="name" { "Name" }
+            input id="name";
+            label { "Email" input id="email"; }
+            label { "Country" div { select {} } }
+            input id="age";
+            input type="hidden";
+            textarea {}
+        });
+
+        let report = audit(&root);
+        assert_eq!(codes(&root), vec!["input-label", "input-label"]);
+        assert_eq!(report.findings()[0].location(), "html>body>input");
+        assert_eq!(report.findings()[1].location(), "html>body>textarea");
+        assert!(report.fails(Severity::Error));
+    }
+}
@@ -0,0 +1,313 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::css::Declaration;
+use crate::html::{Attribute, Node};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Element {
+    tag: String,
+    attributes: Vec<Attribute>,
+    children: Vec<Node>,
+}
+
+impl Element {
+    pub fn new(tag: impl Into<String>) -> Self {
+        Self {
+            tag: tag.into(),
+            attributes: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    // replaces an attribute of the same name
+    pub fn attr(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let name = name.into();
+        self.attributes.retain(|a| a.name() != name);
+        self.attributes.push(Attribute::new(name, value.into()));
+        self
+    }
+
+    pub fn toggle(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        self.attributes.retain(|a| a.name() != name);
+        self.attributes.push(Attribute::toggle(name));
+        self
+    }
+
+    // replaces any style set before
+    pub fn style(mut self, declarations: Vec<Declaration>) -> Self {
+        self.attributes.retain(|a| a.name() != "style");
+        self.attributes.push(Attribute::style(declarations));
+        self
+    }
+
+    pub fn id(self, id: impl Into<String>) -> Self {
+        self.attr("id", id)
+    }
+
+    // added to any classes already set
+    pub fn class(self, class: impl Into<String>) -> Self {
+        let class = class.into();
+        let existing = self
+            .attributes
+            .iter()
+            .find(|a| a.name() == "class")
+            .and_then(Attribute::value)
+            .filter(|v| !v.is_empty())
+            .map(ToString::to_string);
+
+        match existing {
+            Some(existing) => self.attr("class", format!("{} {}", existing, class)),
+            None => self.attr("class", class),
+        }
+    }
+
+    pub fn child(mut self, child: impl Into<Node>) -> Self {
+        self.children.push(child.into());
+        self
+    }
+
+    pub fn children<N: Into<Node>>(mut self, children: impl IntoIterator<Item = N>) -> Self {
+        self.children.extend(children.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.child(Node::text(text.into()))
+    }
+
+    pub fn build(self) -> Node {
+        Node::element(self.tag, self.attributes, self.children)
+    }
+}
+
+impl From<Element> for Node {
+    fn from(element: Element) -> Self {
+        element.build()
+    }
+}
+
+impl From<&str> for Node {
+    fn from(text: &str) -> Self {
+        Node::text(text.to_string())
+    }
+}
+
+impl From<String> for Node {
+    fn from(text: String) -> Self {
+        Node::text(text)
+    }
+}
+
+impl From<Vec<Node>> for Node {
+    fn from(children: Vec<Node>) -> Self {
+        Node::fragment(children)
+    }
+}
+
+// used by the html! macro
+#[doc(hidden)]
+pub fn __nodes() -> Vec<Node> {
+    Vec::new()
+}
+
+#[doc(hidden)]
+pub fn __to_string<T: fmt::Display + ?Sized>(value: &T) -> String {
+    value.to_string()
+}
+
+// several roots become a fragment
+#[doc(hidden)]
+pub fn __root(nodes: Vec<Node>) -> Node {
+    let mut nodes = nodes;
+    match nodes.len() {
+        1 => nodes.remove(0),
+        _ => Node::fragment(nodes),
+    }
+}
+
+// html like syntax for writing nodes by hand
+//   html! { div class="card" id=(id) { h1 { "Title" } input disabled; (node) } }
+// text is written as literals, expressions in parentheses are converted with Node::from for
+// children and Display for attribute values, and elements without children end with ;
+#[macro_export]
+macro_rules! html {
+    (@children $nodes:ident;) => {};
+    (@children $nodes:ident; $text:literal $($rest:tt)*) => {
+        $nodes.push($crate::Node::text($crate::builder::__to_string(&$text)));
+        $crate::html!(@children $nodes; $($rest)*);
+    };
+    (@children $nodes:ident; ($node:expr) $($rest:tt)*) => {
+        $nodes.push($crate::Node::from($node));
+        $crate::html!(@children $nodes; $($rest)*);
+    };
+    (@children $nodes:ident; $tag:ident $($rest:tt)*) => {
+        $crate::html!(@tag $nodes; [$tag] $($rest)*);
+    };
+
+    // tag names and attribute names are collected first since they can contain dashes
+    (@tag $nodes:ident; [$($tag:tt)+] - $more:ident $($rest:tt)*) => {
+        $crate::html!(@tag $nodes; [$($tag)+ - $more] $($rest)*);
+    };
+    (@tag $nodes:ident; [$($tag:tt)+] $($rest:tt)*) => {
+        $crate::html!(@element $nodes; [$($tag)+] [] $($rest)*);
+    };
+
+    (@element $nodes:ident; [$($tag:tt)+] [$($attributes:tt)*] { $($children:tt)* } $($rest:tt)*) => {
+        $crate::html!(@build $nodes; [$($tag)+] [$($attributes)*] [$($children)*]);
+        $crate::html!(@children $nodes; $($rest)*);
+    };
+    (@element $nodes:ident; [$($tag:tt)+] [$($attributes:tt)*] ; $($rest:tt)*) => {
+        $crate::html!(@build $nodes; [$($tag)+] [$($attributes)*] []);
+        $crate::html!(@children $nodes; $($rest)*);
+    };
+    (@element $nodes:ident; [$($tag:tt)+] [$($attributes:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::html!(@element $nodes; [$($tag)+] [$($attributes)* $next] $($rest)*);
+    };
+
+    (@build $nodes:ident; [$first:ident $(- $more:ident)*] [$($attributes:tt)*] [$($children:tt)*]) => {{
+        #[allow(unused_mut)]
+        let mut element = $crate::Element::new(concat!(stringify!($first) $(, "-", stringify!($more))*));
+        $crate::html!(@attributes element; $($attributes)*);
+        #[allow(unused_mut)]
+        let mut children = $crate::builder::__nodes();
+        $crate::html!(@children children; $($children)*);
+        $nodes.push(element.children(children).build());
+    }};
+
+    (@attributes $element:ident;) => {};
+    (@attributes $element:ident; $name:ident $($rest:tt)*) => {
+        $crate::html!(@attribute $element; [$name] $($rest)*);
+    };
+    (@attribute $element:ident; [$($name:tt)+] - $more:ident $($rest:tt)*) => {
+        $crate::html!(@attribute $element; [$($name)+ - $more] $($rest)*);
+    };
+    (@attribute $element:ident; [$first:ident $(- $more:ident)*] = $value:tt $($rest:tt)*) => {
+        $element = $element.attr(
+            concat!(stringify!($first) $(, "-", stringify!($more))*),
+            $crate::builder::__to_string(&$value),
+        );
+        $crate::html!(@attributes $element; $($rest)*);
+    };
+    (@attribute $element:ident; [$first:ident $(- $more:ident)*] $($rest:tt)*) => {
+        $element = $element.toggle(concat!(stringify!($first) $(, "-", stringify!($more))*));
+        $crate::html!(@attributes $element; $($rest)*);
+    };
+
+    ($($tokens:tt)+) => {{
+        let mut nodes = $crate::builder::__nodes();
+        $crate::html!(@children nodes; $($tokens)+);
+        $crate::builder::__root(nodes)
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Element;
+    use crate::css::{Declaration, DeclarationValue};
+    use crate::html::{Attribute, Node};
+
+    #[test]
+    fn same_as_constructors() {
+        let built = Element::new("div")
+            .class("card")
+            .attr("id", "x")
+            .child(Element::new("h1").text("Title"))
+            .child("Some text")
+            .build();
+
+        let constructed = Node::element(
+            "div".to_string(),
+            vec![
+                Attribute::new("class".to_string(), "card".to_string()),
+                Attribute::new("id".to_string(), "x".to_string()),
+            ],
+            vec![
+                Node::element(
+                    "h1".to_string(),
+                    vec![],
+                    vec![Node::text("Title".to_string())],
+                ),
+                Node::text("Some text".to_string()),
+            ],
+        );
+
+        assert_eq!(built, constructed);
+    }
+
+    #[test]
+    fn classes_are_combined() {
+        let node = Element::new("p").class("a").class("b").build();
+
+        assert_eq!(node.to_string(), "<p class=\"a b\"></p>");
+    }
+
+    #[test]
+    fn attributes_are_replaced() {
+        let node = Element::new("input")
+            .attr("type", "text")
+            .toggle("disabled")
+            .attr("type", "email")
+            .build();
+
+        assert_eq!(node.to_string(), "<input disabled type=\"email\">");
+    }
+
+    #[test]
+    fn style_from_declarations() {
+        let node = Element::new("p")
+            .style(vec![Declaration::new(
+                "color".to_string(),
+                DeclarationValue::Basic("red".to_string()),
+            )])
+            .build();
+
+        assert_eq!(node.to_string(), "<p style=\"color:red;\"></p>");
+    }
+
+    #[test]
+    fn children_from_iterator() {
+        let node = Element::new("ul")
+            .children(["a", "b"].map(|item| Element::new("li").text(item)))
+            .build();
+
+        assert_eq!(node.to_string(), "<ul><li>a</li><li>b</li></ul>");
+    }
+
+    #[test]
+    fn html_macro() {
+        let id = 7;
+        let footer = Element::new("footer").text("end");
+        let node = crate::html! {
+            div class="card" data-id=(id) {
+                h1 { "Title & more" }
+                input type="checkbox" aria-hidden checked;
+                my-icon name="star" {}
+                (footer)
+                42
+            }
+        };
+
+        assert_eq!(
+            node.to_string(),
+            "<div class=\"card\" data-id=\"7\"><h1>Title &amp; more</h1><input type=\"checkbox\" aria-hidden checked><my-icon name=\"star\"></my-icon><footer>end</footer>42</div>"
+        );
+    }
+
+    #[test]
+    fn html_macro_several_roots() {
+        let items = vec![Node::text("b".to_string())];
+        let node = crate::html! { li { "a" } li { (items) } };
+
+        assert_eq!(node.to_string(), "<li>a</li><li>b</li>");
+        assert_eq!(node.children().len(), 2);
+    }
+
+    #[test]
+    fn html_macro_text_root() {
+        assert_eq!(crate::html! { "text" }, Node::text("text".to_string()));
+    }
+}
@@ -0,0 +1,686 @@
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::at_rule::{AtRule, AtRuleBlock};
+use crate::css::{MediaQuery, PseudoFunction, Rule, RuleSet, Selector};
+use crate::html::{Attribute, Node};
+use crate::order::family;
+use crate::query::depends_on_tree;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum StyleStrategy {
+    Inline,                    // all styles in a <style> element
+    External { href: String }, // all styles in a separate file, linked from the head
+    Split { href: String },    // critical styles inline, the rest loaded asynchronously
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RenderedPage {
+    html: String,
+    css: Option<String>,
+}
+
+impl RenderedPage {
+    pub fn html(&self) -> &str {
+        &self.html
+    }
+
+    // contents of the separate stylesheet file, if the strategy produces one
+    pub fn css(&self) -> Option<&str> {
+        self.css.as_deref()
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Bundle {
+    page: Node,
+    critical_styles: Vec<RuleSet>,
+    styles: Vec<RuleSet>,
+}
+
+impl Bundle {
+    pub fn new(page: Node) -> Self {
+        Self {
+            page,
+            critical_styles: vec![],
+            styles: vec![],
+        }
+    }
+
+    pub fn add_styles(&mut self, styles: RuleSet) {
+        self.styles.push(styles);
+    }
+
+    // styles needed for first paint, only treated differently by StyleStrategy::Split
+    pub fn add_critical_styles(&mut self, styles: RuleSet) {
+        self.critical_styles.push(styles);
+    }
+
+    fn css(sets: &[RuleSet]) -> String {
+        sets.iter().map(RuleSet::to_string).collect()
+    }
+
+    pub fn render(&self, strategy: &StyleStrategy) -> RenderedPage {
+        let all_css = || {
+            let mut css = Self::css(&self.critical_styles);
+            css.push_str(&Self::css(&self.styles));
+            css
+        };
+
+        let (head_nodes, css) = match strategy {
+            StyleStrategy::Inline => (vec![style_node(all_css())], None),
+            StyleStrategy::External { href } => (vec![stylesheet_link(href)], Some(all_css())),
+            StyleStrategy::Split { href } => (
+                vec![
+                    style_node(Self::css(&self.critical_styles)),
+                    deferred_stylesheet_link(href),
+                    Node::element("noscript".to_string(), vec![], vec![stylesheet_link(href)]),
+                ],
+                Some(Self::css(&self.styles)),
+            ),
+        };
+
+        RenderedPage {
+            html: render_with_head_nodes(&self.page, head_nodes),
+            css,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RenderedSite {
+    pages: Vec<(String, String)>, // (path, html)
+    shared_css: String,
+}
+
+impl RenderedSite {
+    pub fn pages(&self) -> &[(String, String)] {
+        &self.pages
+    }
+
+    pub fn page(&self, path: &str) -> Option<&str> {
+        self.pages
+            .iter()
+            .find(|(p, _)| p == path)
+            .map(|(_, html)| html.as_str())
+    }
+
+    // contents of the stylesheet linked from every page, empty if no rules are shared
+    pub fn shared_css(&self) -> &str {
+        &self.shared_css
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SiteCollection {
+    shared_href: String,
+    pages: Vec<(String, Node, Vec<RuleSet>)>, // (path, page, styles)
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord)]
+enum StyleItem {
+    AtRule(AtRule),
+    Rule(Rule),
+}
+
+// a rule or at-rule along with the media query it applies under
+type StyleUnit = (Option<MediaQuery>, StyleItem);
+
+impl SiteCollection {
+    pub fn new(shared_href: String) -> Self {
+        Self {
+            shared_href,
+            pages: vec![],
+        }
+    }
+
+    pub fn add_page(&mut self, path: String, page: Node, styles: Vec<RuleSet>) {
+        self.pages.push((path, page, styles));
+    }
+
+    // rules used by more than one page go into the shared stylesheet, the rest stay inline on their page.
+    // the shared stylesheet is linked before the inline styles, so a rule only moves there when it
+    // can not override anything the page had before it
+    pub fn render(&self) -> RenderedSite {
+        let page_units = self
+            .pages
+            .iter()
+            .map(|(_, _, styles)| {
+                let mut units = vec![];
+                styles
+                    .iter()
+                    .for_each(|s| flatten_styles(s, None, &mut units));
+                units
+            })
+            .collect::<Vec<Vec<StyleUnit>>>();
+
+        let shared = shared_units(&page_units);
+        let is_shared = |unit: &StyleUnit| shared.contains(unit);
+        let shared_css = css_for_units(&shared);
+
+        let pages = self
+            .pages
+            .iter()
+            .zip(&page_units)
+            .map(|((path, page, _), units)| {
+                let remainder = units
+                    .iter()
+                    .filter(|u| !is_shared(u))
+                    .cloned()
+                    .collect::<Vec<StyleUnit>>();
+
+                let mut head_nodes = vec![];
+                if !shared_css.is_empty() {
+                    head_nodes.push(stylesheet_link(&self.shared_href));
+                }
+                if !remainder.is_empty() {
+                    head_nodes.push(style_node(css_for_units(&remainder)));
+                }
+
+                (path.clone(), render_with_head_nodes(page, head_nodes))
+            })
+            .collect();
+
+        RenderedSite { pages, shared_css }
+    }
+}
+
+// properties a rule sets, including the ones of its nested rules
+fn properties<'a>(rule: &'a Rule, output: &mut Vec<&'a str>) {
+    output.extend(rule.declarations().iter().map(|d| d.property()));
+    rule.sub_rules()
+        .iter()
+        .for_each(|sub_rule| properties(sub_rule, output));
+}
+
+// whether the order of two units can change what applies, rules can only override each other
+// when they set properties of the same family, at-rules holding rules are treated like rules
+// setting everything
+fn conflicts(first: &StyleUnit, second: &StyleUnit) -> bool {
+    let holds_rules = |at_rule: &AtRule| {
+        matches!(
+            at_rule,
+            AtRule::Layer(..)
+                | AtRule::Container(..)
+                | AtRule::Generic {
+                    block: Some(AtRuleBlock::Rules(_)),
+                    ..
+                }
+        )
+    };
+    match (&first.1, &second.1) {
+        (StyleItem::AtRule(_), StyleItem::AtRule(_)) => true,
+        (StyleItem::AtRule(at_rule), StyleItem::Rule(_))
+        | (StyleItem::Rule(_), StyleItem::AtRule(at_rule)) => holds_rules(at_rule),
+        (StyleItem::Rule(first), StyleItem::Rule(second)) => {
+            let (mut first_properties, mut second_properties) = (vec![], vec![]);
+            properties(first, &mut first_properties);
+            properties(second, &mut second_properties);
+            first_properties.iter().any(|first| {
+                second_properties.iter().any(|second| {
+                    first.eq_ignore_ascii_case("all")
+                        || second.eq_ignore_ascii_case("all")
+                        || family(first).eq_ignore_ascii_case(family(second))
+                })
+            })
+        }
+    }
+}
+
+// units used by more than one page, in the order of the shared stylesheet. a unit is left out
+// when a page has a conflicting unit before it that stays inline or comes later in the sheet
+fn shared_units(page_units: &[Vec<StyleUnit>]) -> Vec<StyleUnit> {
+    let mut page_counts: BTreeMap<&StyleUnit, usize> = BTreeMap::new();
+    for units in page_units {
+        for unit in units.iter().collect::<BTreeSet<&StyleUnit>>() {
+            *page_counts.entry(unit).or_default() += 1;
+        }
+    }
+    let mut candidates = page_counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(unit, _)| unit)
+        .collect::<BTreeSet<&StyleUnit>>();
+
+    // leaving a unit out can make a later one conflict, so repeat until nothing changes
+    loop {
+        let mut shared: Vec<&StyleUnit> = vec![];
+        for unit in page_units.iter().flatten() {
+            if candidates.contains(unit) && !shared.contains(&unit) {
+                shared.push(unit);
+            }
+        }
+        let position = |unit: &StyleUnit| shared.iter().position(|u| *u == unit);
+
+        let mut blocked = BTreeSet::new();
+        for units in page_units {
+            for (i, unit) in units.iter().enumerate() {
+                if let Some(unit_position) = position(unit) {
+                    let is_blocked = units[..i].iter().any(|before| {
+                        position(before).is_none_or(|p| p > unit_position)
+                            && conflicts(before, unit)
+                    });
+                    if is_blocked {
+                        blocked.insert(unit);
+                    }
+                }
+            }
+        }
+
+        if blocked.is_empty() {
+            return shared.into_iter().cloned().collect();
+        }
+        candidates.retain(|unit| !blocked.contains(unit));
+    }
+}
+
+// pseudo elements and states like :hover are dropped so rules for them count when the element
+// they apply to is present, a :not() keeps its argument so :not(:hover) still matches
+fn loosened(selector: &Selector) -> Selector {
+    selector.replace(&|selector| match selector {
+        Selector::PseudoElement(base, _) => Some(loosened(base)),
+        Selector::PseudoClass(base, class) if !depends_on_tree(class) => Some(loosened(base)),
+        Selector::Functional(base, PseudoFunction::Not, argument) => Some(Selector::Functional(
+            Box::new(loosened(base)),
+            PseudoFunction::Not,
+            argument.clone(),
+        )),
+        _ => None,
+    })
+}
+
+fn is_used(rule: &Rule, selector: &Selector, page: &Node) -> bool {
+    !page.select(&loosened(selector)).is_empty()
+        || rule
+            .sub_rules()
+            .iter()
+            .any(|sub_rule| is_used(sub_rule, &sub_rule.selector().nested_in(selector), page))
+}
+
+fn is_empty(set: &RuleSet) -> bool {
+    set.rules().is_empty() && set.sub_sets().is_empty() && set.at_rules().is_empty()
+}
+
+// at-rules holding rules are split too and kept on both sides when both have rules,
+// the other at-rules stay critical as rules there may rely on them
+fn split_at_rule(at_rule: &AtRule, page: &Node) -> (Option<AtRule>, Option<AtRule>) {
+    let split = |set: &RuleSet, wrap: &dyn Fn(RuleSet) -> AtRule| {
+        let (critical, rest) = extract_critical(set, page);
+        let wrap = |set: RuleSet| (!is_empty(&set)).then(|| wrap(set));
+        (wrap(critical), wrap(rest))
+    };
+    match at_rule {
+        AtRule::Layer(name, set) => split(set, &|set| AtRule::Layer(name.clone(), set)),
+        AtRule::Container(query, set) => split(set, &|set| AtRule::Container(query.clone(), set)),
+        AtRule::Generic {
+            name,
+            prelude,
+            block: Some(AtRuleBlock::Rules(set)),
+        } => split(set, &|set| AtRule::Generic {
+            name: name.clone(),
+            prelude: prelude.clone(),
+            block: Some(AtRuleBlock::Rules(set)),
+        }),
+        at_rule => (Some(at_rule.clone()), None),
+    }
+}
+
+// (rules matching elements of the page, to inline in its head, the rest, to load later),
+// both keep the media queries and at-rules around their rules
+pub fn extract_critical(set: &RuleSet, page: &Node) -> (RuleSet, RuleSet) {
+    let (critical_rules, rest_rules): (Vec<Rule>, Vec<Rule>) = set
+        .rules()
+        .iter()
+        .cloned()
+        .partition(|rule| is_used(rule, rule.selector(), page));
+    let (critical_sets, rest_sets): (Vec<RuleSet>, Vec<RuleSet>) = set
+        .sub_sets()
+        .iter()
+        .map(|sub_set| extract_critical(sub_set, page))
+        .unzip();
+    let keep = |sets: Vec<RuleSet>| sets.into_iter().filter(|s| !is_empty(s)).collect();
+    let mut critical = RuleSet::new(
+        critical_rules,
+        keep(critical_sets),
+        set.media_query().cloned(),
+    );
+    let mut rest = RuleSet::new(rest_rules, keep(rest_sets), set.media_query().cloned());
+    for at_rule in set.at_rules() {
+        let (critical_part, rest_part) = split_at_rule(at_rule, page);
+        if let Some(at_rule) = critical_part {
+            critical = critical.at_rule(at_rule);
+        }
+        if let Some(at_rule) = rest_part {
+            rest = rest.at_rule(at_rule);
+        }
+    }
+    (critical, rest)
+}
+
+fn flatten_styles(set: &RuleSet, inherited: Option<&MediaQuery>, units: &mut Vec<StyleUnit>) {
+    let query = set.media_query().or(inherited);
+    for at_rule in set.at_rules() {
+        units.push((query.cloned(), StyleItem::AtRule(at_rule.clone())));
+    }
+    for rule in set.rules() {
+        units.push((query.cloned(), StyleItem::Rule(rule.clone())));
+    }
+    for sub_set in set.sub_sets() {
+        flatten_styles(sub_set, query, units);
+    }
+}
+
+// consecutive units under the same media query share one rule set so rule order is kept,
+// at-rules after rules start a new set since a set writes its at-rules first
+fn css_for_units(units: &[StyleUnit]) -> String {
+    let mut sets: Vec<(Option<MediaQuery>, Vec<AtRule>, Vec<Rule>)> = vec![];
+    for (query, item) in units {
+        match (sets.last_mut(), item) {
+            (Some((last_query, _, rules)), StyleItem::Rule(rule)) if last_query == query => {
+                rules.push(rule.clone())
+            }
+            (Some((last_query, at_rules, rules)), StyleItem::AtRule(at_rule))
+                if last_query == query && rules.is_empty() =>
+            {
+                at_rules.push(at_rule.clone())
+            }
+            (_, StyleItem::Rule(rule)) => sets.push((query.clone(), vec![], vec![rule.clone()])),
+            (_, StyleItem::AtRule(at_rule)) => {
+                sets.push((query.clone(), vec![at_rule.clone()], vec![]))
+            }
+        }
+    }
+
+    sets.into_iter()
+        .map(|(query, at_rules, rules)| {
+            at_rules
+                .into_iter()
+                .fold(RuleSet::new(rules, vec![], query), RuleSet::at_rule)
+                .to_string()
+        })
+        .collect()
+}
+
+fn render_with_head_nodes(page: &Node, head_nodes: Vec<Node>) -> String {
+    let mut page = page.clone();
+    match head_children(&mut page) {
+        Some(children) => {
+            children.extend(head_nodes);
+            page.to_string()
+        }
+        // no head to insert into, place styles before the page content
+        None => {
+            let mut html = head_nodes.iter().map(Node::to_string).collect::<String>();
+            html.push_str(&page.to_string());
+            html
+        }
+    }
+}
+
+fn style_node(css: String) -> Node {
+    Node::element("style".to_string(), vec![], vec![Node::text(css)])
+}
+
+fn stylesheet_link(href: &str) -> Node {
+    Node::element(
+        "link".to_string(),
+        vec![
+            Attribute::new("rel".to_string(), "stylesheet".to_string()),
+            Attribute::new("href".to_string(), href.to_string()),
+        ],
+        vec![],
+    )
+}
+
+fn deferred_stylesheet_link(href: &str) -> Node {
+    Node::element(
+        "link".to_string(),
+        vec![
+            Attribute::new("rel".to_string(), "preload".to_string()),
+            Attribute::new("as".to_string(), "style".to_string()),
+            Attribute::new("href".to_string(), href.to_string()),
+            Attribute::new(
+                "onload".to_string(),
+                "this.onload=null;this.rel='stylesheet'".to_string(),
+            ),
+        ],
+        vec![],
+    )
+}
+
+fn head_children(node: &mut Node) -> Option<&mut Vec<Node>> {
+    match node {
+        Node::Element { tag, children, .. } => match tag.eq_ignore_ascii_case("head") {
+            true => Some(children),
+            false => children.iter_mut().find_map(head_children),
+        },
+        Node::Fragment(children) => children.iter_mut().find_map(head_children),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bundle::{extract_critical, Bundle, SiteCollection, StyleStrategy};
+    use crate::css::{
+        Declaration, DeclarationValue, MediaConstraint, MediaQuery, Rule, RuleSet, Selector,
+    };
+    use crate::html::Node;
+
+    fn rule_set(tag: &str, color: &str) -> RuleSet {
+        RuleSet::new(
+            vec![Rule::new(
+                Selector::Tag(tag.to_string()),
+                vec![Declaration::new(
+                    "color".to_string(),
+                    DeclarationValue::Basic(color.to_string()),
+                )],
+                vec![],
+            )],
+            vec![],
+            None,
+        )
+    }
+
+    fn make_bundle(page: Node) -> Bundle {
+        let mut bundle = Bundle::new(page);
+        bundle.add_critical_styles(rule_set("body", "blue"));
+        bundle.add_styles(rule_set("footer", "gray"));
+        bundle
+    }
+
+    fn page() -> Node {
+        Node::element(
+            "html".to_string(),
+            vec![],
+            vec![
+                Node::element("head".to_string(), vec![], vec![]),
+                Node::element("body".to_string(), vec![], vec![]),
+            ],
+        )
+    }
+
+    #[test]
+    fn inline() {
+        let rendered = make_bundle(page()).render(&StyleStrategy::Inline);
+
+        assert_eq!(
+            rendered.html(),
+            "<html><head><style>body{color:blue;}footer{color:gray;}</style></head><body></body></html>"
+        );
+        assert_eq!(rendered.css(), None);
+    }
+
+    #[test]
+    fn external() {
+        let rendered = make_bundle(page()).render(&StyleStrategy::External {
+            href: "/site.css".to_string(),
+        });
+
+        assert_eq!(
+            rendered.html(),
+            "<html><head><link rel=\"stylesheet\" href=\"/site.css\"></head><body></body></html>"
+        );
+        assert_eq!(rendered.css(), Some("body{color:blue;}footer{color:gray;}"));
+    }
+
+    #[test]
+    fn split() {
+        let rendered = make_bundle(page()).render(&StyleStrategy::Split {
+            href: "/site.css".to_string(),
+        });
+
+        assert_eq!(
+            rendered.html(),
+            "<html><head><style>body{color:blue;}</style>\
+            <link rel=\"preload\" as=\"style\" href=\"/site.css\" onload=\"this.onload=null;this.rel='stylesheet'\">\
+            <noscript><link rel=\"stylesheet\" href=\"/site.css\"></noscript>\
+            </head><body></body></html>"
+        );
+        assert_eq!(rendered.css(), Some("footer{color:gray;}"));
+    }
+
+    #[test]
+    fn no_head() {
+        let rendered = make_bundle(Node::element("main".to_string(), vec![], vec![]))
+            .render(&StyleStrategy::Inline);
+
+        assert_eq!(
+            rendered.html(),
+            "<style>body{color:blue;}footer{color:gray;}</style><main></main>"
+        );
+    }
+
+    #[test]
+    fn site_shares_common_rules() {
+        let mut site = SiteCollection::new("/shared.css".to_string());
+        let print = RuleSet::new(
+            vec![],
+            vec![RuleSet::new(
+                vec![rule_set("nav", "black").rules()[0].clone()],
+                vec![],
+                Some(MediaQuery::new(
+                    MediaConstraint::None,
+                    "print".to_string(),
+                    vec![],
+                )),
+            )],
+            None,
+        );
+        site.add_page(
+            "/".to_string(),
+            page(),
+            vec![
+                rule_set("body", "blue"),
+                print.clone(),
+                rule_set("h1", "red"),
+            ],
+        );
+        site.add_page(
+            "/about".to_string(),
+            page(),
+            vec![rule_set("body", "blue"), print],
+        );
+        site.add_page("/plain".to_string(), page(), vec![rule_set("p", "gray")]);
+
+        let rendered = site.render();
+
+        assert_eq!(
+            rendered.shared_css(),
+            "body{color:blue;}@media print{nav{color:black;}}"
+        );
+        assert_eq!(
+            rendered.page("/"),
+            Some("<html><head><link rel=\"stylesheet\" href=\"/shared.css\"><style>h1{color:red;}</style></head><body></body></html>")
+        );
+        assert_eq!(
+            rendered.page("/about"),
+            Some("<html><head><link rel=\"stylesheet\" href=\"/shared.css\"></head><body></body></html>")
+        );
+        assert_eq!(
+            rendered.page("/plain"),
+            Some("<html><head><link rel=\"stylesheet\" href=\"/shared.css\"><style>p{color:gray;}</style></head><body></body></html>")
+        );
+    }
+
+    #[test]
+    fn site_keeps_cascade_order() {
+        let mut site = SiteCollection::new("/shared.css".to_string());
+        let styles = |css: &str| vec![RuleSet::parse(css).unwrap()];
+        site.add_page(
+            "/".to_string(),
+            page(),
+            styles(".x { color: red } .y { color: blue } .z { margin: 0 }"),
+        );
+        site.add_page(
+            "/b".to_string(),
+            page(),
+            styles(".y { color: blue } .z { margin: 0 }"),
+        );
+
+        let rendered = site.render();
+
+        assert_eq!(rendered.shared_css(), ".z{margin:0;}");
+        assert_eq!(
+            rendered.page("/"),
+            Some("<html><head><link rel=\"stylesheet\" href=\"/shared.css\"><style>.x{color:red;}.y{color:blue;}</style></head><body></body></html>")
+        );
+        assert_eq!(
+            rendered.page("/b"),
+            Some("<html><head><link rel=\"stylesheet\" href=\"/shared.css\"><style>.y{color:blue;}</style></head><body></body></html>")
+        );
+
+        let mut site = SiteCollection::new("/shared.css".to_string());
+        site.add_page(
+            "/".to_string(),
+            page(),
+            styles(".a { margin: 0 } .b { margin-top: 1px }"),
+        );
+        site.add_page(
+            "/b".to_string(),
+            page(),
+            styles(".b { margin-top: 1px } .a { margin: 0 }"),
+        );
+
+        let rendered = site.render();
+
+        assert_eq!(rendered.shared_css(), "");
+        assert_eq!(
+            rendered.page("/b"),
+            Some("<html><head><style>.b{margin-top:1px;}.a{margin:0;}</style></head><body></body></html>")
+        );
+    }
+
+    #[test]
+    fn critical_rules() {
+        let styles = RuleSet::parse(
+            "body { margin: 0 } .card:hover, .card::before { color: red } .modal { display: none } a:not(:visited) { color: blue } @media (min-width: 40em) { .card { padding: 1em } .modal { padding: 2em } } @layer base { p { margin: 0 } } @font-face { font-family: Inter; src: url(inter.woff2) }",
+        )
+        .unwrap();
+        let page = Node::element(
+            "body".to_string(),
+            vec![],
+            vec![Node::element(
+                "div".to_string(),
+                vec![crate::html::Attribute::new(
+                    "class".to_string(),
+                    "card".to_string(),
+                )],
+                vec![],
+            )],
+        );
+        let (critical, rest) = extract_critical(&styles, &page);
+
+        assert_eq!(
+            critical.to_string(),
+            "body{margin:0;}.card:hover,.card::before{color:red;}@media (min-width:40em){.card{padding:1em;}}@font-face{font-family:Inter;src:url(\"inter.woff2\");}"
+        );
+        assert_eq!(
+            rest.to_string(),
+            ".modal{display:none;}a:not(:visited){color:blue;}@media (min-width:40em){.modal{padding:2em;}}@layer base{p{margin:0;}}"
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::html::{Attribute, Node};
+
+// the words of a class attribute in order, each written once
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct ClassList {
+    classes: Vec<String>,
+}
+
+impl ClassList {
+    pub fn parse(value: &str) -> Self {
+        let mut list = Self::default();
+        list.add(value);
+        list
+    }
+
+    pub(crate) fn has_duplicates(value: &str) -> bool {
+        let mut words = value.split_whitespace();
+        let mut seen = Vec::new();
+        words.any(|word| match seen.contains(&word) {
+            true => true,
+            false => {
+                seen.push(word);
+                false
+            }
+        })
+    }
+
+    pub fn contains(&self, class: &str) -> bool {
+        self.classes.iter().any(|c| c == class)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.classes.iter().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    // whitespace separated classes are added one by one, false if all were already present
+    pub fn add(&mut self, classes: &str) -> bool {
+        let mut added = false;
+        for class in classes.split_whitespace() {
+            if !self.contains(class) {
+                self.classes.push(class.to_string());
+                added = true;
+            }
+        }
+        added
+    }
+
+    pub fn remove(&mut self, class: &str) -> bool {
+        let len = self.classes.len();
+        self.classes.retain(|c| c != class);
+        self.classes.len() != len
+    }
+
+    // true if the class is present afterwards
+    pub fn toggle(&mut self, class: &str) -> bool {
+        match self.remove(class) {
+            true => false,
+            false => self.add(class),
+        }
+    }
+}
+
+impl fmt::Display for ClassList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, class) in self.classes.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(class)?;
+        }
+        Ok(())
+    }
+}
+
+impl Node {
+    // empty for nodes without a class attribute
+    pub fn class_list(&self) -> ClassList {
+        self.attributes()
+            .iter()
+            .find(|a| a.name().eq_ignore_ascii_case("class"))
+            .and_then(Attribute::value)
+            .map(ClassList::parse)
+            .unwrap_or_default()
+    }
+
+    // an empty list removes the attribute, false if this is not an element
+    pub fn set_class_list(&mut self, list: &ClassList) -> bool {
+        match list.is_empty() {
+            true => {
+                self.remove_attribute("class");
+                self.tag().is_some()
+            }
+            false => self.set_attribute(Attribute::new("class".to_string(), list.to_string())),
+        }
+    }
+
+    pub fn has_class(&self, class: &str) -> bool {
+        self.class_list().contains(class)
+    }
+
+    // false if this is not an element or the class was already present
+    pub fn add_class(&mut self, class: &str) -> bool {
+        let mut list = self.class_list();
+        list.add(class) && self.set_class_list(&list)
+    }
+
+    pub fn remove_class(&mut self, class: &str) -> bool {
+        let mut list = self.class_list();
+        list.remove(class) && self.set_class_list(&list)
+    }
+
+    pub fn toggle_class(&mut self, class: &str) -> bool {
+        let mut list = self.class_list();
+        let present = list.toggle(class);
+        self.set_class_list(&list);
+        present
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::class::ClassList;
+    use crate::html::{Attribute, Node};
+
+    #[test]
+    fn class_list() {
+        let mut list = ClassList::parse("  card  active card ");
+
+        assert_eq!(list.to_string(), "card active");
+        assert!(!list.add("active"));
+        assert!(list.add("wide tall"));
+        assert!(list.remove("active"));
+        assert!(!list.toggle("wide"));
+        assert_eq!(list.iter().collect::<Vec<&str>>(), vec!["card", "tall"]);
+    }
+
+    #[test]
+    fn node_helpers() {
+        let mut node = Node::element("p".to_string(), vec![], vec![]);
+
+        assert!(node.add_class("intro"));
+        assert!(!node.add_class("intro"));
+        assert!(node.toggle_class("lead"));
+        assert!(node.has_class("lead"));
+        assert_eq!(node.to_string(), "<p class=\"intro lead\"></p>");
+
+        assert!(node.remove_class("intro"));
+        assert!(!node.toggle_class("lead"));
+        assert_eq!(node.to_string(), "<p></p>");
+
+        let mut text = Node::text("x".to_string());
+        assert!(!text.add_class("intro"));
+    }
+
+    #[test]
+    fn duplicates_are_written_once() {
+        let node = Node::element(
+            "p".to_string(),
+            vec![Attribute::new("class".to_string(), "a b a".to_string())],
+            vec![],
+        );
+
+        assert_eq!(node.to_string(), "<p class=\"a b\"></p>");
+        assert_eq!(node.estimated_len(), node.to_string().len());
+    }
+}
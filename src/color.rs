@@ -0,0 +1,386 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+#[rustfmt::skip]
+const NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff), ("antiquewhite", 0xfaebd7), ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4), ("azure", 0xf0ffff), ("beige", 0xf5f5dc), ("bisque", 0xffe4c4),
+    ("black", 0x000000), ("blanchedalmond", 0xffebcd), ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2), ("brown", 0xa52a2a), ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0), ("chartreuse", 0x7fff00), ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50), ("cornflowerblue", 0x6495ed), ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c), ("cyan", 0x00ffff), ("darkblue", 0x00008b), ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b), ("darkgray", 0xa9a9a9), ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9), ("darkkhaki", 0xbdb76b), ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f), ("darkorange", 0xff8c00), ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000), ("darksalmon", 0xe9967a), ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b), ("darkslategray", 0x2f4f4f), ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1), ("darkviolet", 0x9400d3), ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff), ("dimgray", 0x696969), ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff), ("firebrick", 0xb22222), ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22), ("fuchsia", 0xff00ff), ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff), ("gold", 0xffd700), ("goldenrod", 0xdaa520), ("gray", 0x808080),
+    ("green", 0x008000), ("greenyellow", 0xadff2f), ("grey", 0x808080), ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4), ("indianred", 0xcd5c5c), ("indigo", 0x4b0082), ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c), ("lavender", 0xe6e6fa), ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00), ("lemonchiffon", 0xfffacd), ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080), ("lightcyan", 0xe0ffff), ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3), ("lightgreen", 0x90ee90), ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1), ("lightsalmon", 0xffa07a), ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa), ("lightslategray", 0x778899), ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de), ("lightyellow", 0xffffe0), ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32), ("linen", 0xfaf0e6), ("magenta", 0xff00ff), ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa), ("mediumblue", 0x0000cd), ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db), ("mediumseagreen", 0x3cb371), ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a), ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585), ("midnightblue", 0x191970), ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1), ("moccasin", 0xffe4b5), ("navajowhite", 0xffdead),
+    ("navy", 0x000080), ("oldlace", 0xfdf5e6), ("olive", 0x808000), ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500), ("orangered", 0xff4500), ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa), ("palegreen", 0x98fb98), ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093), ("papayawhip", 0xffefd5), ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f), ("pink", 0xffc0cb), ("plum", 0xdda0dd), ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080), ("rebeccapurple", 0x663399), ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f), ("royalblue", 0x4169e1), ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072), ("sandybrown", 0xf4a460), ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee), ("sienna", 0xa0522d), ("silver", 0xc0c0c0), ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd), ("slategray", 0x708090), ("slategrey", 0x708090),
+    ("snow", 0xfffafa), ("springgreen", 0x00ff7f), ("steelblue", 0x4682b4), ("tan", 0xd2b48c),
+    ("teal", 0x008080), ("thistle", 0xd8bfd8), ("tomato", 0xff6347), ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee), ("wheat", 0xf5deb3), ("white", 0xffffff), ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00), ("yellowgreen", 0x9acd32),
+];
+
+fn default_alpha() -> u8 {
+    255
+}
+
+// an srgb color with 8 bits per channel, alpha included, written in its shortest form
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Color {
+    red: u8,
+    green: u8,
+    blue: u8,
+    #[serde(default = "default_alpha")]
+    alpha: u8,
+}
+
+// rounds to the nearest byte, core has no f64::round without std
+fn byte(value: f64) -> u8 {
+    (value.clamp(0.0, 255.0) + 0.5) as u8
+}
+
+fn channel(value: f64) -> u8 {
+    byte(value.clamp(0.0, 1.0) * 255.0)
+}
+
+fn hue_to_channel(p: f64, q: f64, hue: f64) -> f64 {
+    let hue = hue - hue as i64 as f64;
+    let hue = if hue < 0.0 { hue + 1.0 } else { hue };
+    match hue {
+        h if h < 1.0 / 6.0 => p + (q - p) * 6.0 * h,
+        h if h < 0.5 => q,
+        h if h < 2.0 / 3.0 => p + (q - p) * (2.0 / 3.0 - h) * 6.0,
+        _ => p,
+    }
+}
+
+fn invalid(color: &str) -> Error {
+    Error::Css(format!("invalid color {}", color))
+}
+
+// a number with an optional % (relative to full) or deg (for hues)
+fn component(text: &str, full: f64, color: &str) -> Result<f64, Error> {
+    let text = text.trim();
+    let (number, scale) = match text.strip_suffix('%') {
+        Some(number) => (number, full / 100.0),
+        None => (text.strip_suffix("deg").unwrap_or(text), 1.0),
+    };
+    number
+        .parse::<f64>()
+        .map(|n| n * scale)
+        .map_err(|_| invalid(color))
+}
+
+impl Color {
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self::rgba(red, green, blue, 1.0)
+    }
+
+    // alpha from 0 to 1
+    pub fn rgba(red: u8, green: u8, blue: u8, alpha: f64) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha: channel(alpha),
+        }
+    }
+
+    // hue in degrees, saturation and lightness from 0 to 100
+    pub fn hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        Self::hsla(hue, saturation, lightness, 1.0)
+    }
+
+    pub fn hsla(hue: f64, saturation: f64, lightness: f64, alpha: f64) -> Self {
+        let (hue, saturation, lightness) = (
+            hue / 360.0,
+            (saturation / 100.0).clamp(0.0, 1.0),
+            (lightness / 100.0).clamp(0.0, 1.0),
+        );
+        let q = match lightness < 0.5 {
+            true => lightness * (1.0 + saturation),
+            false => lightness + saturation - lightness * saturation,
+        };
+        let p = 2.0 * lightness - q;
+        Self {
+            red: channel(hue_to_channel(p, q, hue + 1.0 / 3.0)),
+            green: channel(hue_to_channel(p, q, hue)),
+            blue: channel(hue_to_channel(p, q, hue - 1.0 / 3.0)),
+            alpha: channel(alpha),
+        }
+    }
+
+    // one of the css named colors or transparent
+    pub fn named(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("transparent") {
+            return Some(Self::rgba(0, 0, 0, 0.0));
+        }
+        NAMED_COLORS
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, rgb)| Self::rgb((rgb >> 16) as u8, (rgb >> 8) as u8, *rgb as u8))
+    }
+
+    // #rgb, #rgba, #rrggbb or #rrggbbaa
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid(hex));
+        }
+        let values: Vec<u8> = match digits.len() {
+            3 | 4 => digits
+                .chars()
+                .map(|c| c.to_digit(16).unwrap_or(0) as u8 * 17)
+                .collect(),
+            6 | 8 => (0..digits.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).unwrap_or(0))
+                .collect(),
+            _ => return Err(invalid(hex)),
+        };
+        Ok(Self {
+            red: values[0],
+            green: values[1],
+            blue: values[2],
+            alpha: values.get(3).copied().unwrap_or(255),
+        })
+    }
+
+    // hex, rgb(), rgba(), hsl(), hsla() with commas or spaces and a / before alpha, or a name
+    pub fn parse(color: &str) -> Result<Self, Error> {
+        let text = color.trim();
+        if text.starts_with('#') {
+            return Self::from_hex(text);
+        }
+        let (function, arguments) = match text.split_once('(') {
+            Some((function, rest)) => match rest.strip_suffix(')') {
+                Some(arguments) => (function.trim().to_ascii_lowercase(), arguments),
+                None => return Err(invalid(color)),
+            },
+            None => return Self::named(text).ok_or_else(|| invalid(color)),
+        };
+
+        let parts: Vec<&str> = arguments
+            .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .collect();
+        if parts.len() != 3 && parts.len() != 4 {
+            return Err(invalid(color));
+        }
+        let alpha = match parts.get(3) {
+            Some(alpha) => component(alpha, 1.0, color)?,
+            None => 1.0,
+        };
+        match function.as_str() {
+            "rgb" | "rgba" => {
+                let mut channels = [0u8; 3];
+                for (channel, part) in channels.iter_mut().zip(&parts) {
+                    *channel = byte(component(part, 255.0, color)?);
+                }
+                Ok(Self::rgba(channels[0], channels[1], channels[2], alpha))
+            }
+            "hsl" | "hsla" => Ok(Self::hsla(
+                component(parts[0], 360.0, color)?,
+                component(parts[1], 100.0, color)?,
+                component(parts[2], 100.0, color)?,
+                alpha,
+            )),
+            _ => Err(invalid(color)),
+        }
+    }
+
+    pub fn red(&self) -> u8 {
+        self.red
+    }
+
+    pub fn green(&self) -> u8 {
+        self.green
+    }
+
+    pub fn blue(&self) -> u8 {
+        self.blue
+    }
+
+    // from 0 to 1
+    pub fn alpha(&self) -> f64 {
+        self.alpha as f64 / 255.0
+    }
+
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.alpha = channel(alpha);
+        self
+    }
+
+    // #rrggbb, or #rrggbbaa when not opaque
+    pub fn to_hex(&self) -> String {
+        match self.alpha {
+            255 => format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue),
+            alpha => format!(
+                "#{:02x}{:02x}{:02x}{:02x}",
+                self.red, self.green, self.blue, alpha
+            ),
+        }
+    }
+
+    // (hue in degrees, saturation and lightness from 0 to 100, alpha from 0 to 1)
+    pub fn to_hsl(&self) -> (f64, f64, f64, f64) {
+        let [red, green, blue] = [self.red, self.green, self.blue].map(|c| c as f64 / 255.0);
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        let lightness = (max + min) / 2.0;
+        if max == min {
+            return (0.0, 0.0, lightness * 100.0, self.alpha());
+        }
+
+        let delta = max - min;
+        let saturation = match lightness > 0.5 {
+            true => delta / (2.0 - max - min),
+            false => delta / (max + min),
+        };
+        let hue = match max {
+            m if m == red => (green - blue) / delta + if green < blue { 6.0 } else { 0.0 },
+            m if m == green => (blue - red) / delta + 2.0,
+            _ => (red - green) / delta + 4.0,
+        };
+        (
+            hue * 60.0,
+            saturation * 100.0,
+            lightness * 100.0,
+            self.alpha(),
+        )
+    }
+
+    fn is_short_hex(&self) -> bool {
+        [self.red, self.green, self.blue, self.alpha]
+            .iter()
+            .all(|c| c % 17 == 0)
+    }
+
+    // a name when it is shorter than the hex form
+    fn short_name(&self) -> Option<&'static str> {
+        if self.alpha != 255 {
+            return None;
+        }
+        let rgb = (self.red as u32) << 16 | (self.green as u32) << 8 | self.blue as u32;
+        let hex_len = if self.is_short_hex() { 4 } else { 7 };
+        NAMED_COLORS
+            .iter()
+            .find(|(name, value)| *value == rgb && name.len() < hex_len)
+            .map(|(name, _)| *name)
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(name) = self.short_name() {
+            return f.write_str(name);
+        }
+        match (self.is_short_hex(), self.alpha) {
+            (true, 255) => write!(
+                f,
+                "#{:x}{:x}{:x}",
+                self.red / 17,
+                self.green / 17,
+                self.blue / 17
+            ),
+            (true, alpha) => write!(
+                f,
+                "#{:x}{:x}{:x}{:x}",
+                self.red / 17,
+                self.green / 17,
+                self.blue / 17,
+                alpha / 17
+            ),
+            _ => f.write_str(&self.to_hex()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::color::Color;
+    use crate::error::Error;
+
+    #[test]
+    fn shortest_form() {
+        assert_eq!(Color::rgb(255, 0, 0).to_string(), "red");
+        assert_eq!(Color::rgb(0, 0, 128).to_string(), "navy");
+        assert_eq!(Color::rgb(255, 255, 255).to_string(), "#fff");
+        assert_eq!(Color::rgb(18, 52, 86).to_string(), "#123456");
+        assert_eq!(Color::rgba(0, 0, 0, 0.0).to_string(), "#0000");
+        assert_eq!(Color::rgba(18, 52, 86, 0.5).to_string(), "#12345680");
+    }
+
+    #[test]
+    fn parse_and_convert() {
+        assert_eq!(Color::parse("#FA0").unwrap(), Color::rgb(255, 170, 0));
+        assert_eq!(
+            Color::parse("rgba(10, 20, 30, 50%)").unwrap(),
+            Color::rgba(10, 20, 30, 0.5)
+        );
+        assert_eq!(
+            Color::parse("rgb(100% 0% 0% / 0.2)").unwrap(),
+            Color::rgba(255, 0, 0, 0.2)
+        );
+        assert_eq!(
+            Color::parse("hsl(120deg, 100%, 25%)").unwrap(),
+            Color::rgb(0, 128, 0)
+        );
+        assert_eq!(Color::parse("RebeccaPurple").unwrap().to_hex(), "#663399");
+        assert_eq!(Color::parse("transparent").unwrap().alpha(), 0.0);
+
+        let (hue, saturation, lightness, alpha) = Color::rgb(0x66, 0x33, 0x99).to_hsl();
+        assert_eq!(
+            (hue.round(), saturation.round(), lightness.round(), alpha),
+            (270.0, 50.0, 40.0, 1.0)
+        );
+        assert_eq!(Color::hsl(270.0, 50.0, 40.0).to_hex(), "#663399");
+    }
+
+    #[test]
+    fn invalid_colors() {
+        for color in ["#12", "#ggg", "rgb(1, 2)", "blurple", "rgb(1, 2, 3"] {
+            assert_eq!(
+                Color::parse(color),
+                Err(Error::Css(format!("invalid color {}", color)))
+            );
+        }
+    }
+}
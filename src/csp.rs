@@ -0,0 +1,335 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::html::{Attribute, Node};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ContentSecurityPolicy {
+    directives: BTreeMap<&'static str, BTreeSet<String>>,
+}
+
+impl Default for ContentSecurityPolicy {
+    fn default() -> Self {
+        let mut policy = Self {
+            directives: BTreeMap::new(),
+        };
+        policy.add_source("default-src", "'self'".to_string());
+        policy.add_source("base-uri", "'self'".to_string());
+        policy.add_source("object-src", "'none'".to_string());
+        policy
+    }
+}
+
+impl ContentSecurityPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_source(&mut self, directive: &'static str, source: String) {
+        self.directives.entry(directive).or_default().insert(source);
+    }
+
+    pub fn sources(&self, directive: &str) -> Vec<&str> {
+        self.directives
+            .get(directive)
+            .map(|sources| sources.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    // value for the Content-Security-Policy response header
+    pub fn header_value(&self) -> String {
+        self.directives
+            .iter()
+            .map(|(directive, sources)| {
+                let mut entry = directive.to_string();
+                for source in sources {
+                    entry.push(' ');
+                    entry.push_str(source);
+                }
+                entry
+            })
+            .collect::<Vec<String>>()
+            .join("; ")
+    }
+
+    // for pages served without control over headers, base-uri and frame-ancestors are ignored in a meta tag
+    pub fn to_meta(&self) -> Node {
+        Node::element(
+            "meta".to_string(),
+            vec![
+                Attribute::new(
+                    "http-equiv".to_string(),
+                    "Content-Security-Policy".to_string(),
+                ),
+                Attribute::new("content".to_string(), self.header_value()),
+            ],
+            vec![],
+        )
+    }
+}
+
+type Hasher = fn(&str) -> String;
+
+// policy allowing everything the page references, inline content is allowed with 'unsafe-inline'
+pub fn content_security_policy(root: &Node) -> ContentSecurityPolicy {
+    let mut policy = ContentSecurityPolicy::new();
+    analyze(root, &mut policy, None);
+    policy
+}
+
+// same as content_security_policy but inline content is allowed by its sha256 hash
+#[cfg(feature = "hashes")]
+pub fn content_security_policy_with_hashes(root: &Node) -> ContentSecurityPolicy {
+    let mut policy = ContentSecurityPolicy::new();
+    analyze(root, &mut policy, Some(crate::hash::csp_hash));
+    policy
+}
+
+fn attribute_value<'a>(attributes: &'a [Attribute], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|a| a.name().eq_ignore_ascii_case(name))
+        .and_then(Attribute::value)
+}
+
+// scheme and host of absolute urls, 'self' for relative ones
+fn source_for(url: &str) -> String {
+    let url = url.trim();
+    for scheme in ["https://", "http://", "wss://", "ws://", "//"] {
+        if let Some(rest) = url.strip_prefix(scheme) {
+            let host = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+            return match scheme {
+                "//" => host.to_string(),
+                _ => [scheme, host].concat(),
+            };
+        }
+    }
+
+    for scheme in ["data:", "blob:"] {
+        if url.starts_with(scheme) {
+            return scheme.to_string();
+        }
+    }
+
+    "'self'".to_string()
+}
+
+fn srcset_sources(srcset: &str) -> impl Iterator<Item = String> + '_ {
+    srcset
+        .split(',')
+        .filter_map(|candidate| candidate.split_whitespace().next())
+        .map(source_for)
+}
+
+fn inline_source(
+    content: &str,
+    hash: Option<Hasher>,
+    policy: &mut ContentSecurityPolicy,
+    directive: &'static str,
+) {
+    match hash {
+        Some(hash) => policy.add_source(directive, hash(content)),
+        None => policy.add_source(directive, "'unsafe-inline'".to_string()),
+    }
+}
+
+// event handler and style attributes can only be allowed by hash along with 'unsafe-hashes'
+fn inline_attribute(
+    content: &str,
+    hash: Option<Hasher>,
+    policy: &mut ContentSecurityPolicy,
+    directive: &'static str,
+) {
+    if hash.is_some() {
+        policy.add_source(directive, "'unsafe-hashes'".to_string());
+    }
+    inline_source(content, hash, policy, directive);
+}
+
+fn analyze(node: &Node, policy: &mut ContentSecurityPolicy, hash: Option<Hasher>) {
+    let (tag, attributes, children) = match node {
+        Node::Element {
+            tag,
+            attributes,
+            children,
+            ..
+        } => (tag.to_ascii_lowercase(), attributes, children),
+        Node::Fragment(children) => {
+            children.iter().for_each(|c| analyze(c, policy, hash));
+            return;
+        }
+        _ => return,
+    };
+
+    let content = || {
+        children
+            .iter()
+            .filter_map(|c| match c {
+                Node::Text(text) | Node::Raw(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<String>()
+    };
+
+    for attribute in attributes.iter() {
+        let name = attribute.name().to_ascii_lowercase();
+        let value = attribute.value().unwrap_or("");
+        if name == "style" {
+            inline_attribute(value, hash, policy, "style-src");
+        } else if name.starts_with("on") {
+            inline_attribute(value, hash, policy, "script-src");
+        }
+    }
+
+    match tag.as_str() {
+        "script" => match attribute_value(attributes, "src") {
+            Some(src) => policy.add_source("script-src", source_for(src)),
+            None => {
+                let content = content();
+                if !content.trim().is_empty() {
+                    inline_source(&content, hash, policy, "script-src");
+                }
+            }
+        },
+        "style" => {
+            let content = content();
+            if !content.trim().is_empty() {
+                inline_source(&content, hash, policy, "style-src");
+            }
+        }
+        "link" => {
+            let rel = attribute_value(attributes, "rel")
+                .unwrap_or("")
+                .to_ascii_lowercase();
+            let directive = match attribute_value(attributes, "as") {
+                _ if rel.split_whitespace().any(|r| r == "stylesheet") => Some("style-src"),
+                _ if rel.split_whitespace().any(|r| r == "icon") => Some("img-src"),
+                _ if rel.split_whitespace().any(|r| r == "modulepreload") => Some("script-src"),
+                Some("font") => Some("font-src"),
+                Some("script") => Some("script-src"),
+                Some("style") => Some("style-src"),
+                Some("image") => Some("img-src"),
+                _ => None,
+            };
+
+            if let (Some(directive), Some(href)) = (directive, attribute_value(attributes, "href"))
+            {
+                policy.add_source(directive, source_for(href));
+            }
+        }
+        "img" | "source" => {
+            if let Some(src) = attribute_value(attributes, "src") {
+                policy.add_source("img-src", source_for(src));
+            }
+            if let Some(srcset) = attribute_value(attributes, "srcset") {
+                srcset_sources(srcset).for_each(|s| policy.add_source("img-src", s));
+            }
+        }
+        "iframe" => {
+            if let Some(src) = attribute_value(attributes, "src") {
+                policy.add_source("frame-src", source_for(src));
+            }
+        }
+        "form" => {
+            if let Some(action) = attribute_value(attributes, "action") {
+                policy.add_source("form-action", source_for(action));
+            }
+        }
+        _ => (),
+    }
+
+    children.iter().for_each(|c| analyze(c, policy, hash));
+}
+
+#[cfg(test)]
+mod test {
+    use crate::csp::content_security_policy;
+    use crate::html::Node;
+
+    fn page() -> Node {
+        crate::html! {
+            html {
+                head {
+                    link rel="stylesheet" href="https://cdn.example.com/site.css";
+                    link rel="preload" as="font" href="https://fonts.example.com/a.woff2";
+                    style { "body{margin:0;}" }
+                }
+                body {
+                    img src="/logo.png" srcset="https://img.example.com/a.png 2x";
+                    script src="/app.js" {}
+                    script { "init();" }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn header_value() {
+        assert_eq!(
+            content_security_policy(&page()).header_value(),
+            "base-uri 'self'; default-src 'self'; font-src https://fonts.example.com; img-src 'self' https://img.example.com; object-src 'none'; script-src 'self' 'unsafe-inline'; style-src 'unsafe-inline' https://cdn.example.com"
+        );
+    }
+
+    #[test]
+    fn relative_and_protocol_relative_sources() {
+        let page = crate::html! {
+            body {
+                iframe src="//video.example.com/embed?id=1" {}
+                img src="data:image/png;base64,AAAA";
+                form action="/search" {}
+            }
+        };
+        let policy = content_security_policy(&page);
+
+        assert_eq!(policy.sources("frame-src"), vec!["video.example.com"]);
+        assert_eq!(policy.sources("img-src"), vec!["data:"]);
+        assert_eq!(policy.sources("form-action"), vec!["'self'"]);
+    }
+
+    #[test]
+    fn fragment_children_are_analyzed() {
+        let fragment = crate::html! {
+            script src="https://cdn.example.com/a.js" {}
+            iframe src="https://video.example.com/" {}
+        };
+        let policy = content_security_policy(&fragment);
+
+        assert_eq!(
+            policy.sources("script-src"),
+            vec!["https://cdn.example.com"]
+        );
+        assert_eq!(
+            policy.sources("frame-src"),
+            vec!["https://video.example.com"]
+        );
+    }
+
+    #[test]
+    fn meta_tag() {
+        let meta = content_security_policy(&crate::html! { body {} }).to_meta();
+
+        assert_eq!(
+            meta.to_string(),
+            "<meta http-equiv=\"Content-Security-Policy\" content=\"base-uri 'self'; default-src 'self'; object-src 'none'\">"
+        );
+    }
+
+    #[cfg(feature = "hashes")]
+    #[test]
+    fn inline_hashes() {
+        let page = crate::html! { body onload="init()" { script { "init();" } } };
+        let policy = crate::csp::content_security_policy_with_hashes(&page);
+
+        assert_eq!(
+            policy.sources("script-src"),
+            vec![
+                "'sha256-GEnM5q1nYY/iACnyMTdov+tNp9OFcBnnDgNXUXaVNXc='",
+                "'sha256-w4ujnOpjBoH2vcasx+reJRUwYivG8Q3afx/XevGJod8='",
+                "'unsafe-hashes'"
+            ]
+        );
+    }
+}
@@ -1,42 +1,556 @@
-use serde::Deserialize;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write;
+use serde::{Deserialize, Serialize};
+
+use crate::at_rule::AtRule;
+use crate::color::Color;
+use crate::name::Name;
+use crate::stylesheet::typed_value;
+use crate::values::{display_len, Float, Unit};
+
+// how a character is written in an identifier or a quoted string
+enum CssEscape {
+    Keep,
+    Backslash,   // \x
+    Code,        // \1f followed by a space, for characters that can't follow a backslash
+    Replacement, // NUL is not allowed at all
+}
+
+// what serializing an identifier or a string does in the cssom spec
+fn css_escape(text: &str, index: usize, c: char, in_string: bool) -> CssEscape {
+    match c {
+        '\0' => CssEscape::Replacement,
+        '\u{1}'..='\u{1f}' | '\u{7f}' => CssEscape::Code,
+        '"' | '\\' if in_string => CssEscape::Backslash,
+        _ if in_string => CssEscape::Keep,
+        '0'..='9' if index == 0 || index == 1 && text.starts_with('-') => CssEscape::Code,
+        '-' if text == "-" => CssEscape::Backslash,
+        c if !c.is_ascii() || c.is_ascii_alphanumeric() || c == '-' || c == '_' => CssEscape::Keep,
+        _ => CssEscape::Backslash,
+    }
+}
+
+fn code_len(c: char) -> usize {
+    let mut len = 1;
+    let mut rest = c as u32 >> 4;
+    while rest > 0 {
+        rest >>= 4;
+        len += 1;
+    }
+    len + 2
+}
+
+pub(crate) fn css_escaped_len(text: &str, in_string: bool) -> usize {
+    text.char_indices()
+        .map(|(i, c)| match css_escape(text, i, c, in_string) {
+            CssEscape::Keep => c.len_utf8(),
+            CssEscape::Backslash => c.len_utf8() + 1,
+            CssEscape::Code => code_len(c),
+            CssEscape::Replacement => '\u{fffd}'.len_utf8(),
+        })
+        .sum::<usize>()
+        + if in_string { 2 } else { 0 }
+}
+
+// identifiers are written as is unless escaping is needed, strings are always quoted
+pub(crate) fn write_css_escaped<W: Write>(
+    output: &mut W,
+    text: &str,
+    in_string: bool,
+) -> fmt::Result {
+    if in_string {
+        output.write_char('"')?;
+    }
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        let escape = css_escape(text, i, c, in_string);
+        if let CssEscape::Keep = escape {
+            continue;
+        }
+        output.write_str(&text[start..i])?;
+        match escape {
+            CssEscape::Backslash => {
+                output.write_char('\\')?;
+                output.write_char(c)?;
+            }
+            CssEscape::Code => write!(output, "\\{:x} ", c as u32)?,
+            _ => output.write_char('\u{fffd}')?,
+        }
+        start = i + c.len_utf8();
+    }
+    output.write_str(&text[start..])?;
+    if in_string {
+        output.write_char('"')?;
+    }
+    Ok(())
+}
 
-#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+// length of an escape after its backslash, up to six hex digits and a whitespace or any character
+pub(crate) fn escape_len(rest: &str) -> usize {
+    let hex = rest
+        .bytes()
+        .take(6)
+        .take_while(u8::is_ascii_hexdigit)
+        .count();
+    match hex {
+        0 => rest.chars().next().map_or(0, char::len_utf8),
+        hex => {
+            hex + rest[hex..]
+                .chars()
+                .next()
+                .filter(|c| c.is_whitespace())
+                .map_or(0, char::len_utf8)
+        }
+    }
+}
+
+// the text of an escaped identifier or string content
+pub(crate) fn unescape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find('\\') {
+        output.push_str(&rest[..at]);
+        let escape = &rest[at + 1..];
+        let len = escape_len(escape);
+        let hex = escape
+            .bytes()
+            .take(6)
+            .take_while(u8::is_ascii_hexdigit)
+            .count();
+        match hex {
+            0 => output.push_str(&escape[..len]),
+            hex => {
+                let code = u32::from_str_radix(&escape[..hex], 16).unwrap_or(0);
+                output.push(match char::from_u32(code) {
+                    Some('\0') | None => '\u{fffd}',
+                    Some(c) => c,
+                });
+            }
+        }
+        rest = &escape[len..];
+    }
+    output.push_str(rest);
+    output
+}
+
+fn custom_property_name(name: String) -> String {
+    match name.starts_with("--") {
+        true => name,
+        false => format!("--{}", name),
+    }
+}
+
+// how the parts of a list value are joined
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Separator {
+    Space,
+    Comma,
+}
+
+impl Separator {
+    pub(crate) fn as_char(&self) -> char {
+        match self {
+            Separator::Space => ' ',
+            Separator::Comma => ',',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum DeclarationValue {
     Basic(String),
     Function(String, Vec<String>), // (function name, function arguments
+    Length(Float, Unit),           // any dimension, e.g. 1.5rem, 1fr or 200ms
+    Percentage(Float),
+    Number(Float),
+    Keyword(String), // e.g. auto or inherit, written as an identifier
+    Color(Color),
+    List(Vec<DeclarationValue>, Separator), // e.g. 0 auto, or a comma list of space lists
+    Url(String),                            // written quoted so commas and parentheses are kept
+    Var(String, Option<Box<DeclarationValue>>), // (custom property with its --, fallback)
+}
+
+// text with spaces is written as a string unless it is quoted already,
+// e.g. the rows of grid-template-areas: "header header" "nav main"
+fn needs_quotes(text: &str) -> bool {
+    let quoted = text.len() >= 2
+        && (text.starts_with('"') && text.ends_with('"')
+            || text.starts_with('\'') && text.ends_with('\''));
+    text.contains(' ') && !quoted
 }
 
-impl ToString for DeclarationValue {
-    fn to_string(&self) -> String {
+impl DeclarationValue {
+    pub fn length(value: impl Into<Float>, unit: Unit) -> Self {
+        DeclarationValue::Length(value.into(), unit)
+    }
+
+    pub fn percentage(value: impl Into<Float>) -> Self {
+        DeclarationValue::Percentage(value.into())
+    }
+
+    pub fn number(value: impl Into<Float>) -> Self {
+        DeclarationValue::Number(value.into())
+    }
+
+    pub fn keyword(keyword: impl Into<String>) -> Self {
+        DeclarationValue::Keyword(keyword.into())
+    }
+
+    pub fn color(color: Color) -> Self {
+        DeclarationValue::Color(color)
+    }
+
+    pub fn url(url: impl Into<String>) -> Self {
+        DeclarationValue::Url(url.into())
+    }
+
+    // the -- is added when missing
+    pub fn var(name: impl Into<String>) -> Self {
+        DeclarationValue::Var(custom_property_name(name.into()), None)
+    }
+
+    pub fn var_or(name: impl Into<String>, fallback: DeclarationValue) -> Self {
+        DeclarationValue::Var(custom_property_name(name.into()), Some(Box::new(fallback)))
+    }
+
+    pub fn space_list(values: Vec<DeclarationValue>) -> Self {
+        DeclarationValue::List(values, Separator::Space)
+    }
+
+    pub fn comma_list(values: Vec<DeclarationValue>) -> Self {
+        DeclarationValue::List(values, Separator::Comma)
+    }
+
+    pub fn estimated_len(&self) -> usize {
         match self {
-            DeclarationValue::Basic(s) => match s.contains(" ") {
-                true => format!("\"{}\"", s),
-                false => s.to_string(),
+            DeclarationValue::Basic(s) => match needs_quotes(s) {
+                true => css_escaped_len(s, true),
+                false => s.len(),
             },
-            DeclarationValue::Function(name, args) => format!("{}({})", name, args.join(",")),
+            DeclarationValue::Function(name, args) => {
+                name.len()
+                    + 2
+                    + args.iter().map(String::len).sum::<usize>()
+                    + args.len().saturating_sub(1)
+            }
+            DeclarationValue::Length(value, unit) => display_len(value) + unit.as_str().len(),
+            DeclarationValue::Percentage(value) => display_len(value) + 1,
+            DeclarationValue::Number(value) => display_len(value),
+            DeclarationValue::Keyword(keyword) => css_escaped_len(keyword, false),
+            DeclarationValue::Color(color) => display_len(color),
+            DeclarationValue::List(values, _) => {
+                values
+                    .iter()
+                    .map(DeclarationValue::estimated_len)
+                    .sum::<usize>()
+                    + values.len().saturating_sub(1)
+            }
+            DeclarationValue::Url(url) => 5 + css_escaped_len(url, true),
+            DeclarationValue::Var(name, fallback) => {
+                5 + css_escaped_len(name, false)
+                    + fallback.as_ref().map_or(0, |f| f.estimated_len() + 1)
+            }
+        }
+    }
+
+    fn render<W: Write>(&self, output: &mut W) -> fmt::Result {
+        match self {
+            DeclarationValue::Basic(s) => match needs_quotes(s) {
+                true => write_css_escaped(output, s, true),
+                false => output.write_str(s),
+            },
+            DeclarationValue::Function(name, args) => {
+                output.write_str(name)?;
+                output.write_char('(')?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        output.write_char(',')?;
+                    }
+                    output.write_str(arg)?;
+                }
+                output.write_char(')')
+            }
+            DeclarationValue::Length(value, unit) => write!(output, "{}{}", value, unit),
+            DeclarationValue::Percentage(value) => write!(output, "{}%", value),
+            DeclarationValue::Number(value) => write!(output, "{}", value),
+            DeclarationValue::Keyword(keyword) => write_css_escaped(output, keyword, false),
+            DeclarationValue::Color(color) => write!(output, "{}", color),
+            DeclarationValue::List(values, separator) => {
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        output.write_char(separator.as_char())?;
+                    }
+                    value.render(output)?;
+                }
+                Ok(())
+            }
+            DeclarationValue::Url(url) => {
+                output.write_str("url(")?;
+                write_css_escaped(output, url, true)?;
+                output.write_char(')')
+            }
+            DeclarationValue::Var(name, fallback) => {
+                output.write_str("var(")?;
+                write_css_escaped(output, name, false)?;
+                if let Some(fallback) = fallback {
+                    output.write_char(',')?;
+                    fallback.render(output)?;
+                }
+                output.write_char(')')
+            }
+        }
+    }
+
+    // zero lengths keep their unit where a bare 0 would be read as another component,
+    // text spelling a number, length or color (e.g. from data) is shortened the same way
+    fn render_minified<W: Write>(&self, output: &mut W, zero_units: bool) -> fmt::Result {
+        match self {
+            DeclarationValue::Basic(s) if !needs_quotes(s) => match typed_value(s) {
+                value @ (DeclarationValue::Length(..)
+                | DeclarationValue::Percentage(_)
+                | DeclarationValue::Number(_)
+                | DeclarationValue::Color(_)) => value.render_minified(output, zero_units),
+                _ => self.render(output),
+            },
+            DeclarationValue::Length(value, unit) => {
+                match value.get() == 0.0 && unit.is_length() && !zero_units {
+                    true => output.write_char('0'),
+                    false => {
+                        value.write_minified(output)?;
+                        output.write_str(unit.as_str())
+                    }
+                }
+            }
+            DeclarationValue::Percentage(value) => {
+                value.write_minified(output)?;
+                output.write_char('%')
+            }
+            DeclarationValue::Number(value) => value.write_minified(output),
+            DeclarationValue::List(values, separator) => {
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        output.write_char(separator.as_char())?;
+                    }
+                    value.render_minified(output, zero_units)?;
+                }
+                Ok(())
+            }
+            DeclarationValue::Var(name, Some(fallback)) => {
+                output.write_str("var(")?;
+                write_css_escaped(output, name, false)?;
+                output.write_char(',')?;
+                fallback.render_minified(output, zero_units)?;
+                output.write_char(')')
+            }
+            value => value.render(output),
         }
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+impl fmt::Display for DeclarationValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
+    }
+}
+
+// shorthands for the most used dimensions
+pub fn px(value: impl Into<Float>) -> DeclarationValue {
+    DeclarationValue::length(value, Unit::Px)
+}
+
+pub fn rem(value: impl Into<Float>) -> DeclarationValue {
+    DeclarationValue::length(value, Unit::Rem)
+}
+
+pub fn em(value: impl Into<Float>) -> DeclarationValue {
+    DeclarationValue::length(value, Unit::Em)
+}
+
+// how rule sets are written, compact unless expanded output is asked for
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct CssFormat {
+    expanded: bool,
+    indent: usize,
+    minified: bool,
+}
+
+impl CssFormat {
+    // everything on one line without optional whitespace, what Display writes
+    pub fn compact() -> Self {
+        Self {
+            expanded: false,
+            indent: 0,
+            minified: false,
+        }
+    }
+
+    // compact with shortened values and no semicolon before a closing brace
+    pub fn minified() -> Self {
+        Self::compact().minify(true)
+    }
+
+    // one declaration per line, blocks indented by two spaces
+    pub fn expanded() -> Self {
+        Self {
+            expanded: true,
+            indent: 2,
+            minified: false,
+        }
+    }
+
+    // spaces per level in expanded output
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    // short hex colors, no units on zero lengths, no leading zeros and no last semicolons,
+    // values of custom properties are kept since they may be used in calc()
+    pub fn minify(mut self, minified: bool) -> Self {
+        self.minified = minified;
+        self
+    }
+
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    pub fn is_minified(&self) -> bool {
+        self.minified
+    }
+
+    pub(crate) fn pick(&self, compact: &'static str, expanded: &'static str) -> &'static str {
+        match self.expanded {
+            true => expanded,
+            false => compact,
+        }
+    }
+
+    pub(crate) fn write_indent<W: Write>(&self, output: &mut W, depth: usize) -> fmt::Result {
+        if self.expanded {
+            for _ in 0..depth * self.indent {
+                output.write_char(' ')?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for CssFormat {
+    fn default() -> Self {
+        Self::compact()
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Declaration {
-    property: String,
+    property: Name,
     value: DeclarationValue,
 }
 
 impl Declaration {
     pub fn new(property: String, value: DeclarationValue) -> Self {
-        Self { property, value }
+        Self {
+            property: Name::new(property),
+            value,
+        }
     }
+
+    // --name: value, the -- is added when missing
+    pub fn custom_property(name: impl Into<String>, value: DeclarationValue) -> Self {
+        Self::new(custom_property_name(name.into()), value)
+    }
+
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    pub fn is_custom_property(&self) -> bool {
+        self.property.starts_with("--")
+    }
+
+    pub fn value(&self) -> &DeclarationValue {
+        &self.value
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        css_escaped_len(&self.property, false) + self.value.estimated_len() + 2
+    }
+
+    fn render<W: Write>(&self, output: &mut W) -> fmt::Result {
+        self.render_formatted(output, &CssFormat::compact(), 0)
+    }
+
+    pub(crate) fn render_formatted<W: Write>(
+        &self,
+        output: &mut W,
+        format: &CssFormat,
+        depth: usize,
+    ) -> fmt::Result {
+        self.render_without_semicolon(output, format, depth)?;
+        output.write_str(format.pick(";", ";\n"))
+    }
+
+    fn render_without_semicolon<W: Write>(
+        &self,
+        output: &mut W,
+        format: &CssFormat,
+        depth: usize,
+    ) -> fmt::Result {
+        format.write_indent(output, depth)?;
+        write_css_escaped(output, &self.property, false)?;
+        output.write_str(format.pick(":", ": "))?;
+        match format.minified && !self.is_custom_property() {
+            true => self
+                .value
+                .render_minified(output, keeps_zero_units(&self.property)),
+            false => self.value.render(output),
+        }
+    }
+}
+
+// flex:0 sets flex-grow and flex:1 0 sets flex-shrink, only 0px is read as flex-basis
+fn keeps_zero_units(property: &str) -> bool {
+    ["", "-webkit-", "-ms-"]
+        .iter()
+        .any(|prefix| property.strip_prefix(prefix) == Some("flex"))
+}
+
+// the declarations of a block, minified output leaves out the semicolon after the last one
+pub(crate) fn write_declarations<W: Write>(
+    output: &mut W,
+    declarations: &[Declaration],
+    format: &CssFormat,
+    depth: usize,
+) -> fmt::Result {
+    for (i, declaration) in declarations.iter().enumerate() {
+        match format.minified && i + 1 == declarations.len() {
+            true => {
+                declaration.render_without_semicolon(output, format, depth)?;
+                output.write_str(format.pick("", "\n"))?;
+            }
+            false => declaration.render_formatted(output, format, depth)?,
+        }
+    }
+    Ok(())
 }
 
-impl ToString for Declaration {
-    fn to_string(&self) -> String {
-        format!("{}:{};", self.property, self.value.to_string())
+impl fmt::Display for Declaration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Combinator {
     Descendant,
     Child,
@@ -44,7 +558,119 @@ pub enum Combinator {
     GeneralSibling,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+impl Combinator {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Combinator::Descendant => " ",
+            Combinator::Child => ">",
+            Combinator::AdjacentSibling => "+",
+            Combinator::GeneralSibling => "~",
+        }
+    }
+}
+
+// pseudo classes taking a selector as their argument
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum PseudoFunction {
+    Not,
+    Is,
+    Where,
+    Has,
+}
+
+impl PseudoFunction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PseudoFunction::Not => "not",
+            PseudoFunction::Is => "is",
+            PseudoFunction::Where => "where",
+            PseudoFunction::Has => "has",
+        }
+    }
+}
+
+// the i and s flags of attribute selectors, without one the document language decides
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum AttributeCase {
+    #[default]
+    Default,
+    Insensitive,
+    Sensitive,
+}
+
+impl AttributeCase {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AttributeCase::Default => "",
+            AttributeCase::Insensitive => " i",
+            AttributeCase::Sensitive => " s",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum NthKind {
+    Child,
+    LastChild,
+    OfType,
+    LastOfType,
+}
+
+impl NthKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            NthKind::Child => "nth-child",
+            NthKind::LastChild => "nth-last-child",
+            NthKind::OfType => "nth-of-type",
+            NthKind::LastOfType => "nth-last-of-type",
+        }
+    }
+}
+
+fn integer_len(n: i32) -> usize {
+    let mut len = 1;
+    let mut rest = n.unsigned_abs();
+    while rest >= 10 {
+        rest /= 10;
+        len += 1;
+    }
+    len
+}
+
+// length of an+b in its shortest form (e.g. 2n+1, -n+3, 4, n)
+fn nth_len(a: i32, b: i32) -> usize {
+    let a_len = match a {
+        0 => 0,
+        1 => 1,
+        -1 => 2,
+        a => integer_len(a) + (a < 0) as usize + 1,
+    };
+    let b_len = match (a, b) {
+        (0, b) => integer_len(b) + (b < 0) as usize,
+        (_, 0) => 0,
+        (_, b) => integer_len(b) + 1,
+    };
+    a_len + b_len
+}
+
+fn render_nth<W: Write>(a: i32, b: i32, output: &mut W) -> fmt::Result {
+    match a {
+        0 => return write!(output, "{}", b),
+        1 => output.write_char('n')?,
+        -1 => output.write_str("-n")?,
+        a => write!(output, "{}n", a)?,
+    }
+    match b {
+        0 => Ok(()),
+        b => write!(output, "{:+}", b),
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Selector {
     Universal,
     Tag(String),                                          // tag name
@@ -54,53 +680,299 @@ pub enum Selector {
     PseudoClass(Box<Selector>, String),                   // (base selector, pseudo class)
     PseudoElement(Box<Selector>, String),                 // (base selector, pseudo element)
     Attribute(String),                                    // attribute name
-    AttributeValue(String, String),                       // (attribute name, attribute value)
-    AttributeContains(String, String),                    // (attribute name, search string)
+    // (attribute name, attribute value, case flag)
+    AttributeValue(String, String, #[serde(default)] AttributeCase),
+    // (attribute name, search string, case flag)
+    AttributeContains(String, String, #[serde(default)] AttributeCase),
+    // (attribute name, prefix, case flag)
+    AttributePrefix(String, String, #[serde(default)] AttributeCase),
+    // (attribute name, suffix, case flag)
+    AttributeSuffix(String, String, #[serde(default)] AttributeCase),
+    // (attribute name, substring, case flag)
+    AttributeSubstring(String, String, #[serde(default)] AttributeCase),
+    // (attribute name, value matched alone or before a -, case flag)
+    AttributeDash(String, String, #[serde(default)] AttributeCase),
     Chain(Vec<Selector>), // no space merge (e.g. p.my-class[someAttribute])
     Group(Vec<Selector>), // comma separated list (e.g. body, h1, p)
+    // (base selector, function, argument)
+    Functional(Box<Selector>, PseudoFunction, Box<Selector>),
+    // argument of :has relative to its subject (e.g. > a)
+    Relative(Combinator, Box<Selector>),
+    // elements at positions a*n+b among their siblings, counting only those matching of if given
+    Nth {
+        base: Box<Selector>,
+        kind: NthKind,
+        a: i32,
+        b: i32,
+        of: Option<Box<Selector>>,
+    },
+    Parent, // & in a sub rule, replaced by the selector of the parent rule
 }
 
-impl ToString for Selector {
-    fn to_string(&self) -> String {
+impl Selector {
+    pub fn estimated_len(&self) -> usize {
         match self {
-            Selector::Universal => "*".to_string(),
-            Selector::Tag(s) => s.to_string(),
-            Selector::Id(id) => format!("#{}", id),
-            Selector::Class(class) => format!(".{}", class),
+            Selector::Universal | Selector::Parent => 1,
+            Selector::Tag(s) => css_escaped_len(s, false),
+            Selector::Id(id) => css_escaped_len(id, false) + 1,
+            Selector::Class(class) => css_escaped_len(class, false) + 1,
+            Selector::Combinator(base, _, relative) => {
+                base.estimated_len() + 1 + relative.estimated_len()
+            }
+            Selector::PseudoClass(base, class) => base.estimated_len() + class.len() + 1,
+            Selector::PseudoElement(base, class) => base.estimated_len() + class.len() + 2,
+            Selector::Functional(base, function, argument) => {
+                base.estimated_len() + function.as_str().len() + 3 + argument.estimated_len()
+            }
+            Selector::Relative(Combinator::Descendant, selector) => selector.estimated_len(),
+            Selector::Relative(_, selector) => selector.estimated_len() + 1,
+            Selector::Nth {
+                base,
+                kind,
+                a,
+                b,
+                of,
+            } => {
+                base.estimated_len()
+                    + kind.as_str().len()
+                    + 3
+                    + nth_len(*a, *b)
+                    + of.as_ref().map_or(0, |of| of.estimated_len() + 4)
+            }
+            Selector::Attribute(attr) => css_escaped_len(attr, false) + 2,
+            Selector::AttributeValue(attr, value, case) => {
+                css_escaped_len(attr, false)
+                    + css_escaped_len(value, true)
+                    + case.as_str().len()
+                    + 3
+            }
+            Selector::AttributeContains(attr, value, case)
+            | Selector::AttributePrefix(attr, value, case)
+            | Selector::AttributeSuffix(attr, value, case)
+            | Selector::AttributeSubstring(attr, value, case)
+            | Selector::AttributeDash(attr, value, case) => {
+                css_escaped_len(attr, false)
+                    + css_escaped_len(value, true)
+                    + case.as_str().len()
+                    + 4
+            }
+            Selector::Chain(items) => items.iter().map(Selector::estimated_len).sum(),
+            Selector::Group(items) => {
+                items.iter().map(Selector::estimated_len).sum::<usize>()
+                    + items.len().saturating_sub(1)
+            }
+        }
+    }
+
+    fn render<W: Write>(&self, output: &mut W) -> fmt::Result {
+        match self {
+            Selector::Universal => output.write_char('*'),
+            Selector::Parent => output.write_char('&'),
+            Selector::Tag(s) => write_css_escaped(output, s, false),
+            Selector::Id(id) => {
+                output.write_char('#')?;
+                write_css_escaped(output, id, false)
+            }
+            Selector::Class(class) => {
+                output.write_char('.')?;
+                write_css_escaped(output, class, false)
+            }
             Selector::Combinator(base, op, relative) => {
-                format!(
-                    "{}{}{}",
-                    base.to_string(),
-                    match op {
-                        Combinator::Descendant => " ",
-                        Combinator::Child => ">",
-                        Combinator::AdjacentSibling => "+",
-                        Combinator::GeneralSibling => "~",
-                    },
-                    relative.to_string()
-                )
-            }
-            Selector::PseudoClass(base, class) => format!("{}:{}", base.to_string(), class),
-            Selector::PseudoElement(base, class) => format!("{}::{}", base.to_string(), class),
-            Selector::Attribute(attr) => format!("[{}]", attr),
-            Selector::AttributeValue(attr, value) => format!("[{}=\"{}\"]", attr, value),
-            Selector::AttributeContains(attr, value) => format!("[{}~=\"{}\"]", attr, value),
-            Selector::Chain(items) => items
-                .iter()
-                .map(Selector::to_string)
-                .collect::<Vec<String>>()
-                .join(""),
-            Selector::Group(items) => items
-                .iter()
-                .map(Selector::to_string)
-                .collect::<Vec<String>>()
-                .join(","),
+                base.render(output)?;
+                output.write_str(op.as_str())?;
+                relative.render(output)
+            }
+            Selector::PseudoClass(base, class) => {
+                base.render(output)?;
+                output.write_char(':')?;
+                output.write_str(class)
+            }
+            Selector::PseudoElement(base, class) => {
+                base.render(output)?;
+                output.write_str("::")?;
+                output.write_str(class)
+            }
+            Selector::Functional(base, function, argument) => {
+                base.render(output)?;
+                output.write_char(':')?;
+                output.write_str(function.as_str())?;
+                output.write_char('(')?;
+                argument.render(output)?;
+                output.write_char(')')
+            }
+            // descendants are the default for :has
+            Selector::Relative(Combinator::Descendant, selector) => selector.render(output),
+            Selector::Relative(combinator, selector) => {
+                output.write_str(combinator.as_str())?;
+                selector.render(output)
+            }
+            Selector::Nth {
+                base,
+                kind,
+                a,
+                b,
+                of,
+            } => {
+                base.render(output)?;
+                output.write_char(':')?;
+                output.write_str(kind.as_str())?;
+                output.write_char('(')?;
+                render_nth(*a, *b, output)?;
+                if let Some(of) = of {
+                    output.write_str(" of ")?;
+                    of.render(output)?;
+                }
+                output.write_char(')')
+            }
+            Selector::Attribute(attr) => {
+                output.write_char('[')?;
+                write_css_escaped(output, attr, false)?;
+                output.write_char(']')
+            }
+            Selector::AttributeValue(attr, value, case)
+            | Selector::AttributeContains(attr, value, case)
+            | Selector::AttributePrefix(attr, value, case)
+            | Selector::AttributeSuffix(attr, value, case)
+            | Selector::AttributeSubstring(attr, value, case)
+            | Selector::AttributeDash(attr, value, case) => {
+                let operator = match self {
+                    Selector::AttributeValue(..) => "=",
+                    Selector::AttributeContains(..) => "~=",
+                    Selector::AttributePrefix(..) => "^=",
+                    Selector::AttributeSuffix(..) => "$=",
+                    Selector::AttributeSubstring(..) => "*=",
+                    _ => "|=",
+                };
+                output.write_char('[')?;
+                write_css_escaped(output, attr, false)?;
+                output.write_str(operator)?;
+                write_css_escaped(output, value, true)?;
+                output.write_str(case.as_str())?;
+                output.write_char(']')
+            }
+            Selector::Chain(items) => {
+                for item in items {
+                    item.render(output)?;
+                }
+                Ok(())
+            }
+            Selector::Group(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        output.write_char(',')?;
+                    }
+                    item.render(output)?;
+                }
+                Ok(())
+            }
         }
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+impl Selector {
+    // whether this selector or any selector within it matches
+    pub(crate) fn any(&self, f: &dyn Fn(&Selector) -> bool) -> bool {
+        f(self)
+            || match self {
+                Selector::Combinator(base, _, other) | Selector::Functional(base, _, other) => {
+                    base.any(f) || other.any(f)
+                }
+                Selector::PseudoClass(base, _)
+                | Selector::PseudoElement(base, _)
+                | Selector::Relative(_, base) => base.any(f),
+                Selector::Nth { base, of, .. } => {
+                    base.any(f) || of.as_ref().is_some_and(|of| of.any(f))
+                }
+                Selector::Chain(items) | Selector::Group(items) => {
+                    items.iter().any(|item| item.any(f))
+                }
+                _ => false,
+            }
+    }
+
+    // copy with every selector f returns a replacement for replaced, outermost first
+    pub(crate) fn replace(&self, f: &dyn Fn(&Selector) -> Option<Selector>) -> Selector {
+        if let Some(replacement) = f(self) {
+            return replacement;
+        }
+        let replace = |selector: &Selector| Box::new(selector.replace(f));
+        let replace_all = |items: &[Selector]| items.iter().map(|item| item.replace(f)).collect();
+        match self {
+            Selector::Combinator(base, combinator, relative) => {
+                Selector::Combinator(replace(base), combinator.clone(), replace(relative))
+            }
+            Selector::PseudoClass(base, class) => {
+                Selector::PseudoClass(replace(base), class.clone())
+            }
+            Selector::PseudoElement(base, element) => {
+                Selector::PseudoElement(replace(base), element.clone())
+            }
+            Selector::Functional(base, function, argument) => {
+                Selector::Functional(replace(base), function.clone(), replace(argument))
+            }
+            Selector::Relative(combinator, selector) => {
+                Selector::Relative(combinator.clone(), replace(selector))
+            }
+            Selector::Nth {
+                base,
+                kind,
+                a,
+                b,
+                of,
+            } => Selector::Nth {
+                base: replace(base),
+                kind: kind.clone(),
+                a: *a,
+                b: *b,
+                of: of.as_deref().map(replace),
+            },
+            Selector::Chain(items) => Selector::Chain(replace_all(items)),
+            Selector::Group(items) => Selector::Group(replace_all(items)),
+            selector => selector.clone(),
+        }
+    }
+
+    pub fn has_parent(&self) -> bool {
+        self.any(&|selector| matches!(selector, Selector::Parent))
+    }
+
+    // the full selector of a sub rule, like scss & stands for the parent (&.active, &:hover,
+    // & + li, .dark &) and a sub rule without one is a child of the parent
+    pub fn nested_in(&self, parent: &Selector) -> Selector {
+        match (self, parent) {
+            (Selector::Group(items), _) => {
+                Selector::Group(items.iter().map(|item| item.nested_in(parent)).collect())
+            }
+            // each parent gets its own copy so a group never ends up inside a compound
+            (_, Selector::Group(parents)) => Selector::Group(
+                parents
+                    .iter()
+                    .map(|parent| self.nested_in(parent))
+                    .collect(),
+            ),
+            _ if self.has_parent() => self.replace(&|selector| match selector {
+                Selector::Parent => Some(parent.clone()),
+                _ => None,
+            }),
+            _ => Selector::Combinator(
+                Box::new(parent.clone()),
+                Combinator::Child,
+                Box::new(self.clone()),
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Rule {
+    #[serde(deserialize_with = "crate::selector::deserialize_selector")]
     selector: Selector,
     declarations: Vec<Declaration>,
     #[serde(default)]
@@ -116,65 +988,110 @@ impl Rule {
         }
     }
 
-    fn make_string(&self) -> String {
-        let mut all_rules = vec![format!(
-            "{}{{{}}}",
-            self.selector.to_string(),
-            self.declarations
+    pub fn selector(&self) -> &Selector {
+        &self.selector
+    }
+
+    pub fn declarations(&self) -> &[Declaration] {
+        &self.declarations
+    }
+
+    pub fn sub_rules(&self) -> &[Rule] {
+        &self.sub_rules
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        self.estimated_len_with_selector(&self.selector)
+    }
+
+    fn estimated_len_with_selector(&self, selector: &Selector) -> usize {
+        let declarations_len = self
+            .declarations
+            .iter()
+            .map(Declaration::estimated_len)
+            .sum::<usize>();
+
+        selector.estimated_len()
+            + declarations_len
+            + 2
+            + self
+                .sub_rules
                 .iter()
-                .map(Declaration::to_string)
-                .collect::<Vec<String>>()
-                .join("")
-        )];
+                .map(|r| r.estimated_len_with_selector(&r.selector.nested_in(selector)))
+                .sum::<usize>()
+    }
+
+    fn render_block<W: Write>(
+        &self,
+        selector: &Selector,
+        output: &mut W,
+        format: &CssFormat,
+        depth: usize,
+    ) -> fmt::Result {
+        format.write_indent(output, depth)?;
+        selector.render(output)?;
+        output.write_str(format.pick("{", " {\n"))?;
+        write_declarations(output, &self.declarations, format, depth + 1)?;
+        format.write_indent(output, depth)?;
+        output.write_str(format.pick("}", "}\n"))
+    }
+
+    fn render<W: Write>(&self, output: &mut W) -> fmt::Result {
+        self.render_formatted(output, &CssFormat::compact(), 0)
+    }
+
+    fn render_formatted<W: Write>(
+        &self,
+        output: &mut W,
+        format: &CssFormat,
+        depth: usize,
+    ) -> fmt::Result {
+        self.render_block(&self.selector, output, format, depth)?;
 
-        let mut sub_rules = vec![(format!("{}>", self.selector.to_string()), &self.sub_rules)];
+        let mut sub_rules = vec![(self.selector.clone(), &self.sub_rules)];
 
-        while let Some((prefix, rules)) = sub_rules.pop() {
+        while let Some((parent, rules)) = sub_rules.pop() {
             for rule in rules {
-                all_rules.push(format!(
-                    "{}{}{{{}}}",
-                    prefix,
-                    rule.selector.to_string(),
-                    rule.declarations
-                        .iter()
-                        .map(Declaration::to_string)
-                        .collect::<Vec<String>>()
-                        .join("")
-                ));
+                let selector = rule.selector.nested_in(&parent);
+                rule.render_block(&selector, output, format, depth)?;
 
                 if !rule.sub_rules.is_empty() {
-                    sub_rules.push((
-                        format!("{}{}>", prefix, rule.selector.to_string()),
-                        &rule.sub_rules,
-                    ))
+                    sub_rules.push((selector, &rule.sub_rules))
                 }
             }
         }
 
-        all_rules.join("")
+        Ok(())
     }
 }
 
-impl ToString for Rule {
-    fn to_string(&self) -> String {
-        self.make_string()
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum MediaConstraint {
+    #[default]
     None,
     Not,
     Only,
 }
 
-impl Default for MediaConstraint {
-    fn default() -> Self {
-        Self::None
+impl MediaConstraint {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MediaConstraint::None => "",
+            MediaConstraint::Only => "only ",
+            MediaConstraint::Not => "not ",
+        }
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MediaFeature {
     property: String,
     value: String,
@@ -184,62 +1101,264 @@ impl MediaFeature {
     pub fn new(property: String, value: String) -> Self {
         Self { property, value }
     }
+
+    pub fn estimated_len(&self) -> usize {
+        css_escaped_len(&self.property, false) + self.value.len() + 3
+    }
+
+    fn render<W: Write>(&self, output: &mut W) -> fmt::Result {
+        output.write_char('(')?;
+        write_css_escaped(output, &self.property, false)?;
+        output.write_char(':')?;
+        output.write_str(&self.value)?;
+        output.write_char(')')
+    }
 }
 
-impl ToString for MediaFeature {
-    fn to_string(&self) -> String {
-        format!("({}:{})", self.property, self.value)
+impl fmt::Display for MediaFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+// nested conditions are put in parentheses when they are operands, except for an and in an and
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum MediaCondition {
     Lone(MediaFeature),
-    And(MediaFeature, MediaFeature),
-    Or(MediaFeature, MediaFeature),
-    Not(MediaFeature, MediaFeature),
+    And(Vec<MediaCondition>),
+    Or(Vec<MediaCondition>),
+    Not(Box<MediaCondition>),
+    Group(Box<MediaCondition>), // explicit parentheses
 }
 
-impl ToString for MediaCondition {
-    fn to_string(&self) -> String {
+impl MediaCondition {
+    pub fn feature(property: impl Into<String>, value: impl Into<String>) -> Self {
+        MediaCondition::Lone(MediaFeature::new(property.into(), value.into()))
+    }
+
+    pub fn and(conditions: Vec<MediaCondition>) -> Self {
+        MediaCondition::And(conditions)
+    }
+
+    pub fn or(conditions: Vec<MediaCondition>) -> Self {
+        MediaCondition::Or(conditions)
+    }
+
+    pub fn negate(condition: MediaCondition) -> Self {
+        MediaCondition::Not(Box::new(condition))
+    }
+
+    pub fn group(condition: MediaCondition) -> Self {
+        MediaCondition::Group(Box::new(condition))
+    }
+
+    fn needs_parentheses(&self, in_and: bool) -> bool {
+        match self {
+            MediaCondition::And(_) => !in_and,
+            MediaCondition::Or(_) | MediaCondition::Not(_) => true,
+            MediaCondition::Lone(_) | MediaCondition::Group(_) => false,
+        }
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        let joined_len = |conditions: &[MediaCondition], in_and: bool| {
+            conditions
+                .iter()
+                .map(|condition| condition.operand_len(in_and))
+                .sum::<usize>()
+                + conditions.len().saturating_sub(1) * if in_and { 5 } else { 4 }
+        };
         match self {
-            MediaCondition::Lone(f) => f.to_string(),
-            MediaCondition::And(f1, f2) => format!("{} and {}", f1.to_string(), f2.to_string()),
-            MediaCondition::Or(f1, f2) => format!("{} or {}", f1.to_string(), f2.to_string()),
-            MediaCondition::Not(f1, f2) => format!("{} not {}", f1.to_string(), f2.to_string())
+            MediaCondition::Lone(f) => f.estimated_len(),
+            MediaCondition::And(conditions) => joined_len(conditions, true),
+            MediaCondition::Or(conditions) => joined_len(conditions, false),
+            MediaCondition::Not(condition) => 4 + condition.operand_len(false),
+            MediaCondition::Group(condition) => 2 + condition.estimated_len(),
+        }
+    }
+
+    // length when used as an operand of and, or and not
+    pub(crate) fn operand_len(&self, in_and: bool) -> usize {
+        self.estimated_len() + if self.needs_parentheses(in_and) { 2 } else { 0 }
+    }
+
+    pub(crate) fn render<W: Write>(&self, output: &mut W) -> fmt::Result {
+        let (conditions, in_and) = match self {
+            MediaCondition::Lone(f) => return f.render(output),
+            MediaCondition::And(conditions) => (conditions, true),
+            MediaCondition::Or(conditions) => (conditions, false),
+            MediaCondition::Not(condition) => {
+                output.write_str("not ")?;
+                return condition.render_operand(output, false);
+            }
+            MediaCondition::Group(condition) => {
+                output.write_char('(')?;
+                condition.render(output)?;
+                return output.write_char(')');
+            }
+        };
+
+        for (i, condition) in conditions.iter().enumerate() {
+            if i > 0 {
+                output.write_str(if in_and { " and " } else { " or " })?;
+            }
+            condition.render_operand(output, in_and)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn render_operand<W: Write>(&self, output: &mut W, in_and: bool) -> fmt::Result {
+        match self.needs_parentheses(in_and) {
+            true => {
+                output.write_char('(')?;
+                self.render(output)?;
+                output.write_char(')')
+            }
+            false => self.render(output),
         }
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+impl fmt::Display for MediaCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MediaQuery {
-    media_type: String,
+    #[serde(default)]
+    media_type: Option<String>,
     #[serde(default)]
     constraint: MediaConstraint,
     #[serde(default)]
     features: Vec<MediaCondition>,
 }
 
-impl MediaQuery {
-    pub fn new(
-        constraint: MediaConstraint,
-        media_type: String,
-        features: Vec<MediaCondition>,
-    ) -> Self {
-        Self {
-            media_type,
-            constraint,
-            features,
-        }
+impl MediaQuery {
+    pub fn new(
+        constraint: MediaConstraint,
+        media_type: String,
+        features: Vec<MediaCondition>,
+    ) -> Self {
+        Self {
+            media_type: Some(media_type),
+            constraint,
+            features,
+        }
+    }
+
+    // e.g. @media (max-width:600px), applies to all media types
+    pub fn without_type(constraint: MediaConstraint, features: Vec<MediaCondition>) -> Self {
+        Self {
+            media_type: None,
+            constraint,
+            features,
+        }
+    }
+
+    pub fn media_type(&self) -> Option<&str> {
+        self.media_type.as_deref()
+    }
+
+    pub fn constraint(&self) -> &MediaConstraint {
+        &self.constraint
+    }
+
+    pub fn features(&self) -> &[MediaCondition] {
+        &self.features
+    }
+
+    // only needs a type to follow, and a query without features is only its type
+    fn written_type(&self) -> Option<&str> {
+        match (&self.media_type, &self.constraint) {
+            (Some(media_type), _) => Some(media_type),
+            (None, MediaConstraint::Only) => Some("all"),
+            (None, _) if self.features.is_empty() => Some("all"),
+            (None, _) => None,
+        }
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        let joined_len = self
+            .features
+            .iter()
+            .map(|feature| feature.operand_len(true))
+            .sum::<usize>()
+            + self.features.len().saturating_sub(1) * 5;
+
+        7 + match self.written_type() {
+            Some(media_type) => {
+                self.constraint.as_str().len()
+                    + media_type.len()
+                    + joined_len
+                    + if self.features.is_empty() { 0 } else { 5 }
+            }
+            None => match (&self.constraint, self.features.as_slice()) {
+                (MediaConstraint::Not, [feature]) => 4 + feature.operand_len(false),
+                (MediaConstraint::Not, _) => 6 + joined_len,
+                (_, [feature]) => feature.estimated_len(),
+                _ => joined_len,
+            },
+        }
+    }
+
+    fn render_joined<W: Write>(&self, output: &mut W) -> fmt::Result {
+        for (i, feature) in self.features.iter().enumerate() {
+            if i > 0 {
+                output.write_str(" and ")?;
+            }
+            feature.render_operand(output, true)?;
+        }
+        Ok(())
+    }
+
+    fn render<W: Write>(&self, output: &mut W) -> fmt::Result {
+        output.write_str("@media ")?;
+        match self.written_type() {
+            Some(media_type) => {
+                output.write_str(self.constraint.as_str())?;
+                output.write_str(media_type)?;
+                if !self.features.is_empty() {
+                    output.write_str(" and ")?;
+                }
+                self.render_joined(output)
+            }
+            // without a type, not applies to all of the features together
+            None => match (&self.constraint, self.features.as_slice()) {
+                (MediaConstraint::Not, [feature]) => {
+                    output.write_str("not ")?;
+                    feature.render_operand(output, false)
+                }
+                (MediaConstraint::Not, _) => {
+                    output.write_str("not (")?;
+                    self.render_joined(output)?;
+                    output.write_char(')')
+                }
+                (_, [feature]) => feature.render(output),
+                _ => self.render_joined(output),
+            },
+        }
+    }
+}
+
+impl fmt::Display for MediaQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RuleSet {
     media_query: Option<MediaQuery>,
     rules: Vec<Rule>,
     #[serde(default)]
     sub_sets: Vec<RuleSet>,
+    #[serde(default)]
+    at_rules: Vec<AtRule>,
 }
 
 impl RuleSet {
@@ -248,60 +1367,158 @@ impl RuleSet {
             rules,
             sub_sets,
             media_query,
+            at_rules: vec![],
         }
     }
-}
 
-impl ToString for RuleSet {
-    fn to_string(&self) -> String {
-        let all_sets = format!(
-            "{}{}",
-            self.rules
+    // written before the rules of the set, inside its media query if there is one
+    pub fn at_rule(mut self, at_rule: impl Into<AtRule>) -> Self {
+        self.at_rules.push(at_rule.into());
+        self
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    pub fn sub_sets(&self) -> &[RuleSet] {
+        &self.sub_sets
+    }
+
+    pub fn media_query(&self) -> Option<&MediaQuery> {
+        self.media_query.as_ref()
+    }
+
+    pub fn at_rules(&self) -> &[AtRule] {
+        &self.at_rules
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        let all_sets = self.rules.iter().map(Rule::estimated_len).sum::<usize>()
+            + self
+                .sub_sets
                 .iter()
-                .map(Rule::to_string)
-                .collect::<Vec<String>>()
-                .join(""),
-            self.sub_sets
+                .map(RuleSet::estimated_len)
+                .sum::<usize>()
+            + self
+                .at_rules
                 .iter()
-                .map(RuleSet::to_string)
-                .collect::<Vec<String>>()
-                .join(""),
-        );
+                .map(AtRule::estimated_len)
+                .sum::<usize>();
 
         match &self.media_query {
             None => all_sets,
-            Some(query) => format!(
-                "@media {}{}{}{{{}}}",
-                match query.constraint {
-                    MediaConstraint::None => "",
-                    MediaConstraint::Only => "only ",
-                    MediaConstraint::Not => "not ",
-                },
-                query.media_type,
-                match query.features.len() {
-                    0 => String::new(),
-                    _ => format!(
-                        " and {}",
-                        query
-                            .features
-                            .iter()
-                            .map(MediaCondition::to_string)
-                            .collect::<Vec<String>>()
-                            .join("")
-                    ),
-                },
-                all_sets
-            ),
+            Some(query) => query.estimated_len() + all_sets + 2,
+        }
+    }
+
+    // streams the output without building it in memory first
+    pub fn write_to<W: Write>(&self, output: &mut W) -> fmt::Result {
+        self.render(output)
+    }
+
+    // e.g. CssFormat::expanded() for readable output in development builds
+    pub fn write_formatted<W: Write>(&self, output: &mut W, format: &CssFormat) -> fmt::Result {
+        self.render_formatted(output, format, 0)
+    }
+
+    pub fn to_string_formatted(&self, format: &CssFormat) -> String {
+        let mut output = String::with_capacity(self.estimated_len());
+        // writing to a String can not fail
+        let _ = self.render_formatted(&mut output, format, 0);
+        output
+    }
+
+    fn render<W: Write>(&self, output: &mut W) -> fmt::Result {
+        self.render_formatted(output, &CssFormat::compact(), 0)
+    }
+
+    pub(crate) fn render_formatted<W: Write>(
+        &self,
+        output: &mut W,
+        format: &CssFormat,
+        depth: usize,
+    ) -> fmt::Result {
+        let inner = match &self.media_query {
+            Some(query) => {
+                format.write_indent(output, depth)?;
+                query.render(output)?;
+                output.write_str(format.pick("{", " {\n"))?;
+                depth + 1
+            }
+            None => depth,
+        };
+
+        for at_rule in &self.at_rules {
+            at_rule.render_formatted(output, format, inner)?;
+        }
+
+        for rule in &self.rules {
+            rule.render_formatted(output, format, inner)?;
+        }
+
+        for set in &self.sub_sets {
+            set.render_formatted(output, format, inner)?;
+        }
+
+        match self.media_query.is_some() {
+            true => {
+                format.write_indent(output, depth)?;
+                output.write_str(format.pick("}", "}\n"))
+            }
+            false => Ok(()),
         }
     }
 }
 
+impl fmt::Display for RuleSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl RuleSet {
+    pub fn write_io<W: std::io::Write>(&self, output: &mut W) -> std::io::Result<()> {
+        crate::stream::write_io(output, |writer| self.render(writer))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl RuleSet {
+    pub fn to_bytes(&self) -> bytes::Bytes {
+        self.write_bytes(&mut bytes::BytesMut::new())
+    }
+
+    pub fn write_bytes(&self, buffer: &mut bytes::BytesMut) -> bytes::Bytes {
+        buffer.reserve(self.estimated_len());
+        // writing to a BytesMut can not fail
+        let _ = self.render(buffer);
+        buffer.split().freeze()
+    }
+}
+
+#[cfg(feature = "hashes")]
+impl RuleSet {
+    // rendered output along with its hash, computed in the same pass
+    pub fn to_string_with_hash(&self) -> (String, crate::hash::ContentHash) {
+        let mut output = String::with_capacity(self.estimated_len());
+        let mut writer = crate::hash::HashingWriter::new(&mut output);
+        // writing to a String can not fail
+        let _ = self.render(&mut writer);
+        let hash = writer.finish();
+        (output, hash)
+    }
+}
+
 #[cfg(test)]
 mod to_string {
+    use crate::color::Color;
     use crate::css::{
-        Combinator, Declaration, DeclarationValue, MediaCondition, MediaConstraint, MediaFeature,
-        MediaQuery, Rule, RuleSet, Selector,
+        AttributeCase, Combinator, CssFormat, Declaration, DeclarationValue, MediaCondition,
+        MediaConstraint, MediaFeature, MediaQuery, Rule, RuleSet, Selector,
     };
+    use crate::values::Unit;
 
     #[test]
     fn declaration() {
@@ -449,18 +1666,126 @@ mod to_string {
 
     #[test]
     fn attribute_value() {
-        let s = Selector::AttributeValue("title".to_string(), "hello".to_string());
+        let s = Selector::AttributeValue(
+            "title".to_string(),
+            "hello".to_string(),
+            AttributeCase::Default,
+        );
 
         assert_eq!(s.to_string(), "[title=\"hello\"]");
     }
 
     #[test]
     fn attribute_contains() {
-        let s = Selector::AttributeContains("title".to_string(), "hello".to_string());
+        let s = Selector::AttributeContains(
+            "title".to_string(),
+            "hello".to_string(),
+            AttributeCase::Default,
+        );
 
         assert_eq!(s.to_string(), "[title~=\"hello\"]");
     }
 
+    #[test]
+    fn typed_values() {
+        let declarations = [
+            DeclarationValue::length(1.5, Unit::Rem),
+            DeclarationValue::length(-0.0, Unit::Px),
+            DeclarationValue::percentage(33.25),
+            DeclarationValue::number(2),
+            DeclarationValue::keyword("auto"),
+            DeclarationValue::length(1, Unit::Fr),
+            DeclarationValue::color(Color::hsla(0.0, 100.0, 50.0, 0.4)),
+            DeclarationValue::comma_list(vec![
+                DeclarationValue::Basic("Fira Sans".to_string()),
+                DeclarationValue::space_list(vec![
+                    DeclarationValue::number(0),
+                    DeclarationValue::keyword("auto"),
+                ]),
+            ]),
+            DeclarationValue::url("img/a (1),\"b\".png"),
+            DeclarationValue::var("brand"),
+            DeclarationValue::var_or("--gap", DeclarationValue::length(1, Unit::Rem)),
+        ];
+
+        assert_eq!(
+            declarations
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "1.5rem",
+                "0px",
+                "33.25%",
+                "2",
+                "auto",
+                "1fr",
+                "#f006",
+                "\"Fira Sans\",0 auto",
+                "url(\"img/a (1),\\\"b\\\".png\")",
+                "var(--brand)",
+                "var(--gap,1rem)"
+            ]
+        );
+        for declaration in declarations {
+            assert_eq!(declaration.estimated_len(), declaration.to_string().len());
+        }
+    }
+
+    #[test]
+    fn escaping() {
+        let declaration = Declaration::new(
+            "font-family".to_string(),
+            DeclarationValue::Basic("Say \"Hi\" Sans\\".to_string()),
+        );
+        let selector = Selector::Chain(vec![
+            Selector::Class("1st".to_string()),
+            Selector::Id("-2".to_string()),
+            Selector::Class("a\u{7}b\0".to_string()),
+            Selector::Class("-".to_string()),
+        ]);
+
+        assert_eq!(
+            declaration.to_string(),
+            "font-family:\"Say \\\"Hi\\\" Sans\\\\\";"
+        );
+        assert_eq!(selector.to_string(), ".\\31 st#-\\32 .a\\7 b\u{fffd}.\\-");
+        assert_eq!(declaration.estimated_len(), declaration.to_string().len());
+        assert_eq!(selector.estimated_len(), selector.to_string().len());
+    }
+
+    #[test]
+    fn attribute_prefix_suffix_substring_and_dash() {
+        let s = Selector::Chain(vec![
+            Selector::AttributePrefix(
+                "href".to_string(),
+                "https:".to_string(),
+                AttributeCase::Default,
+            ),
+            Selector::AttributeSuffix(
+                "href".to_string(),
+                ".pdf".to_string(),
+                AttributeCase::Insensitive,
+            ),
+            Selector::AttributeSubstring(
+                "class".to_string(),
+                "btn".to_string(),
+                AttributeCase::Default,
+            ),
+            Selector::AttributeDash(
+                "lang".to_string(),
+                "en".to_string(),
+                AttributeCase::Sensitive,
+            ),
+        ]);
+
+        assert_eq!(
+            s.to_string(),
+            "[href^=\"https:\"][href$=\".pdf\" i][class*=\"btn\"][lang|=\"en\" s]"
+        );
+        assert_eq!(s.estimated_len(), s.to_string().len());
+    }
+
     #[test]
     fn chain() {
         let s = Selector::Chain(vec![
@@ -574,6 +1899,65 @@ mod to_string {
         )
     }
 
+    #[test]
+    fn rule_with_parent_references() {
+        let color = |value: &str| {
+            vec![Declaration::new(
+                "color".to_string(),
+                DeclarationValue::keyword(value),
+            )]
+        };
+        let rule = Rule::new(
+            Selector::Group(vec![
+                Selector::Class("tab".to_string()),
+                Selector::Class("link".to_string()),
+            ]),
+            color("gray"),
+            vec![
+                Rule::new(
+                    Selector::Chain(vec![
+                        Selector::Parent,
+                        Selector::Class("active".to_string()),
+                    ]),
+                    color("black"),
+                    vec![Rule::new(
+                        Selector::PseudoClass(Box::new(Selector::Parent), "hover".to_string()),
+                        color("blue"),
+                        vec![],
+                    )],
+                ),
+                Rule::new(
+                    Selector::Combinator(
+                        Box::new(Selector::Parent),
+                        Combinator::AdjacentSibling,
+                        Box::new(Selector::Parent),
+                    ),
+                    color("red"),
+                    vec![],
+                ),
+                Rule::new(
+                    Selector::Combinator(
+                        Box::new(Selector::Class("dark".to_string())),
+                        Combinator::Descendant,
+                        Box::new(Selector::Parent),
+                    ),
+                    color("white"),
+                    vec![Rule::new(
+                        Selector::Tag("span".to_string()),
+                        color("inherit"),
+                        vec![],
+                    )],
+                ),
+            ],
+        );
+
+        assert_eq!(
+            rule.to_string(),
+            ".tab,.link{color:gray;}.tab.active,.link.active{color:black;}.tab+.tab,.link+.link{color:red;}.dark .tab,.dark .link{color:white;}.dark .tab>span,.dark .link>span{color:inherit;}.tab.active:hover,.link.active:hover{color:blue;}"
+        );
+        assert_eq!(rule.estimated_len(), rule.to_string().len());
+    }
+
     #[test]
     fn rule_set() {
         let set = make_rule_set();
@@ -653,10 +2037,10 @@ mod to_string {
         set.media_query = Some(MediaQuery::new(
             MediaConstraint::None,
             "screen".to_string(),
-            vec![MediaCondition::And(
-                MediaFeature::new("max-width".to_string(), "1000px".to_string()),
-                MediaFeature::new("orientation".to_string(), "landscape".to_string()),
-            )],
+            vec![MediaCondition::And(vec![
+                MediaCondition::feature("max-width", "1000px"),
+                MediaCondition::feature("orientation", "landscape"),
+            ])],
         ));
 
         assert_eq!(
@@ -671,15 +2055,15 @@ mod to_string {
         set.media_query = Some(MediaQuery::new(
             MediaConstraint::None,
             "screen".to_string(),
-            vec![MediaCondition::Or(
-                MediaFeature::new("max-width".to_string(), "1000px".to_string()),
-                MediaFeature::new("orientation".to_string(), "landscape".to_string()),
-            )],
+            vec![MediaCondition::Or(vec![
+                MediaCondition::feature("max-width", "1000px"),
+                MediaCondition::feature("orientation", "landscape"),
+            ])],
         ));
 
         assert_eq!(
             set.to_string(),
-            "@media screen and (max-width:1000px) or (orientation:landscape){body{color:blue;}section{background-color:red;}h1{font-family:\"Times New Roman\";}}"
+            "@media screen and ((max-width:1000px) or (orientation:landscape)){body{color:blue;}section{background-color:red;}h1{font-family:\"Times New Roman\";}}"
         )
     }
 
@@ -689,18 +2073,92 @@ mod to_string {
         set.media_query = Some(MediaQuery::new(
             MediaConstraint::None,
             "screen".to_string(),
-            vec![MediaCondition::Not(
-                MediaFeature::new("max-width".to_string(), "1000px".to_string()),
-                MediaFeature::new("orientation".to_string(), "landscape".to_string()),
-            )],
+            vec![
+                MediaCondition::feature("max-width", "1000px"),
+                MediaCondition::negate(MediaCondition::feature("orientation", "landscape")),
+            ],
         ));
 
         assert_eq!(
             set.to_string(),
-            "@media screen and (max-width:1000px) not (orientation:landscape){body{color:blue;}section{background-color:red;}h1{font-family:\"Times New Roman\";}}"
+            "@media screen and (max-width:1000px) and (not (orientation:landscape)){body{color:blue;}section{background-color:red;}h1{font-family:\"Times New Roman\";}}"
         )
     }
 
+    #[test]
+    fn rule_set_with_query_with_nested_conditions() {
+        let mut set = RuleSet::new(vec![], vec![], None);
+        set.media_query = Some(MediaQuery::new(
+            MediaConstraint::None,
+            "screen".to_string(),
+            vec![
+                MediaCondition::feature("min-width", "40em"),
+                MediaCondition::or(vec![
+                    MediaCondition::and(vec![
+                        MediaCondition::feature("hover", "hover"),
+                        MediaCondition::feature("pointer", "fine"),
+                    ]),
+                    MediaCondition::negate(MediaCondition::feature("orientation", "portrait")),
+                    MediaCondition::group(MediaCondition::feature("color", "1")),
+                ]),
+            ],
+        ));
+
+        assert_eq!(
+            set.to_string(),
+            "@media screen and (min-width:40em) and (((hover:hover) and (pointer:fine)) or (not (orientation:portrait)) or ((color:1))){}"
+        );
+        assert_eq!(set.estimated_len(), set.to_string().len());
+    }
+
+    #[test]
+    fn rule_set_with_query_without_type() {
+        let width = MediaCondition::feature("max-width", "600px");
+        let either = MediaCondition::or(vec![
+            MediaCondition::feature("hover", "none"),
+            MediaCondition::feature("pointer", "coarse"),
+        ]);
+        let queries = [
+            (
+                MediaQuery::without_type(MediaConstraint::None, vec![width.clone()]),
+                "@media (max-width:600px){}",
+            ),
+            (
+                MediaQuery::without_type(MediaConstraint::None, vec![either.clone()]),
+                "@media (hover:none) or (pointer:coarse){}",
+            ),
+            (
+                MediaQuery::without_type(
+                    MediaConstraint::None,
+                    vec![width.clone(), either.clone()],
+                ),
+                "@media (max-width:600px) and ((hover:none) or (pointer:coarse)){}",
+            ),
+            (
+                MediaQuery::without_type(MediaConstraint::Not, vec![either.clone()]),
+                "@media not ((hover:none) or (pointer:coarse)){}",
+            ),
+            (
+                MediaQuery::without_type(MediaConstraint::Not, vec![width.clone(), either]),
+                "@media not ((max-width:600px) and ((hover:none) or (pointer:coarse))){}",
+            ),
+            (
+                MediaQuery::without_type(MediaConstraint::Only, vec![width]),
+                "@media only all and (max-width:600px){}",
+            ),
+            (
+                MediaQuery::without_type(MediaConstraint::None, vec![]),
+                "@media all{}",
+            ),
+        ];
+
+        for (query, expected) in queries {
+            let set = RuleSet::new(vec![], vec![], Some(query));
+            assert_eq!(set.to_string(), expected);
+            assert_eq!(set.estimated_len(), expected.len());
+        }
+    }
+
     #[test]
     fn rule_set_multiple_no_media_query_dont_nest() {
         let mut set = make_rule_set();
@@ -722,4 +2180,256 @@ mod to_string {
 
         assert_eq!(set.to_string(), "body{color:blue;}section{background-color:red;}h1{font-family:\"Times New Roman\";}@media screen{body{color:blue;}section{background-color:red;}h1{font-family:\"Times New Roman\";}}")
     }
+
+    #[test]
+    fn estimated_len_matches_output() {
+        let mut set = make_rule_set();
+        set.rules.push(Rule::new(
+            Selector::Group(vec![
+                Selector::Chain(vec![
+                    Selector::Tag("a".to_string()),
+                    Selector::AttributeValue(
+                        "rel".to_string(),
+                        "next".to_string(),
+                        AttributeCase::Default,
+                    ),
+                ]),
+                Selector::PseudoClass(
+                    Box::new(Selector::Class("link".to_string())),
+                    "hover".to_string(),
+                ),
+            ]),
+            vec![Declaration::new(
+                "color".to_string(),
+                DeclarationValue::Function(
+                    "rgb".to_string(),
+                    vec!["200".into(), "200".into(), "200".into()],
+                ),
+            )],
+            vec![Rule::new(
+                Selector::Id("main".to_string()),
+                vec![],
+                vec![Rule::new(Selector::Universal, vec![], vec![])],
+            )],
+        ));
+        let mut with_media = make_rule_set();
+        with_media.media_query = Some(MediaQuery::new(
+            MediaConstraint::Only,
+            "screen".to_string(),
+            vec![MediaCondition::And(vec![
+                MediaCondition::feature("max-width", "1000px"),
+                MediaCondition::feature("orientation", "landscape"),
+            ])],
+        ));
+        set.sub_sets.push(with_media);
+
+        assert_eq!(set.estimated_len(), set.to_string().len());
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn rule_set_to_bytes() {
+        let set = make_rule_set();
+
+        assert_eq!(set.to_bytes(), set.to_string().into_bytes());
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn rule_set_write_bytes() {
+        let mut buffer = bytes::BytesMut::new();
+        let set = make_rule_set();
+
+        assert_eq!(set.write_bytes(&mut buffer), set.to_string().into_bytes());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn selectors_ordered_and_deduplicated() {
+        use std::collections::BTreeSet;
+
+        let set = [
+            Selector::Class("b".to_string()),
+            Selector::Tag("body".to_string()),
+            Selector::Class("a".to_string()),
+            Selector::Tag("body".to_string()),
+        ]
+        .into_iter()
+        .collect::<BTreeSet<Selector>>();
+
+        assert_eq!(
+            set.into_iter().collect::<Vec<Selector>>(),
+            vec![
+                Selector::Tag("body".to_string()),
+                Selector::Class("a".to_string()),
+                Selector::Class("b".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn equal_rules_hash_equal() {
+        use std::collections::HashSet;
+
+        let set = [make_rule_set(), make_rule_set()]
+            .into_iter()
+            .flat_map(|set| set.rules)
+            .collect::<HashSet<Rule>>();
+
+        assert_eq!(set.len(), 3);
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_rule_sets_estimate_their_length() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let mut seed = 7u32;
+        for _ in 0..200 {
+            let bytes = (0..512)
+                .map(|_| {
+                    seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                    (seed >> 16) as u8
+                })
+                .collect::<Vec<u8>>();
+
+            if let Ok(set) = RuleSet::arbitrary(&mut Unstructured::new(&bytes)) {
+                assert_eq!(set.estimated_len(), set.to_string().len());
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn rule_set_write_io() {
+        let set = make_rule_set();
+        let mut output: Vec<u8> = vec![];
+        set.write_io(&mut output).unwrap();
+
+        assert_eq!(output, set.to_string().into_bytes());
+    }
+
+    #[test]
+    fn rule_set_json_round_trip() {
+        let set = make_rule_set();
+        let json = serde_json::to_string(&set).unwrap();
+
+        assert_eq!(serde_json::from_str::<RuleSet>(&json).unwrap(), set);
+    }
+
+    #[test]
+    fn rule_set_expanded() {
+        let set = RuleSet::new(
+            vec![Rule::new(
+                Selector::Tag("nav".to_string()),
+                vec![
+                    Declaration::new(
+                        "color".to_string(),
+                        DeclarationValue::Basic("blue".to_string()),
+                    ),
+                    Declaration::new(
+                        "margin".to_string(),
+                        DeclarationValue::Basic("0".to_string()),
+                    ),
+                ],
+                vec![Rule::new(Selector::Tag("a".to_string()), vec![], vec![])],
+            )],
+            vec![make_rule_set()],
+            Some(MediaQuery::new(
+                MediaConstraint::None,
+                "screen".to_string(),
+                vec![],
+            )),
+        );
+
+        assert_eq!(
+            set.to_string_formatted(&CssFormat::expanded()),
+            "@media screen {\n  nav {\n    color: blue;\n    margin: 0;\n  }\n  nav>a {\n  }\n  body {\n    color: blue;\n  }\n  section {\n    background-color: red;\n  }\n  h1 {\n    font-family: \"Times New Roman\";\n  }\n}\n"
+        );
+        assert_eq!(
+            set.to_string_formatted(&CssFormat::default()),
+            set.to_string()
+        );
+        assert!(set
+            .to_string_formatted(&CssFormat::expanded().indent(4))
+            .contains("\n        color: blue;\n"));
+    }
+
+    #[test]
+    fn rule_set_to_string_minified() {
+        let mut rules = RuleSet::parse(
+            ".card { margin: 0px 0.5em; color: #FFCC00; opacity: 0.75; --gap: 0px; transition: opacity 0s } .empty { }",
+        )
+        .unwrap()
+        .rules()
+        .to_vec();
+        rules.push(Rule::new(
+            Selector::Class("hint".to_string()),
+            vec![Declaration::new(
+                "border-color".to_string(),
+                DeclarationValue::Basic("#AABBCC".to_string()),
+            )],
+            vec![],
+        ));
+        let set = RuleSet::new(rules, vec![], None);
+
+        assert_eq!(
+            set.to_string_formatted(&CssFormat::minified()),
+            ".card{margin:0 .5em;color:#fc0;opacity:.75;--gap:0px;transition:opacity 0s}.empty{}.hint{border-color:#abc}"
+        );
+        assert_eq!(
+            RuleSet::parse(".a { flex: 0px } .b { flex: 1 0px } .c { -webkit-flex: 1 1 0.0px; flex-basis: 0px }")
+                .unwrap()
+                .to_string_formatted(&CssFormat::minified()),
+            ".a{flex:0px}.b{flex:1 0px}.c{-webkit-flex:1 1 0px;flex-basis:0}"
+        );
+        assert_eq!(
+            RuleSet::new(
+                vec![Rule::new(
+                    Selector::Class("a".to_string()),
+                    vec![
+                        Declaration::new(
+                            "margin".to_string(),
+                            DeclarationValue::Basic("0px".to_string()),
+                        ),
+                        Declaration::new(
+                            "padding".to_string(),
+                            DeclarationValue::Basic("0.5em".to_string()),
+                        ),
+                        Declaration::new(
+                            "flex".to_string(),
+                            DeclarationValue::Basic("0px".to_string()),
+                        ),
+                        Declaration::new(
+                            "opacity".to_string(),
+                            DeclarationValue::Basic("-0.25".to_string()),
+                        ),
+                        Declaration::new(
+                            "font-family".to_string(),
+                            DeclarationValue::Basic("0.5em Sans".to_string()),
+                        ),
+                    ],
+                    vec![],
+                )],
+                vec![],
+                None,
+            )
+            .to_string_formatted(&CssFormat::minified()),
+            ".a{margin:0;padding:.5em;flex:0px;opacity:-.25;font-family:\"0.5em Sans\"}"
+        );
+        assert_eq!(
+            set.to_string_formatted(&CssFormat::expanded().minify(true)),
+            ".card {\n  margin: 0 .5em;\n  color: #fc0;\n  opacity: .75;\n  --gap: 0px;\n  transition: opacity 0s\n}\n.empty {\n}\n.hint {\n  border-color: #abc\n}\n"
+        );
+    }
+
+    #[cfg(feature = "hashes")]
+    #[test]
+    fn rule_set_to_string_with_hash() {
+        let set = make_rule_set();
+        let (css, hash) = set.to_string_with_hash();
+
+        assert_eq!(css, set.to_string());
+        assert_eq!(hash, crate::hash::ContentHash::of(css.as_bytes()));
+    }
 }
@@ -0,0 +1,120 @@
+use alloc::string::ToString;
+#[cfg(any(feature = "json", feature = "yaml"))]
+use alloc::vec::Vec;
+
+use crate::css::RuleSet;
+use crate::error::Error;
+use crate::html::Node;
+
+// the same structures as the garnish pipeline, read from data files instead of scripts
+
+// documents holding a list of nodes are read as a fragment, like garnish scripts
+#[cfg(feature = "json")]
+pub fn make_html_from_json(input: &str) -> Result<Node, Error> {
+    serde_json::from_str::<Node>(input).or_else(|node_error| {
+        serde_json::from_str::<Vec<Node>>(input)
+            .map(Node::Fragment)
+            .map_err(|_| Error::Deserialize(node_error.to_string()))
+    })
+}
+
+#[cfg(feature = "json")]
+pub fn make_css_from_json(input: &str) -> Result<RuleSet, Error> {
+    serde_json::from_str(input).map_err(|e| Error::Deserialize(e.to_string()))
+}
+
+#[cfg(feature = "yaml")]
+pub fn make_html_from_yaml(input: &str) -> Result<Node, Error> {
+    serde_yaml::from_str::<Node>(input).or_else(|node_error| {
+        serde_yaml::from_str::<Vec<Node>>(input)
+            .map(Node::Fragment)
+            .map_err(|_| Error::Deserialize(node_error.to_string()))
+    })
+}
+
+#[cfg(feature = "yaml")]
+pub fn make_css_from_yaml(input: &str) -> Result<RuleSet, Error> {
+    serde_yaml::from_str(input).map_err(|e| Error::Deserialize(e.to_string()))
+}
+
+// toml documents are always tables so there is no fragment fallback
+#[cfg(feature = "toml")]
+pub fn make_html_from_toml(input: &str) -> Result<Node, Error> {
+    toml::from_str(input).map_err(|e| Error::Deserialize(e.to_string()))
+}
+
+#[cfg(feature = "toml")]
+pub fn make_css_from_toml(input: &str) -> Result<RuleSet, Error> {
+    toml::from_str(input).map_err(|e| Error::Deserialize(e.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::html::{Attribute, Node};
+
+    fn link() -> Node {
+        Node::element(
+            "a".to_string(),
+            vec![Attribute::new("href".to_string(), "/".to_string())],
+            vec![Node::text("Home".to_string())],
+        )
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn html_from_json() {
+        let input = r#"{"Element": {"tag": "a", "attributes": [{"name": "href", "value": "/"}], "children": [{"Text": "Home"}]}}"#;
+
+        assert_eq!(crate::make_html_from_json(input).unwrap(), link());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_list_is_fragment() {
+        let input = r#"[{"Text": "first"}, {"Comment": "second"}]"#;
+
+        assert_eq!(
+            crate::make_html_from_json(input).unwrap(),
+            Node::fragment(vec![
+                Node::text("first".to_string()),
+                Node::comment("second".to_string())
+            ])
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn css_from_json() {
+        let input = r#"{"media_query": null, "rules": [{"selector": {"Tag": "p"}, "declarations": [{"property": "color", "value": {"Basic": "red"}}]}]}"#;
+
+        assert_eq!(
+            crate::make_css_from_json(input).unwrap().to_string(),
+            "p{color:red;}"
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn invalid_json() {
+        assert!(matches!(
+            crate::make_html_from_json("{\"Element\": {}}"),
+            Err(crate::error::Error::Deserialize(_))
+        ));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn html_from_yaml() {
+        let input = "!Element\ntag: a\nattributes:\n  - name: href\n    value: /\nchildren:\n  - !Text Home\n";
+
+        assert_eq!(crate::make_html_from_yaml(input).unwrap(), link());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn html_from_toml() {
+        let input = "[Element]\ntag = \"a\"\nattributes = [{ name = \"href\", value = \"/\" }]\nchildren = [{ Text = \"Home\" }]\n";
+
+        assert_eq!(crate::make_html_from_toml(input).unwrap(), link());
+    }
+}
@@ -0,0 +1,107 @@
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "json")]
+use crate::error::Error;
+use crate::html::{Attribute, Node};
+
+// user-id, userId and data-user-id all become data-user-id, the same way dataset names map in the browser
+fn data_attribute_name(name: &str) -> String {
+    let name = name.strip_prefix("data-").unwrap_or(name);
+    let mut output = String::with_capacity(name.len() + 5);
+    output.push_str("data-");
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            output.push('-');
+        }
+        output.push(c.to_ascii_lowercase());
+    }
+    output
+}
+
+impl Attribute {
+    // numbers and bools are written with Display, e.g. data-count="3" and data-open="true"
+    pub fn data(name: &str, value: impl fmt::Display) -> Self {
+        Self::new(data_attribute_name(name), value.to_string())
+    }
+
+    #[cfg(feature = "json")]
+    pub fn data_json<T: serde::Serialize + ?Sized>(name: &str, value: &T) -> Result<Self, Error> {
+        serde_json::to_string(value)
+            .map(|json| Self::new(data_attribute_name(name), json))
+            .map_err(|e| Error::Serialize(e.to_string()))
+    }
+}
+
+impl Node {
+    pub fn data(&self, name: &str) -> Option<&str> {
+        let name = data_attribute_name(name);
+        self.attributes()
+            .iter()
+            .find(|a| a.name().eq_ignore_ascii_case(&name))
+            .and_then(Attribute::value)
+    }
+
+    // None when missing or not parsable as T
+    pub fn data_as<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.data(name).and_then(|value| value.parse().ok())
+    }
+
+    #[cfg(feature = "json")]
+    pub fn data_json<T: serde::de::DeserializeOwned>(
+        &self,
+        name: &str,
+    ) -> Result<Option<T>, Error> {
+        self.data(name)
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|e| Error::Deserialize(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::html::{Attribute, Node};
+
+    #[test]
+    fn names_and_values() {
+        let node = Node::element(
+            "div".to_string(),
+            vec![
+                Attribute::data("userId", 42),
+                Attribute::data("data-open", true),
+                Attribute::data("label", "a \"b\""),
+            ],
+            vec![],
+        );
+
+        assert_eq!(
+            node.to_string(),
+            "<div data-user-id=\"42\" data-open=\"true\" data-label=\"a &quot;b&quot;\"></div>"
+        );
+        assert_eq!(node.data_as::<u32>("user-id"), Some(42));
+        assert_eq!(node.data_as::<bool>("open"), Some(true));
+        assert_eq!(node.data_as::<u32>("label"), None);
+        assert_eq!(node.data("label"), Some("a \"b\""));
+        assert_eq!(node.data("missing"), None);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_values() {
+        let node = Node::element(
+            "div".to_string(),
+            vec![Attribute::data_json("items", &[1, 2, 3]).unwrap()],
+            vec![],
+        );
+
+        assert_eq!(node.to_string(), "<div data-items=\"[1,2,3]\"></div>");
+        assert_eq!(
+            node.data_json::<Vec<u8>>("items").unwrap(),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(node.data_json::<Vec<u8>>("other").unwrap(), None);
+        assert!(node.data_json::<String>("items").is_err());
+    }
+}
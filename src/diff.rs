@@ -0,0 +1,420 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::html::{Attribute, Node};
+
+// paths are child indices from the root into the Node tree, fragments are flattened into their
+// parent and do not count as a level, an inserted node ends up at the last index of its path.
+// every other node is one child even when the browser sees it differently: adjacent texts merge,
+// slots write nothing and raw or markdown markup can stand for many nodes
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub enum Patch {
+    Replace {
+        path: Vec<usize>,
+        node: Node,
+    },
+    Insert {
+        path: Vec<usize>,
+        node: Node,
+    },
+    Remove {
+        path: Vec<usize>,
+    },
+    SetAttribute {
+        path: Vec<usize>,
+        attribute: Attribute,
+    },
+    RemoveAttribute {
+        path: Vec<usize>,
+        name: String,
+    },
+    SetText {
+        path: Vec<usize>,
+        text: String,
+    },
+}
+
+fn flatten<'a>(children: &'a [Node], output: &mut Vec<&'a Node>) {
+    for child in children {
+        match child {
+            Node::Fragment(children) => flatten(children, output),
+            child => output.push(child),
+        }
+    }
+}
+
+fn child_path(path: &[usize], index: usize) -> Vec<usize> {
+    let mut path = path.to_vec();
+    path.push(index);
+    path
+}
+
+fn find_attribute<'a>(attributes: &'a [Attribute], name: &str) -> Option<&'a Attribute> {
+    attributes
+        .iter()
+        .find(|a| a.name().eq_ignore_ascii_case(name))
+}
+
+fn diff_attributes(old: &[Attribute], new: &[Attribute], path: &[usize], patches: &mut Vec<Patch>) {
+    for attribute in old {
+        if find_attribute(new, attribute.name()).is_none() {
+            patches.push(Patch::RemoveAttribute {
+                path: path.to_vec(),
+                name: attribute.name().to_string(),
+            });
+        }
+    }
+
+    for attribute in new {
+        if find_attribute(old, attribute.name()) != Some(attribute) {
+            patches.push(Patch::SetAttribute {
+                path: path.to_vec(),
+                attribute: attribute.clone(),
+            });
+        }
+    }
+}
+
+// children are matched by position, changes past the shorter list become inserts or removes
+fn diff_children(old: &[Node], new: &[Node], path: &[usize], patches: &mut Vec<Patch>) {
+    let (mut old_children, mut new_children) = (Vec::new(), Vec::new());
+    flatten(old, &mut old_children);
+    flatten(new, &mut new_children);
+
+    for (index, (old, new)) in old_children.iter().zip(&new_children).enumerate() {
+        diff_node(old, new, &child_path(path, index), patches);
+    }
+
+    for (index, node) in new_children.iter().enumerate().skip(old_children.len()) {
+        patches.push(Patch::Insert {
+            path: child_path(path, index),
+            node: (*node).clone(),
+        });
+    }
+
+    // from the end so the earlier indices stay valid
+    for index in (new_children.len()..old_children.len()).rev() {
+        patches.push(Patch::Remove {
+            path: child_path(path, index),
+        });
+    }
+}
+
+fn diff_node(old: &Node, new: &Node, path: &[usize], patches: &mut Vec<Patch>) {
+    match (old, new) {
+        (Node::Text(old), Node::Text(new)) => {
+            if old != new {
+                patches.push(Patch::SetText {
+                    path: path.to_vec(),
+                    text: new.clone(),
+                });
+            }
+        }
+        (
+            Node::Element {
+                tag: old_tag,
+                attributes: old_attributes,
+                children: old_children,
+                self_closing: old_self_closing,
+            },
+            Node::Element {
+                tag: new_tag,
+                attributes: new_attributes,
+                children: new_children,
+                self_closing: new_self_closing,
+            },
+        ) if old_tag.eq_ignore_ascii_case(new_tag) && old_self_closing == new_self_closing => {
+            diff_attributes(old_attributes, new_attributes, path, patches);
+            diff_children(old_children, new_children, path, patches);
+        }
+        (Node::Fragment(old), Node::Fragment(new)) => diff_children(old, new, path, patches),
+        (old, new) => {
+            if old != new {
+                patches.push(Patch::Replace {
+                    path: path.to_vec(),
+                    node: new.clone(),
+                });
+            }
+        }
+    }
+}
+
+// patches turning the old tree into the new one, in the order they have to be applied
+pub fn diff(old: &Node, new: &Node) -> Vec<Patch> {
+    let mut patches = Vec::new();
+    diff_node(old, new, &[], &mut patches);
+    patches
+}
+
+// number of nodes a node stands for once fragments are flattened
+fn flat_len(node: &Node) -> usize {
+    match node {
+        Node::Fragment(children) => children.iter().map(flat_len).sum(),
+        _ => 1,
+    }
+}
+
+// the list holding the child at a flattened index and its position in that list
+fn locate(children: &mut Vec<Node>, mut index: usize) -> Option<(&mut Vec<Node>, usize)> {
+    let mut position = 0;
+    while index >= flat_len(children.get(position)?) {
+        index -= flat_len(&children[position]);
+        position += 1;
+    }
+    match &children[position] {
+        Node::Fragment(_) => match &mut children[position] {
+            Node::Fragment(children) => locate(children, index),
+            _ => None,
+        },
+        _ => Some((children, position)),
+    }
+}
+
+fn missing(path: &[usize]) -> Error {
+    Error::Patch(format!("no node at path {:?}", path))
+}
+
+fn node_at<'a>(root: &'a mut Node, path: &[usize]) -> Result<&'a mut Node, Error> {
+    path.iter().try_fold(root, |node, index| {
+        node.children_mut()
+            .and_then(|children| locate(children, *index))
+            .map(|(children, position)| &mut children[position])
+            .ok_or_else(|| missing(path))
+    })
+}
+
+// the children of the node holding the last index of the path and that index
+fn parent_at<'a>(root: &'a mut Node, path: &[usize]) -> Result<(&'a mut Vec<Node>, usize), Error> {
+    let (index, parent) = path.split_last().ok_or_else(|| missing(path))?;
+    let children = node_at(root, parent)?
+        .children_mut()
+        .ok_or_else(|| missing(path))?;
+    Ok((children, *index))
+}
+
+fn apply_patch(root: &mut Node, patch: &Patch) -> Result<(), Error> {
+    match patch {
+        Patch::Replace { path, node } => *node_at(root, path)? = node.clone(),
+        Patch::Insert { path, node } => {
+            let (children, index) = parent_at(root, path)?;
+            let len: usize = children.iter().map(flat_len).sum();
+            if index == len {
+                children.push(node.clone());
+            } else {
+                let (children, position) = locate(children, index).ok_or_else(|| missing(path))?;
+                children.insert(position, node.clone());
+            }
+        }
+        Patch::Remove { path } => {
+            let (children, index) = parent_at(root, path)?;
+            let (children, position) = locate(children, index).ok_or_else(|| missing(path))?;
+            children.remove(position);
+        }
+        Patch::SetAttribute { path, attribute } => {
+            if !node_at(root, path)?.set_attribute(attribute.clone()) {
+                return Err(Error::Patch(format!("no element at path {:?}", path)));
+            }
+        }
+        Patch::RemoveAttribute { path, name } => {
+            let node = node_at(root, path)?;
+            if node.tag().is_none() {
+                return Err(Error::Patch(format!("no element at path {:?}", path)));
+            }
+            node.remove_attribute(name);
+        }
+        Patch::SetText { path, text } => match node_at(root, path)? {
+            Node::Text(existing) => *existing = text.clone(),
+            _ => return Err(Error::Patch(format!("no text at path {:?}", path))),
+        },
+    }
+    Ok(())
+}
+
+// applies patches from diff in order, fragments of the tree are kept where the paths allow,
+// so the result renders the same as the new tree even when its fragments differ
+pub fn apply(root: &mut Node, patches: &[Patch]) -> Result<(), Error> {
+    patches
+        .iter()
+        .try_for_each(|patch| apply_patch(root, patch))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::diff::{apply, diff, Patch};
+    use crate::error::Error;
+    use crate::html::{Attribute, Node};
+
+    #[test]
+    fn unchanged() {
+        let node = crate::html! { p class="a" { "x" } };
+
+        assert_eq!(diff(&node, &node.clone()), vec![]);
+    }
+
+    #[test]
+    fn text_and_attributes() {
+        let old = crate::html! { div class="a" id="x" { p { "one" } } };
+        let new = crate::html! { div class="b" title="t" { p { "two" } } };
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                Patch::RemoveAttribute {
+                    path: vec![],
+                    name: "id".to_string()
+                },
+                Patch::SetAttribute {
+                    path: vec![],
+                    attribute: Attribute::new("class".to_string(), "b".to_string())
+                },
+                Patch::SetAttribute {
+                    path: vec![],
+                    attribute: Attribute::new("title".to_string(), "t".to_string())
+                },
+                Patch::SetText {
+                    path: vec![0, 0],
+                    text: "two".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn inserts_removes_and_replacements() {
+        let old = crate::html! {
+            ul {
+                li { "a" }
+                (crate::html! { li {} "b" })
+            }
+        };
+        let new = crate::html! { ul { li { "a" } "b" } };
+        let longer = crate::html! { ul { li { "a" } li {} "b" li {} } };
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                Patch::Replace {
+                    path: vec![1],
+                    node: Node::text("b".to_string())
+                },
+                Patch::Remove { path: vec![2] },
+            ]
+        );
+        assert_eq!(
+            diff(&old, &longer),
+            vec![Patch::Insert {
+                path: vec![3],
+                node: crate::html! { li {} }
+            }]
+        );
+    }
+
+    #[test]
+    fn node_tree_paths() {
+        let old = crate::html! {
+            div {
+                "a"
+                "b"
+                (Node::raw("<i>x</i><b>y</b>".to_string()))
+                p { "one" }
+            }
+        };
+        let new = crate::html! {
+            div {
+                "a"
+                "c"
+                (Node::raw("<i>x</i>".to_string()))
+                p { "two" }
+            }
+        };
+        let patches = diff(&old, &new);
+
+        assert_eq!(
+            patches,
+            vec![
+                Patch::SetText {
+                    path: vec![1],
+                    text: "c".to_string()
+                },
+                Patch::Replace {
+                    path: vec![2],
+                    node: Node::raw("<i>x</i>".to_string())
+                },
+                Patch::SetText {
+                    path: vec![3, 0],
+                    text: "two".to_string()
+                },
+            ]
+        );
+
+        let mut patched = old.clone();
+        apply(&mut patched, &patches).unwrap();
+        assert_eq!(patched, new);
+    }
+
+    #[test]
+    fn round_trip() {
+        let old = crate::html! {
+            div class="a" id="x" {
+                p { "one" }
+                ul { li { "a" } }
+                "tail"
+            }
+        };
+        let new = crate::html! {
+            div class="b" {
+                p title="t" { "two" }
+                ul { li { "b" } li { "c" } }
+            }
+        };
+
+        for (old, new) in [(&old, &new), (&new, &old)] {
+            let mut patched = old.clone();
+            apply(&mut patched, &diff(old, new)).unwrap();
+            assert_eq!(&patched, new);
+        }
+    }
+
+    #[test]
+    fn fragments() {
+        let old = crate::html! {
+            ul {
+                (Node::fragment(vec![crate::html! { li { "a" } }]))
+                (crate::html! { li {} "b" })
+            }
+        };
+        let new = crate::html! {
+            ul {
+                li { "x" }
+                (crate::html! { "b" li {} })
+                li {}
+            }
+        };
+        let mut patched = old.clone();
+        apply(&mut patched, &diff(&old, &new)).unwrap();
+
+        assert_eq!(patched.to_string(), new.to_string());
+        assert_eq!(
+            apply(
+                &mut patched,
+                &[Patch::Remove {
+                    path: vec![0, 0, 0]
+                }]
+            ),
+            Err(Error::Patch("no node at path [0, 0, 0]".to_string()))
+        );
+        assert_eq!(
+            apply(
+                &mut patched,
+                &[Patch::SetText {
+                    path: vec![0],
+                    text: "y".to_string()
+                }]
+            ),
+            Err(Error::Patch("no text at path [0]".to_string()))
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use scraper::node::Node as DomNode;
+use scraper::{ElementRef, Html};
+
+use crate::html::{Attribute, Node};
+
+fn convert(node: ego_tree::NodeRef<DomNode>) -> Option<Node> {
+    match node.value() {
+        DomNode::Text(text) => Some(Node::text(text.to_string())),
+        DomNode::Comment(comment) => Some(Node::comment(comment.trim().to_string())),
+        DomNode::Element(element) => {
+            let attributes = element
+                .attrs()
+                .map(|(name, value)| match value.is_empty() {
+                    true => Attribute::toggle(name.to_string()),
+                    false => Attribute::new(name.to_string(), value.to_string()),
+                })
+                .collect();
+
+            Some(Node::element(
+                element.name().to_string(),
+                attributes,
+                node.children().filter_map(convert).collect(),
+            ))
+        }
+        // doctype and processing instructions have no Node equivalent
+        _ => None,
+    }
+}
+
+pub fn node_from_element(element: ElementRef) -> Node {
+    // an ElementRef always wraps an element
+    convert(*element).unwrap_or_else(|| Node::text(String::new()))
+}
+
+// root html element of a parsed document, the parser always creates one
+pub fn node_from_document(document: &Html) -> Node {
+    node_from_element(document.root_element())
+}
+
+// top level nodes of a parsed fragment, without the html wrapper the parser adds
+pub fn nodes_from_fragment(fragment: &Html) -> Vec<Node> {
+    fragment
+        .root_element()
+        .children()
+        .filter_map(convert)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use scraper::Html;
+
+    use crate::dom::{node_from_document, nodes_from_fragment};
+
+    #[test]
+    fn document() {
+        let document = Html::parse_document(
+            "<!DOCTYPE html><html lang=\"en\"><head><title>Home</title></head><body><p class=\"intro\">Hello</p></body></html>",
+        );
+
+        assert_eq!(
+            node_from_document(&document).to_string(),
+            "<html lang=\"en\"><head><title>Home</title></head><body><p class=\"intro\">Hello</p></body></html>"
+        );
+    }
+
+    #[test]
+    fn fragment() {
+        let fragment = Html::parse_fragment("<p>One</p><!-- note --><input disabled>");
+
+        let nodes = nodes_from_fragment(&fragment)
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            nodes,
+            vec!["<p>One</p>", "<!-- note -->", "<input disabled>"]
+        );
+    }
+}
@@ -0,0 +1,340 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::error::Error;
+use crate::html::{Attribute, Node};
+
+// tag used when an abbreviation has none, e.g. ul>.item is ul>li.item
+fn implicit_tag(parent: &str) -> &'static str {
+    match parent {
+        "ul" | "ol" | "menu" => "li",
+        "table" | "tbody" | "thead" | "tfoot" => "tr",
+        "tr" => "td",
+        "select" | "optgroup" | "datalist" => "option",
+        _ => "div",
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '$' | '@')
+}
+
+// $ is replaced by the position in the repetition, $$ pads it to two digits and so on
+fn number(text: &str, index: usize) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            output.push(c);
+            continue;
+        }
+        let mut width = 1;
+        while chars.next_if_eq(&'$').is_some() {
+            width += 1;
+        }
+        output.push_str(&format!("{:0width$}", index, width = width));
+    }
+    output
+}
+
+fn number_node(node: &Node, index: usize) -> Node {
+    match node {
+        Node::Text(text) => Node::Text(number(text, index)),
+        Node::Element {
+            tag,
+            attributes,
+            children,
+            self_closing,
+        } => Node::Element {
+            tag: tag.clone(),
+            attributes: attributes
+                .iter()
+                .map(|a| match a.value() {
+                    Some(value) => Attribute::new(number(a.name(), index), number(value, index)),
+                    None => Attribute::toggle(number(a.name(), index)),
+                })
+                .collect(),
+            children: children.iter().map(|c| number_node(c, index)).collect(),
+            self_closing: *self_closing,
+        },
+        Node::Fragment(children) => {
+            Node::Fragment(children.iter().map(|c| number_node(c, index)).collect())
+        }
+        node => node.clone(),
+    }
+}
+
+struct Expander<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Expander<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::Parse(format!("{} at byte {}", message, self.position))
+    }
+
+    fn next_is(&self, c: char) -> bool {
+        self.rest().starts_with(c)
+    }
+
+    fn take_while<P: Fn(char) -> bool>(&mut self, predicate: P) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.position += len;
+        &rest[..len]
+    }
+
+    fn take_through(&mut self, terminator: char, message: &str) -> Result<&'a str, Error> {
+        let rest = self.rest();
+        match rest.find(terminator) {
+            Some(end) => {
+                self.position += end + terminator.len_utf8();
+                Ok(&rest[..end])
+            }
+            None => Err(self.error(message)),
+        }
+    }
+
+    fn name(&mut self, what: &str) -> Result<&'a str, Error> {
+        match self.take_while(is_name_char) {
+            "" => Err(self.error(&format!("missing {} name", what))),
+            name => Ok(name),
+        }
+    }
+
+    // [a=1 b="x y" c]
+    fn attributes(&mut self, attributes: &mut Vec<Attribute>) -> Result<(), Error> {
+        loop {
+            self.take_while(char::is_whitespace);
+            if self.next_is(']') {
+                self.position += 1;
+                return Ok(());
+            }
+            let name = self.take_while(|c| !c.is_whitespace() && !matches!(c, '=' | ']'));
+            if name.is_empty() {
+                return Err(self.error("unterminated attribute list"));
+            }
+            if !self.next_is('=') {
+                attributes.push(Attribute::toggle(name.to_string()));
+                continue;
+            }
+            self.position += 1;
+            let value = match self.rest().chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    self.position += 1;
+                    self.take_through(quote, "unterminated attribute value")?
+                }
+                _ => self.take_while(|c| !c.is_whitespace() && c != ']'),
+            };
+            attributes.push(Attribute::new(name.to_string(), value.to_string()));
+        }
+    }
+
+    // tag#id.class[attributes]{text}*count, any part can be left out
+    fn element(&mut self, parent: &str) -> Result<Node, Error> {
+        let start = self.position;
+        let tag = self.take_while(is_name_char);
+        let (mut id, mut classes, mut attributes, mut text) = (None, Vec::new(), Vec::new(), None);
+        loop {
+            match self.rest().chars().next() {
+                Some('#') => {
+                    self.position += 1;
+                    id = Some(self.name("id")?);
+                }
+                Some('.') => {
+                    self.position += 1;
+                    classes.push(self.name("class")?);
+                }
+                Some('[') => {
+                    self.position += 1;
+                    self.attributes(&mut attributes)?;
+                }
+                Some('{') => {
+                    self.position += 1;
+                    text = Some(self.take_through('}', "unterminated text")?);
+                }
+                _ => break,
+            }
+        }
+        if self.position == start {
+            return Err(self.error("expected an element"));
+        }
+
+        // {text} on its own is a text node
+        if let ("", None, true, true, Some(text)) =
+            (tag, id, classes.is_empty(), attributes.is_empty(), text)
+        {
+            return Ok(Node::text(text.to_string()));
+        }
+
+        let tag = match tag {
+            "" => implicit_tag(parent),
+            tag => tag,
+        };
+        let mut all = Vec::new();
+        if let Some(id) = id {
+            all.push(Attribute::new("id".to_string(), id.to_string()));
+        }
+        if !classes.is_empty() {
+            all.push(Attribute::new("class".to_string(), classes.join(" ")));
+        }
+        all.extend(attributes);
+        let children = text
+            .map(|t| Node::text(t.to_string()))
+            .into_iter()
+            .collect();
+        Ok(Node::element(tag.to_string(), all, children))
+    }
+
+    fn count(&mut self) -> Result<usize, Error> {
+        if !self.next_is('*') {
+            return Ok(1);
+        }
+        self.position += 1;
+        self.take_while(|c| c.is_ascii_digit())
+            .parse()
+            .map_err(|_| self.error("missing repeat count"))
+    }
+
+    // an element with its children or a group, repeated as often as asked
+    fn item(&mut self, parent: &str, nodes: &mut Vec<Node>) -> Result<usize, Error> {
+        let mut node = match self.next_is('(') {
+            true => {
+                self.position += 1;
+                let (children, _) = self.siblings(parent)?;
+                if !self.next_is(')') {
+                    return Err(self.error("unclosed group"));
+                }
+                self.position += 1;
+                Node::fragment(children)
+            }
+            false => self.element(parent)?,
+        };
+        let count = self.count()?;
+
+        let mut climbs = 0;
+        if self.next_is('>') {
+            self.position += 1;
+            let tag = node.tag().unwrap_or(parent).to_ascii_lowercase();
+            let (children, up) = self.siblings(&tag)?;
+            if let Some(existing) = node.children_mut() {
+                existing.extend(children);
+            }
+            climbs = up;
+        }
+
+        match count {
+            1 => nodes.push(node),
+            count => nodes.extend((1..=count).map(|index| number_node(&node, index))),
+        }
+        Ok(climbs)
+    }
+
+    // siblings joined with +, returns how many levels a trailing ^ climbs out of the list
+    fn siblings(&mut self, parent: &str) -> Result<(Vec<Node>, usize), Error> {
+        let mut nodes = Vec::new();
+        loop {
+            let climbs = self.item(parent, &mut nodes)?;
+            match climbs {
+                0 => (),
+                1 => continue,
+                climbs => return Ok((nodes, climbs - 1)),
+            }
+            match self.rest().chars().next() {
+                Some('+') => self.position += 1,
+                Some('^') => {
+                    let climbs = self.take_while(|c| c == '^').len();
+                    return Ok((nodes, climbs));
+                }
+                _ => return Ok((nodes, 0)),
+            }
+        }
+    }
+}
+
+impl Node {
+    // ul>li.item$*3>a[href=#]{Item $} and the other emmet operators: + for siblings, ^ to climb up
+    // and () for groups. Several top level nodes are returned as a fragment.
+    pub fn from_emmet(abbreviation: &str) -> Result<Node, Error> {
+        let mut expander = Expander {
+            input: abbreviation.trim(),
+            position: 0,
+        };
+        let mut roots = Vec::new();
+        while expander.position < expander.input.len() {
+            let (nodes, _) = expander.siblings("")?;
+            roots.extend(nodes);
+            if expander.next_is(')') {
+                return Err(expander.error("unexpected )"));
+            }
+        }
+        Ok(match roots.len() {
+            1 => roots.remove(0),
+            _ => Node::Fragment(roots),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Error;
+    use crate::html::Node;
+
+    fn expand(abbreviation: &str) -> String {
+        Node::from_emmet(abbreviation).unwrap().to_string()
+    }
+
+    #[test]
+    fn children_and_siblings() {
+        assert_eq!(
+            expand("div.card>h2.title+p.body"),
+            "<div class=\"card\"><h2 class=\"title\"></h2><p class=\"body\"></p></div>"
+        );
+        assert_eq!(
+            expand("header>nav#main^main+footer"),
+            "<header><nav id=\"main\"></nav></header><main></main><footer></footer>"
+        );
+        assert_eq!(
+            expand("div>(a+b)*2+{end}"),
+            "<div><a></a><b></b><a></a><b></b>end</div>"
+        );
+    }
+
+    #[test]
+    fn repetition_and_implicit_tags() {
+        assert_eq!(
+            expand("ul>.item$*3>a[href=/$ title=\"Item $\"]{Item $$}"),
+            "<ul><li class=\"item1\"><a href=\"/1\" title=\"Item 1\">Item 01</a></li><li class=\"item2\"><a href=\"/2\" title=\"Item 2\">Item 02</a></li><li class=\"item3\"><a href=\"/3\" title=\"Item 3\">Item 03</a></li></ul>"
+        );
+        assert_eq!(
+            expand("table>.row>{x}+.cell{y}"),
+            "<table><tr class=\"row\">x<td class=\"cell\">y</td></tr></table>"
+        );
+        assert_eq!(
+            expand("input[type=checkbox checked]"),
+            "<input type=\"checkbox\" checked>"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Node::from_emmet("div>"),
+            Err(Error::Parse("expected an element at byte 4".to_string()))
+        );
+        assert_eq!(
+            Node::from_emmet("(a+b"),
+            Err(Error::Parse("unclosed group at byte 4".to_string()))
+        );
+        assert_eq!(
+            Node::from_emmet("a{text"),
+            Err(Error::Parse("unterminated text at byte 2".to_string()))
+        );
+    }
+}
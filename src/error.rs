@@ -0,0 +1,206 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::validate::ValidationError;
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Error {
+    Garnish(String),     // lexing, parsing, building or executing the garnish script
+    Deserialize(String), // converting the script's resulting data into html/css types
+    Css(String),         // css that can not be parsed, printed or represented by the css types
+    // reading scripts or serving requests, shared so errors stay cloneable
+    #[cfg(feature = "std")]
+    Io {
+        context: String, // what was being read or served, e.g. the script path
+        source: std::sync::Arc<std::io::Error>,
+    },
+    Parse(String),                    // html that can not be parsed into nodes
+    Serialize(String),                // converting html/css types into another data format
+    Patch(String),                    // a patch whose path does not lead to a node it applies to
+    Validation(Vec<ValidationError>), // what validate or validate_css found
+}
+
+impl Error {
+    #[cfg(feature = "std")]
+    pub fn io(context: impl Into<String>, source: std::io::Error) -> Self {
+        Error::Io {
+            context: context.into(),
+            source: std::sync::Arc::new(source),
+        }
+    }
+
+    fn message(&self) -> Option<&str> {
+        match self {
+            Error::Garnish(message)
+            | Error::Deserialize(message)
+            | Error::Css(message)
+            | Error::Parse(message)
+            | Error::Serialize(message)
+            | Error::Patch(message) => Some(message),
+            _ => None,
+        }
+    }
+}
+
+// io errors are equal when they happened doing the same thing for the same reason
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            #[cfg(feature = "std")]
+            (
+                Error::Io { context, source },
+                Error::Io {
+                    context: other_context,
+                    source: other_source,
+                },
+            ) => context == other_context && source.kind() == other_source.kind(),
+            (Error::Validation(errors), Error::Validation(other_errors)) => errors == other_errors,
+            _ => {
+                core::mem::discriminant(self) == core::mem::discriminant(other)
+                    && self.message() == other.message()
+            }
+        }
+    }
+}
+
+impl Eq for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Garnish(message) => write!(f, "garnish error: {}", message),
+            Error::Deserialize(message) => write!(f, "deserialization error: {}", message),
+            Error::Css(message) => write!(f, "css error: {}", message),
+            #[cfg(feature = "std")]
+            Error::Io { context, source } => write!(f, "io error: {}: {}", context, source),
+            Error::Parse(message) => write!(f, "parse error: {}", message),
+            Error::Serialize(message) => write!(f, "serialization error: {}", message),
+            Error::Patch(message) => write!(f, "patch error: {}", message),
+            Error::Validation(errors) => {
+                f.write_str("validation error: ")?;
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("; ")?;
+                    }
+                    write!(f, "{}", error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+// lets the findings of validate and validate_css be returned with ?
+impl From<Vec<ValidationError>> for Error {
+    fn from(errors: Vec<ValidationError>) -> Self {
+        Error::Validation(errors)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Error;
+    use crate::html::Node;
+    use crate::validate::validate;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            Error::Garnish("unexpected token".to_string()).to_string(),
+            "garnish error: unexpected token"
+        );
+        assert_eq!(
+            Error::Deserialize("missing field `tag`".to_string()).to_string(),
+            "deserialization error: missing field `tag`"
+        );
+        assert_eq!(
+            Error::Css("unexpected token".to_string()).to_string(),
+            "css error: unexpected token"
+        );
+        assert_eq!(
+            Error::Parse("unterminated tag at byte 3".to_string()).to_string(),
+            "parse error: unterminated tag at byte 3"
+        );
+        assert_eq!(
+            Error::Serialize("key must be a string".to_string()).to_string(),
+            "serialization error: key must be a string"
+        );
+        assert_eq!(
+            Error::Patch("no node at path [0, 2]".to_string()).to_string(),
+            "patch error: no node at path [0, 2]"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn io_source() {
+        use std::error::Error as _;
+
+        let error = Error::io(
+            "pages/index.garnish",
+            std::io::Error::new(std::io::ErrorKind::NotFound, "file not found"),
+        );
+
+        assert_eq!(
+            error.to_string(),
+            "io error: pages/index.garnish: file not found"
+        );
+        assert_eq!(error.source().unwrap().to_string(), "file not found");
+        assert_eq!(
+            error.clone(),
+            Error::io("pages/index.garnish", std::io::ErrorKind::NotFound.into())
+        );
+        assert!(Error::Css("x".to_string()).source().is_none());
+    }
+
+    #[test]
+    fn validation() {
+        fn check(root: &Node) -> Result<(), Error> {
+            let errors = validate(root);
+            if !errors.is_empty() {
+                Err(errors)?;
+            }
+            Ok(())
+        }
+
+        let error = check(&Node::element(
+            "img".to_string(),
+            vec![],
+            vec![Node::text("x".to_string())],
+        ))
+        .unwrap_err();
+
+        assert!(matches!(&error, Error::Validation(errors) if errors.len() == 1));
+        assert_eq!(
+            error.to_string(),
+            "validation error: img: <img> is a void element and can not have children"
+        );
+        assert_eq!(
+            check(&Node::element("p".to_string(), vec![], vec![])),
+            Ok(())
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn is_std_error() {
+        fn boxed() -> Result<(), Box<dyn std::error::Error>> {
+            Err(Error::Garnish("failed".to_string()))?;
+            Ok(())
+        }
+
+        assert_eq!(boxed().unwrap_err().to_string(), "garnish error: failed");
+    }
+}
@@ -0,0 +1,235 @@
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::css::{Declaration, DeclarationValue};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum Direction {
+    Row,
+    Column,
+    RowReverse,
+    ColumnReverse,
+}
+
+impl Direction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Row => "row",
+            Direction::Column => "column",
+            Direction::RowReverse => "row-reverse",
+            Direction::ColumnReverse => "column-reverse",
+        }
+    }
+}
+
+// placement along the main axis
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum Justify {
+    Start,
+    End,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+impl Justify {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Justify::Start => "flex-start",
+            Justify::End => "flex-end",
+            Justify::Center => "center",
+            Justify::SpaceBetween => "space-between",
+            Justify::SpaceAround => "space-around",
+            Justify::SpaceEvenly => "space-evenly",
+        }
+    }
+}
+
+// placement along the cross axis
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum Align {
+    Start,
+    End,
+    Center,
+    Baseline,
+    Stretch,
+}
+
+impl Align {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Align::Start => "flex-start",
+            Align::End => "flex-end",
+            Align::Center => "center",
+            Align::Baseline => "baseline",
+            Align::Stretch => "stretch",
+        }
+    }
+}
+
+// a flex container, e.g. flex::row().center().gap(px(8)).declarations()
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct Flex {
+    direction: Direction,
+    wrap: bool,
+    inline: bool,
+    justify: Option<Justify>,
+    align: Option<Align>,
+    gap: Option<DeclarationValue>,
+}
+
+pub fn row() -> Flex {
+    Flex::new(Direction::Row)
+}
+
+pub fn column() -> Flex {
+    Flex::new(Direction::Column)
+}
+
+impl Flex {
+    pub fn new(direction: Direction) -> Self {
+        Self {
+            direction,
+            wrap: false,
+            inline: false,
+            justify: None,
+            align: None,
+            gap: None,
+        }
+    }
+
+    pub fn reverse(mut self) -> Self {
+        self.direction = match self.direction {
+            Direction::Row => Direction::RowReverse,
+            Direction::Column => Direction::ColumnReverse,
+            Direction::RowReverse => Direction::Row,
+            Direction::ColumnReverse => Direction::Column,
+        };
+        self
+    }
+
+    pub fn wrap(mut self) -> Self {
+        self.wrap = true;
+        self
+    }
+
+    // display:inline-flex instead of flex
+    pub fn inline(mut self) -> Self {
+        self.inline = true;
+        self
+    }
+
+    pub fn justify(mut self, justify: Justify) -> Self {
+        self.justify = Some(justify);
+        self
+    }
+
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = Some(align);
+        self
+    }
+
+    // centered on both axes
+    pub fn center(self) -> Self {
+        self.justify(Justify::Center).align(Align::Center)
+    }
+
+    // first and last items at the edges, the rest spread between them
+    pub fn between(self) -> Self {
+        self.justify(Justify::SpaceBetween)
+    }
+
+    pub fn gap(mut self, gap: DeclarationValue) -> Self {
+        self.gap = Some(gap);
+        self
+    }
+
+    // the default row direction and nowrap are left out
+    pub fn declarations(&self) -> Vec<Declaration> {
+        let mut keywords = vec![(
+            "display",
+            match self.inline {
+                true => "inline-flex",
+                false => "flex",
+            },
+        )];
+        if self.direction != Direction::Row {
+            keywords.push(("flex-direction", self.direction.as_str()));
+        }
+        if self.wrap {
+            keywords.push(("flex-wrap", "wrap"));
+        }
+        if let Some(justify) = self.justify {
+            keywords.push(("justify-content", justify.as_str()));
+        }
+        if let Some(align) = self.align {
+            keywords.push(("align-items", align.as_str()));
+        }
+        let mut declarations = keywords
+            .into_iter()
+            .map(|(property, keyword)| {
+                Declaration::new(property.to_string(), DeclarationValue::keyword(keyword))
+            })
+            .collect::<Vec<_>>();
+        if let Some(gap) = &self.gap {
+            declarations.push(Declaration::new("gap".to_string(), gap.clone()));
+        }
+        declarations
+    }
+}
+
+impl From<Flex> for Vec<Declaration> {
+    fn from(flex: Flex) -> Self {
+        flex.declarations()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::css::{px, rem, Declaration, Rule, Selector};
+    use crate::flex::{self, Align, Justify};
+
+    fn css(declarations: Vec<Declaration>) -> String {
+        declarations.iter().map(Declaration::to_string).collect()
+    }
+
+    #[test]
+    fn presets() {
+        assert_eq!(
+            css(flex::row().center().gap(px(8)).into()),
+            "display:flex;justify-content:center;align-items:center;gap:8px;"
+        );
+        assert_eq!(
+            css(flex::column().wrap().align(Align::Stretch).declarations()),
+            "display:flex;flex-direction:column;flex-wrap:wrap;align-items:stretch;"
+        );
+        assert_eq!(
+            css(flex::row().reverse().inline().between().declarations()),
+            "display:inline-flex;flex-direction:row-reverse;justify-content:space-between;"
+        );
+    }
+
+    #[test]
+    fn in_rules() {
+        let rule = Rule::new(
+            Selector::Class("toolbar".to_string()),
+            flex::row()
+                .justify(Justify::End)
+                .gap(rem(0.5))
+                .declarations(),
+            vec![],
+        );
+
+        assert_eq!(
+            rule.to_string(),
+            ".toolbar{display:flex;justify-content:flex-end;gap:0.5rem;}"
+        );
+    }
+}
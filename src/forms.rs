@@ -0,0 +1,365 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::builder::Element;
+use crate::html::{Attribute, Node};
+
+// ids default to the field name with anything that is not a letter, digit, - or _ replaced by -
+fn id_for(name: &str) -> String {
+    name.split(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+// what inputs, selects and textareas have in common
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Control {
+    name: String,
+    id: Option<String>,
+    label: Option<String>,
+    attributes: Vec<Attribute>,
+}
+
+impl Control {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            id: None,
+            label: None,
+            attributes: Vec::new(),
+        }
+    }
+
+    fn set(&mut self, attribute: Attribute) {
+        self.attributes
+            .retain(|a| !a.name().eq_ignore_ascii_case(attribute.name()));
+        self.attributes.push(attribute);
+    }
+
+    // a label pointing at the control followed by the control, the id and name come first
+    fn build(self, tag: &str, first: Vec<Attribute>, children: Vec<Node>) -> Node {
+        let id = self.id.unwrap_or_else(|| id_for(&self.name));
+        let mut attributes = first;
+        attributes.push(Attribute::new("id".to_string(), id.clone()));
+        attributes.push(Attribute::new("name".to_string(), self.name));
+        attributes.extend(self.attributes);
+        let control = Node::element(tag.to_string(), attributes, children);
+
+        match self.label {
+            Some(label) => Node::fragment(vec![
+                Element::new("label").attr("for", id).text(label).build(),
+                control,
+            ]),
+            None => control,
+        }
+    }
+}
+
+// builder methods shared by all controls
+macro_rules! control_methods {
+    () => {
+        pub fn id(mut self, id: impl Into<String>) -> Self {
+            self.control.id = Some(id.into());
+            self
+        }
+
+        pub fn label(mut self, label: impl Into<String>) -> Self {
+            self.control.label = Some(label.into());
+            self
+        }
+
+        pub fn required(mut self) -> Self {
+            self.control.set(Attribute::toggle("required".to_string()));
+            self
+        }
+
+        pub fn attr(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+            self.control.set(Attribute::new(name.into(), value.into()));
+            self
+        }
+    };
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Input {
+    control: Control,
+    input_type: String,
+}
+
+impl Input {
+    pub fn new(name: impl Into<String>, input_type: impl Into<String>) -> Self {
+        Self {
+            control: Control::new(name.into()),
+            input_type: input_type.into(),
+        }
+    }
+
+    control_methods!();
+
+    pub fn value(self, value: impl Into<String>) -> Self {
+        self.attr("value", value)
+    }
+
+    pub fn placeholder(self, placeholder: impl Into<String>) -> Self {
+        self.attr("placeholder", placeholder)
+    }
+
+    pub fn build(self) -> Node {
+        let input_type = Attribute::new("type".to_string(), self.input_type);
+        self.control.build("input", vec![input_type], Vec::new())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Select {
+    control: Control,
+    options: Vec<(String, String)>, // (value, text)
+    selected: Option<String>,
+}
+
+impl Select {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            control: Control::new(name.into()),
+            options: Vec::new(),
+            selected: None,
+        }
+    }
+
+    control_methods!();
+
+    pub fn option(mut self, value: impl Into<String>, text: impl Into<String>) -> Self {
+        self.options.push((value.into(), text.into()));
+        self
+    }
+
+    pub fn options<V: Into<String>, T: Into<String>>(
+        mut self,
+        options: impl IntoIterator<Item = (V, T)>,
+    ) -> Self {
+        self.options
+            .extend(options.into_iter().map(|(v, t)| (v.into(), t.into())));
+        self
+    }
+
+    // the option with this value is marked selected
+    pub fn selected(mut self, value: impl Into<String>) -> Self {
+        self.selected = Some(value.into());
+        self
+    }
+
+    pub fn build(self) -> Node {
+        let selected = self.selected;
+        let options = self
+            .options
+            .into_iter()
+            .map(|(value, text)| {
+                let option = match selected.as_deref() == Some(value.as_str()) {
+                    true => Element::new("option")
+                        .attr("value", value)
+                        .toggle("selected"),
+                    false => Element::new("option").attr("value", value),
+                };
+                option.text(text).build()
+            })
+            .collect();
+        self.control.build("select", Vec::new(), options)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TextArea {
+    control: Control,
+    value: String,
+}
+
+impl TextArea {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            control: Control::new(name.into()),
+            value: String::new(),
+        }
+    }
+
+    control_methods!();
+
+    // written as the content of the textarea, escaped like any other text
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = value.into();
+        self
+    }
+
+    pub fn build(self) -> Node {
+        let children = match self.value.is_empty() {
+            true => Vec::new(),
+            false => vec![Node::text(self.value)],
+        };
+        self.control.build("textarea", Vec::new(), children)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Fieldset {
+    legend: Option<String>,
+    fields: Vec<Node>,
+}
+
+impl Fieldset {
+    pub fn new() -> Self {
+        Self {
+            legend: None,
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn legend(mut self, legend: impl Into<String>) -> Self {
+        self.legend = Some(legend.into());
+        self
+    }
+
+    pub fn field(mut self, field: impl Into<Node>) -> Self {
+        self.fields.push(field.into());
+        self
+    }
+
+    pub fn build(self) -> Node {
+        let legend = self.legend.map(|l| Element::new("legend").text(l).build());
+        Element::new("fieldset")
+            .children(legend)
+            .children(self.fields)
+            .build()
+    }
+}
+
+impl Default for Fieldset {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Form {
+    element: Element,
+}
+
+impl Form {
+    pub fn new(action: impl Into<String>) -> Self {
+        Self {
+            element: Element::new("form").attr("action", action),
+        }
+    }
+
+    pub fn method(mut self, method: impl Into<String>) -> Self {
+        self.element = self.element.attr("method", method);
+        self
+    }
+
+    pub fn attr(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.element = self.element.attr(name, value);
+        self
+    }
+
+    pub fn field(mut self, field: impl Into<Node>) -> Self {
+        self.element = self.element.child(field);
+        self
+    }
+
+    pub fn submit(mut self, text: impl Into<String>) -> Self {
+        self.element = self
+            .element
+            .child(Element::new("button").attr("type", "submit").text(text));
+        self
+    }
+
+    pub fn build(self) -> Node {
+        self.element.build()
+    }
+}
+
+impl From<Input> for Node {
+    fn from(input: Input) -> Self {
+        input.build()
+    }
+}
+
+impl From<Select> for Node {
+    fn from(select: Select) -> Self {
+        select.build()
+    }
+}
+
+impl From<TextArea> for Node {
+    fn from(text_area: TextArea) -> Self {
+        text_area.build()
+    }
+}
+
+impl From<Fieldset> for Node {
+    fn from(fieldset: Fieldset) -> Self {
+        fieldset.build()
+    }
+}
+
+impl From<Form> for Node {
+    fn from(form: Form) -> Self {
+        form.build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::forms::{Fieldset, Form, Input, Select, TextArea};
+
+    #[test]
+    fn input_with_label() {
+        let input = Input::new("user[email]", "email")
+            .label("Email")
+            .value("a\"b@x.test")
+            .required()
+            .build();
+
+        assert_eq!(
+            input.to_string(),
+            "<label for=\"user-email\">Email</label><input type=\"email\" id=\"user-email\" name=\"user[email]\" value=\"a&quot;b@x.test\" required>"
+        );
+    }
+
+    #[test]
+    fn select_and_textarea() {
+        let select = Select::new("size")
+            .id("shirt-size")
+            .options([("s", "Small"), ("m", "Medium")])
+            .selected("m")
+            .build();
+        let text_area = TextArea::new("notes").value("<b>bold</b>").build();
+
+        assert_eq!(
+            select.to_string(),
+            "<select id=\"shirt-size\" name=\"size\"><option value=\"s\">Small</option><option value=\"m\" selected>Medium</option></select>"
+        );
+        assert_eq!(
+            text_area.to_string(),
+            "<textarea id=\"notes\" name=\"notes\">&lt;b&gt;bold&lt;/b&gt;</textarea>"
+        );
+    }
+
+    #[test]
+    fn form_with_fieldset() {
+        let form = Form::new("/signup")
+            .method("post")
+            .field(
+                Fieldset::new()
+                    .legend("Account")
+                    .field(Input::new("name", "text").label("Name")),
+            )
+            .submit("Sign up")
+            .build();
+
+        assert_eq!(
+            form.to_string(),
+            "<form action=\"/signup\" method=\"post\"><fieldset><legend>Account</legend><label for=\"name\">Name</label><input type=\"text\" id=\"name\" name=\"name\"></fieldset><button type=\"submit\">Sign up</button></form>"
+        );
+    }
+}
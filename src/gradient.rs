@@ -0,0 +1,281 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+use crate::color::Color;
+use crate::css::DeclarationValue;
+use crate::values::Float;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum Side {
+    Top,
+    Right,
+    Bottom,
+    Left,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Side {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Side::Top => "top",
+            Side::Right => "right",
+            Side::Bottom => "bottom",
+            Side::Left => "left",
+            Side::TopLeft => "top left",
+            Side::TopRight => "top right",
+            Side::BottomLeft => "bottom left",
+            Side::BottomRight => "bottom right",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum LinearDirection {
+    Angle(Float), // degrees
+    To(Side),
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum RadialShape {
+    Circle,
+    Ellipse,
+}
+
+impl RadialShape {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RadialShape::Circle => "circle",
+            RadialShape::Ellipse => "ellipse",
+        }
+    }
+}
+
+// the center of a radial or conic gradient, e.g. (50%, 0)
+pub type Position = (DeclarationValue, DeclarationValue);
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum GradientKind {
+    Linear(Option<LinearDirection>),
+    Radial(Option<RadialShape>, Option<Position>),
+    Conic(Option<Float>, Option<Position>), // (starting angle in degrees, center)
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct ColorStop {
+    color: Color,
+    position: Option<DeclarationValue>,
+}
+
+impl ColorStop {
+    pub fn new(color: Color, position: Option<DeclarationValue>) -> Self {
+        Self { color, position }
+    }
+
+    pub fn color(&self) -> &Color {
+        &self.color
+    }
+
+    pub fn position(&self) -> Option<&DeclarationValue> {
+        self.position.as_ref()
+    }
+}
+
+impl fmt::Display for ColorStop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.position {
+            Some(position) => write!(f, "{} {}", self.color, position),
+            None => write!(f, "{}", self.color),
+        }
+    }
+}
+
+// linear-gradient(), radial-gradient() or conic-gradient() and their repeating forms,
+// e.g. Gradient::linear(LinearDirection::To(Side::Right)).stop(red).stop_at(blue, percentage(80))
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct Gradient {
+    kind: GradientKind,
+    repeating: bool,
+    stops: Vec<ColorStop>,
+}
+
+impl Gradient {
+    pub fn new(kind: GradientKind) -> Self {
+        Self {
+            kind,
+            repeating: false,
+            stops: Vec::new(),
+        }
+    }
+
+    pub fn linear(direction: LinearDirection) -> Self {
+        Self::new(GradientKind::Linear(Some(direction)))
+    }
+
+    pub fn radial(shape: RadialShape) -> Self {
+        Self::new(GradientKind::Radial(Some(shape), None))
+    }
+
+    pub fn conic(from: impl Into<Float>) -> Self {
+        Self::new(GradientKind::Conic(Some(from.into()), None))
+    }
+
+    pub fn repeating(mut self) -> Self {
+        self.repeating = true;
+        self
+    }
+
+    pub fn stop(mut self, color: Color) -> Self {
+        self.stops.push(ColorStop::new(color, None));
+        self
+    }
+
+    // a percentage or a length, an angle for conic gradients
+    pub fn stop_at(mut self, color: Color, position: DeclarationValue) -> Self {
+        self.stops.push(ColorStop::new(color, Some(position)));
+        self
+    }
+
+    pub fn kind(&self) -> &GradientKind {
+        &self.kind
+    }
+
+    pub fn stops(&self) -> &[ColorStop] {
+        &self.stops
+    }
+
+    fn name(&self) -> String {
+        let name = match self.kind {
+            GradientKind::Linear(_) => "linear-gradient",
+            GradientKind::Radial(..) => "radial-gradient",
+            GradientKind::Conic(..) => "conic-gradient",
+        };
+        match self.repeating {
+            true => format!("repeating-{}", name),
+            false => name.to_string(),
+        }
+    }
+
+    // the argument before the color stops, empty when everything is left at its default
+    fn prelude(&self) -> String {
+        let mut parts = Vec::new();
+        match &self.kind {
+            GradientKind::Linear(Some(LinearDirection::Angle(angle))) => {
+                parts.push(format!("{}deg", angle))
+            }
+            GradientKind::Linear(Some(LinearDirection::To(side))) => {
+                parts.push(format!("to {}", side.as_str()))
+            }
+            GradientKind::Linear(None) => (),
+            GradientKind::Radial(shape, _) => {
+                parts.extend(shape.map(|shape| shape.as_str().to_string()))
+            }
+            GradientKind::Conic(from, _) => {
+                parts.extend(from.map(|from| format!("from {}deg", from)))
+            }
+        }
+        if let GradientKind::Radial(_, Some((x, y))) | GradientKind::Conic(_, Some((x, y))) =
+            &self.kind
+        {
+            parts.push(format!("at {} {}", x, y));
+        }
+        parts.join(" ")
+    }
+
+    pub fn to_value(&self) -> DeclarationValue {
+        let prelude = self.prelude();
+        let mut arguments = Vec::with_capacity(self.stops.len() + 1);
+        if !prelude.is_empty() {
+            arguments.push(prelude);
+        }
+        arguments.extend(self.stops.iter().map(ColorStop::to_string));
+        DeclarationValue::Function(self.name(), arguments)
+    }
+}
+
+impl From<Gradient> for DeclarationValue {
+    fn from(gradient: Gradient) -> Self {
+        gradient.to_value()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use crate::color::Color;
+    use crate::css::{px, Declaration, DeclarationValue};
+    use crate::gradient::{Gradient, GradientKind, LinearDirection, RadialShape, Side};
+    use crate::values::Unit;
+
+    #[test]
+    fn linear() {
+        let gradient = Gradient::linear(LinearDirection::To(Side::BottomRight))
+            .stop(Color::rgb(255, 0, 0))
+            .stop_at(
+                Color::rgba(0, 0, 255, 0.5),
+                DeclarationValue::percentage(80),
+            );
+        let declaration = Declaration::new("background".to_string(), gradient.into());
+
+        assert_eq!(
+            declaration.to_string(),
+            "background:linear-gradient(to bottom right,red,#0000ff80 80%);"
+        );
+        assert_eq!(declaration.estimated_len(), declaration.to_string().len());
+        assert_eq!(
+            Gradient::linear(LinearDirection::Angle(45.into()))
+                .repeating()
+                .stop_at(Color::rgb(0, 0, 0), px(0))
+                .stop_at(Color::rgb(255, 255, 255), px(10))
+                .to_value()
+                .to_string(),
+            "repeating-linear-gradient(45deg,#000 0px,#fff 10px)"
+        );
+    }
+
+    #[test]
+    fn radial_and_conic() {
+        assert_eq!(
+            Gradient::new(GradientKind::Radial(
+                Some(RadialShape::Circle),
+                Some((
+                    DeclarationValue::percentage(50),
+                    DeclarationValue::number(0)
+                ))
+            ))
+            .stop(Color::rgb(255, 255, 255))
+            .stop(Color::rgb(0, 0, 0))
+            .to_value()
+            .to_string(),
+            "radial-gradient(circle at 50% 0,#fff,#000)"
+        );
+        assert_eq!(
+            Gradient::new(GradientKind::Radial(None, None))
+                .stop(Color::rgb(255, 0, 0))
+                .stop(Color::rgb(0, 0, 255))
+                .to_value()
+                .to_string(),
+            "radial-gradient(red,#00f)"
+        );
+        assert_eq!(
+            Gradient::conic(90)
+                .stop_at(
+                    Color::rgb(255, 0, 0),
+                    DeclarationValue::length(0, Unit::Deg)
+                )
+                .stop_at(
+                    Color::rgb(0, 0, 255),
+                    DeclarationValue::length(180, Unit::Deg)
+                )
+                .to_value()
+                .to_string(),
+            "conic-gradient(from 90deg,red 0deg,#00f 180deg)"
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::css::{Declaration, DeclarationValue};
+use crate::values::{Float, Unit};
+
+// a share of the free space in a grid, e.g. 1fr
+pub fn fr(value: impl Into<Float>) -> DeclarationValue {
+    DeclarationValue::length(value, Unit::Fr)
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum RepeatCount {
+    Times(u32),
+    AutoFill,
+    AutoFit,
+}
+
+impl RepeatCount {
+    fn to_argument(self) -> String {
+        match self {
+            RepeatCount::Times(count) => count.to_string(),
+            RepeatCount::AutoFill => "auto-fill".to_string(),
+            RepeatCount::AutoFit => "auto-fit".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum Track {
+    Size(DeclarationValue), // e.g. 200px, 1fr or auto
+    MinMax(DeclarationValue, DeclarationValue),
+    Repeat(RepeatCount, Vec<Track>),
+}
+
+impl Track {
+    fn to_value(&self) -> DeclarationValue {
+        match self {
+            Track::Size(size) => size.clone(),
+            Track::MinMax(min, max) => DeclarationValue::Function(
+                "minmax".to_string(),
+                vec![min.to_string(), max.to_string()],
+            ),
+            Track::Repeat(count, tracks) => DeclarationValue::Function(
+                "repeat".to_string(),
+                vec![count.to_argument(), tracks_value(tracks).to_string()],
+            ),
+        }
+    }
+}
+
+fn tracks_value(tracks: &[Track]) -> DeclarationValue {
+    match tracks {
+        [track] => track.to_value(),
+        tracks => DeclarationValue::space_list(tracks.iter().map(Track::to_value).collect()),
+    }
+}
+
+// track list of grid-template-columns or grid-template-rows,
+// e.g. 200px repeat(auto-fill,minmax(8rem,1fr))
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct GridTemplate {
+    tracks: Vec<Track>,
+}
+
+impl GridTemplate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn track(mut self, size: DeclarationValue) -> Self {
+        self.tracks.push(Track::Size(size));
+        self
+    }
+
+    pub fn minmax(mut self, min: DeclarationValue, max: DeclarationValue) -> Self {
+        self.tracks.push(Track::MinMax(min, max));
+        self
+    }
+
+    pub fn repeat(mut self, count: RepeatCount, template: GridTemplate) -> Self {
+        self.tracks.push(Track::Repeat(count, template.tracks));
+        self
+    }
+
+    pub fn tracks(&self) -> &[Track] {
+        &self.tracks
+    }
+
+    // none when there are no tracks
+    pub fn to_value(&self) -> DeclarationValue {
+        match self.tracks.is_empty() {
+            true => DeclarationValue::keyword("none"),
+            false => tracks_value(&self.tracks),
+        }
+    }
+
+    pub fn columns(&self) -> Declaration {
+        Declaration::new("grid-template-columns".to_string(), self.to_value())
+    }
+
+    pub fn rows(&self) -> Declaration {
+        Declaration::new("grid-template-rows".to_string(), self.to_value())
+    }
+}
+
+// named areas of grid-template-areas, one quoted string per row with . for unnamed cells
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct GridAreas {
+    rows: Vec<Vec<String>>,
+}
+
+impl GridAreas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn row<S: Into<String>>(mut self, cells: impl IntoIterator<Item = S>) -> Self {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    pub fn to_value(&self) -> DeclarationValue {
+        let mut rows = self
+            .rows
+            .iter()
+            .map(|cells| DeclarationValue::Basic(format!("\"{}\"", cells.join(" "))))
+            .collect::<Vec<_>>();
+        match rows.len() {
+            0 => DeclarationValue::keyword("none"),
+            1 => rows.remove(0),
+            _ => DeclarationValue::space_list(rows),
+        }
+    }
+
+    pub fn declaration(&self) -> Declaration {
+        Declaration::new("grid-template-areas".to_string(), self.to_value())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use crate::css::{DeclarationValue, Rule, Selector};
+    use crate::grid::{fr, GridAreas, GridTemplate, RepeatCount};
+    use crate::values::Unit;
+
+    #[test]
+    fn templates() {
+        let columns = GridTemplate::new()
+            .track(DeclarationValue::length(200, Unit::Px))
+            .repeat(
+                RepeatCount::AutoFill,
+                GridTemplate::new().minmax(DeclarationValue::length(8, Unit::Rem), fr(1)),
+            )
+            .track(DeclarationValue::keyword("auto"));
+        let rows = GridTemplate::new().repeat(
+            RepeatCount::Times(3),
+            GridTemplate::new().track(fr(1)).track(fr(2)),
+        );
+
+        assert_eq!(
+            columns.columns().to_string(),
+            "grid-template-columns:200px repeat(auto-fill,minmax(8rem,1fr)) auto;"
+        );
+        assert_eq!(
+            rows.rows().to_string(),
+            "grid-template-rows:repeat(3,1fr 2fr);"
+        );
+        assert_eq!(
+            GridTemplate::new().rows().to_string(),
+            "grid-template-rows:none;"
+        );
+    }
+
+    #[test]
+    fn areas() {
+        let areas = GridAreas::new()
+            .row(["header", "header"])
+            .row(["nav", "main"])
+            .row([".", "footer"]);
+        let rule = Rule::new(
+            Selector::Class("page".to_string()),
+            vec![areas.declaration()],
+            vec![],
+        );
+
+        assert_eq!(
+            rule.to_string(),
+            ".page{grid-template-areas:\"header header\" \"nav main\" \". footer\";}"
+        );
+        assert_eq!(rule.estimated_len(), rule.to_string().len());
+        assert_eq!(
+            GridAreas::new().row(["main"]).declaration().to_string(),
+            "grid-template-areas:\"main\";"
+        );
+        // written by hand the rows are kept as they are
+        assert_eq!(
+            DeclarationValue::Basic("\"a b\" \"c d\"".to_string()).to_string(),
+            "\"a b\" \"c d\""
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use alloc::format;
+use alloc::string::String;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use core::fmt;
+use core::fmt::Write;
+use sha2::{Digest, Sha256};
+
+pub fn sha256_base64(content: &[u8]) -> String {
+    STANDARD.encode(Sha256::digest(content))
+}
+
+// source expression allowing inline content in a content security policy
+pub(crate) fn csp_hash(content: &str) -> String {
+    format!("'sha256-{}'", sha256_base64(content.as_bytes()))
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct ContentHash([u8; 32]);
+
+impl ContentHash {
+    pub fn of(content: &[u8]) -> Self {
+        Self(Sha256::digest(content).into())
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    pub fn hex(&self) -> String {
+        let mut output = String::with_capacity(64);
+        for byte in self.0 {
+            // writing to a String can not fail
+            let _ = write!(output, "{:02x}", byte);
+        }
+        output
+    }
+
+    // first 8 hex characters, enough to cache-bust file names (e.g. site.1a2b3c4d.css)
+    pub fn short(&self) -> String {
+        let mut hex = self.hex();
+        hex.truncate(8);
+        hex
+    }
+
+    // strong ETag header value, quotes included
+    pub fn etag(&self) -> String {
+        format!("\"{}\"", self.hex())
+    }
+}
+
+// hashes everything written while passing it on, so output is only walked once
+pub(crate) struct HashingWriter<'a, W: Write> {
+    output: &'a mut W,
+    hasher: Sha256,
+}
+
+impl<'a, W: Write> HashingWriter<'a, W> {
+    pub(crate) fn new(output: &'a mut W) -> Self {
+        Self {
+            output,
+            hasher: Sha256::new(),
+        }
+    }
+
+    pub(crate) fn finish(self) -> ContentHash {
+        ContentHash(self.hasher.finalize().into())
+    }
+}
+
+impl<W: Write> Write for HashingWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.hasher.update(s.as_bytes());
+        self.output.write_str(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::hash::ContentHash;
+
+    #[test]
+    fn formats() {
+        let hash = ContentHash::of(b"init();");
+
+        assert_eq!(
+            hash.hex(),
+            "1849cce6ad67618fe20029f2313768bfeb4da7d3857019e70e03575176953577"
+        );
+        assert_eq!(hash.short(), "1849cce6");
+        assert_eq!(hash.etag(), format!("\"{}\"", hash.hex()));
+    }
+}
@@ -0,0 +1,254 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::html::{Attribute, Document, Node};
+
+fn attribute_value<'a>(node: &'a Node, name: &str) -> Option<&'a str> {
+    node.attributes()
+        .iter()
+        .find(|a| a.name().eq_ignore_ascii_case(name))
+        .and_then(Attribute::value)
+}
+
+fn text(node: &Node) -> String {
+    let mut output = String::new();
+    for text in node.iter().filter_map(|n| match n {
+        Node::Text(text) => Some(text),
+        _ => None,
+    }) {
+        output.push_str(text);
+    }
+    output
+}
+
+// entries with the same key replace each other, entries without one are always kept
+fn key(node: &Node) -> Option<String> {
+    let tag = node.tag()?.to_ascii_lowercase();
+    let value = |name| attribute_value(node, name).map(str::to_ascii_lowercase);
+    match tag.as_str() {
+        "title" | "base" => Some(tag),
+        "meta" if attribute_value(node, "charset").is_some() => Some("meta charset".to_string()),
+        "meta" => ["name", "property", "http-equiv", "itemprop"]
+            .iter()
+            .find_map(|name| value(name).map(|v| format!("meta {}={}", name, v))),
+        "link" if value("rel").as_deref() == Some("canonical") => {
+            Some("link canonical".to_string())
+        }
+        "link" => Some(format!(
+            "link {} {} {}",
+            value("rel").unwrap_or_default(),
+            attribute_value(node, "href").unwrap_or(""),
+            value("hreflang").unwrap_or_default()
+        )),
+        "script" => match attribute_value(node, "src") {
+            Some(src) => Some(format!("script {}", src)),
+            None => Some(format!("script {}", text(node))),
+        },
+        "style" => Some(format!("style {}", text(node))),
+        _ => None,
+    }
+}
+
+// charset has to come first, then what affects how the rest is read, then metadata, styles and scripts
+fn rank(node: &Node) -> u8 {
+    let tag = node.tag().unwrap_or("").to_ascii_lowercase();
+    let has = |name| attribute_value(node, name).is_some();
+    let rel = attribute_value(node, "rel")
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    let viewport =
+        attribute_value(node, "name").is_some_and(|n| n.eq_ignore_ascii_case("viewport"));
+    let hint = rel.split_whitespace().any(|r| {
+        matches!(
+            r,
+            "preconnect" | "dns-prefetch" | "preload" | "modulepreload"
+        )
+    });
+    let stylesheet = rel.split_whitespace().any(|r| r == "stylesheet");
+
+    match tag.as_str() {
+        "meta" if has("charset") => 0,
+        "meta" if has("http-equiv") || viewport => 1,
+        "base" => 2,
+        "title" => 3,
+        "meta" => 4,
+        "link" if hint => 5,
+        "link" if stylesheet => 6,
+        "style" => 6,
+        "link" => 7,
+        "script" => 8,
+        _ => 9,
+    }
+}
+
+// head entries contributed by several components, rendered once each in a canonical order
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct Head {
+    entries: Vec<Node>,
+}
+
+impl Head {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // replaces an earlier entry with the same key (one title, one meta[name=description], ...)
+    pub fn add(&mut self, node: Node) {
+        if let Node::Fragment(children) = node {
+            children.into_iter().for_each(|c| self.add(c));
+            return;
+        }
+
+        match key(&node) {
+            Some(node_key) => match self
+                .entries
+                .iter_mut()
+                .find(|e| key(e).as_ref() == Some(&node_key))
+            {
+                Some(existing) => *existing = node,
+                None => self.entries.push(node),
+            },
+            None => self.entries.push(node),
+        }
+    }
+
+    // entries of the other head win
+    pub fn merge(&mut self, other: Head) {
+        other.entries.into_iter().for_each(|e| self.add(e));
+    }
+
+    pub fn set_title(&mut self, title: String) {
+        self.add(Node::element(
+            "title".to_string(),
+            vec![],
+            vec![Node::text(title)],
+        ));
+    }
+
+    pub fn add_meta(&mut self, name: String, content: String) {
+        self.add(Node::element(
+            "meta".to_string(),
+            vec![
+                Attribute::new("name".to_string(), name),
+                Attribute::new("content".to_string(), content),
+            ],
+            vec![],
+        ));
+    }
+
+    pub fn add_link(&mut self, rel: String, href: String) {
+        self.add(Node::element(
+            "link".to_string(),
+            vec![
+                Attribute::new("rel".to_string(), rel),
+                Attribute::new("href".to_string(), href),
+            ],
+            vec![],
+        ));
+    }
+
+    pub fn add_script(&mut self, src: String) {
+        self.add(Node::element(
+            "script".to_string(),
+            vec![Attribute::new("src".to_string(), src)],
+            vec![],
+        ));
+    }
+
+    pub fn into_nodes(self) -> Vec<Node> {
+        let mut entries = self.entries;
+        // stable, entries of the same rank keep the order they were added in
+        entries.sort_by_key(rank);
+        entries
+    }
+
+    pub fn into_node(self) -> Node {
+        Node::element("head".to_string(), vec![], self.into_nodes())
+    }
+
+    // combined with what the document head already has, entries of this head win
+    pub fn apply(self, document: &mut Document) {
+        let head = document.head_mut();
+        let mut combined = Head::new();
+        head.drain(..).for_each(|n| combined.add(n));
+        combined.merge(self);
+        *head = combined.into_nodes();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::head::Head;
+    use crate::html::{Attribute, Document, Node};
+
+    fn meta(attributes: Vec<(&str, &str)>) -> Node {
+        Node::element(
+            "meta".to_string(),
+            attributes
+                .into_iter()
+                .map(|(n, v)| Attribute::new(n.to_string(), v.to_string()))
+                .collect(),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn deduplicated_in_canonical_order() {
+        let mut layout = Head::new();
+        layout.add_script("/app.js".to_string());
+        layout.add_link("stylesheet".to_string(), "/site.css".to_string());
+        layout.set_title("Site".to_string());
+        layout.add_meta("description".to_string(), "A site".to_string());
+        layout.add(meta(vec![("charset", "utf-8")]));
+
+        let mut page = Head::new();
+        page.set_title("Page".to_string());
+        page.add_meta("description".to_string(), "A page".to_string());
+        page.add_script("/app.js".to_string());
+        page.add_link("stylesheet".to_string(), "/page.css".to_string());
+
+        layout.merge(page);
+
+        assert_eq!(
+            layout.into_node().to_string(),
+            "<head><meta charset=\"utf-8\"><title>Page</title><meta name=\"description\" content=\"A page\"><link rel=\"stylesheet\" href=\"/site.css\"><link rel=\"stylesheet\" href=\"/page.css\"><script src=\"/app.js\"></script></head>"
+        );
+    }
+
+    #[test]
+    fn open_graph_and_fragments() {
+        let mut head = Head::new();
+        head.add(Node::fragment(vec![
+            meta(vec![("property", "og:title"), ("content", "One")]),
+            meta(vec![("property", "og:image"), ("content", "/a.png")]),
+        ]));
+        head.add(meta(vec![("property", "OG:TITLE"), ("content", "Two")]));
+
+        assert_eq!(
+            head.into_nodes()
+                .iter()
+                .filter_map(|n| n.attributes()[1].value())
+                .collect::<Vec<&str>>(),
+            vec!["Two", "/a.png"]
+        );
+    }
+
+    #[test]
+    fn apply_to_document() {
+        let mut document = Document::default();
+        document.set_title("Old".to_string());
+        document.add_script("/app.js".to_string());
+
+        let mut head = Head::new();
+        head.set_title("New".to_string());
+        head.add_script("/app.js".to_string());
+        head.apply(&mut document);
+
+        assert_eq!(
+            document.to_string(),
+            "<!DOCTYPE html><html><head><title>New</title><script src=\"/app.js\"></script></head><body></body></html>"
+        );
+    }
+}
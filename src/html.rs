@@ -1,51 +1,586 @@
-use serde::Deserialize;
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write;
+use serde::{Deserialize, Deserializer, Serialize};
 
-#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+use crate::class::ClassList;
+use crate::css::{Declaration, RuleSet};
+use crate::i18n::MessageArgument;
+use crate::name::Name;
+
+// entity replacing a character that would otherwise be read as markup
+fn entity(c: char, in_attribute: bool) -> Option<&'static str> {
+    match c {
+        '&' => Some("&amp;"),
+        '<' if !in_attribute => Some("&lt;"),
+        '>' if !in_attribute => Some("&gt;"),
+        '"' if in_attribute => Some("&quot;"),
+        _ => None,
+    }
+}
+
+pub(crate) fn escaped_len(text: &str, in_attribute: bool) -> usize {
+    text.chars()
+        .map(|c| entity(c, in_attribute).map_or(c.len_utf8(), str::len))
+        .sum()
+}
+
+pub(crate) fn write_escaped<W: Write>(
+    output: &mut W,
+    text: &str,
+    in_attribute: bool,
+) -> fmt::Result {
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        if let Some(entity) = entity(c, in_attribute) {
+            output.write_str(&text[start..i])?;
+            output.write_str(entity)?;
+            start = i + c.len_utf8();
+        }
+    }
+    output.write_str(&text[start..])
+}
+
+// script and style contents are raw text, entities would be taken literally
+fn is_raw_text_element(tag: &str) -> bool {
+    tag.eq_ignore_ascii_case("script") || tag.eq_ignore_ascii_case("style")
+}
+
+// a closing tag in script or style content would end the element early, </script is written as
+// <\/script which means the same inside javascript strings, regexes and css strings
+fn raw_text_breaks(text: &str) -> impl Iterator<Item = usize> + '_ {
+    text.match_indices("</").map(|(i, _)| i).filter(move |i| {
+        let rest = &text.as_bytes()[i + 2..];
+        ["script", "style"].iter().any(|tag| {
+            rest.get(..tag.len())
+                .is_some_and(|r| r.eq_ignore_ascii_case(tag.as_bytes()))
+        })
+    })
+}
+
+fn raw_text_len(text: &str) -> usize {
+    text.len() + raw_text_breaks(text).count()
+}
+
+fn write_raw_text<W: Write>(output: &mut W, text: &str) -> fmt::Result {
+    let mut start = 0;
+    for i in raw_text_breaks(text) {
+        output.write_str(&text[start..i + 1])?;
+        output.write_char('\\')?;
+        start = i + 1;
+    }
+    output.write_str(&text[start..])
+}
+
+// a comment ends at the first -- followed by > or !>, so dashes in a row are kept apart by a space
+fn comment_breaks(text: &str) -> impl Iterator<Item = usize> + '_ {
+    text.match_indices('-')
+        .map(|(i, _)| i)
+        .filter(move |i| text[..*i].ends_with('-'))
+}
+
+fn comment_len(text: &str) -> usize {
+    text.len() + comment_breaks(text).count() + 9
+}
+
+fn write_comment<W: Write>(output: &mut W, text: &str) -> fmt::Result {
+    output.write_str("<!-- ")?;
+    let mut start = 0;
+    for i in comment_breaks(text) {
+        output.write_str(&text[start..i])?;
+        output.write_char(' ')?;
+        start = i;
+    }
+    output.write_str(&text[start..])?;
+    output.write_str(" -->")
+}
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+pub fn is_void_element(tag: &str) -> bool {
+    VOID_ELEMENTS.iter().any(|v| v.eq_ignore_ascii_case(tag))
+}
+
+#[rustfmt::skip]
+const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "allowfullscreen", "async", "autofocus", "autoplay", "checked", "controls", "default", "defer",
+    "disabled", "formnovalidate", "hidden", "inert", "ismap", "itemscope", "loop", "multiple",
+    "muted", "nomodule", "novalidate", "open", "playsinline", "readonly", "required", "reversed",
+    "selected",
+];
+
+// present or not, the value of these is never read by the browser (disabled="false" disables)
+pub fn is_boolean_attribute(name: &str) -> bool {
+    BOOLEAN_ATTRIBUTES
+        .iter()
+        .any(|b| b.eq_ignore_ascii_case(name))
+}
+
+// added to svg elements that are not inside another svg element, unless given
+const SVG_XMLNS_ATTRIBUTE: &str = " xmlns=\"http://www.w3.org/2000/svg\"";
+
+// svg names are case sensitive, these are written with their svg casing whatever case they are given in
+const SVG_TAG_NAMES: &[&str] = &[
+    "altGlyph",
+    "altGlyphDef",
+    "altGlyphItem",
+    "animateColor",
+    "animateMotion",
+    "animateTransform",
+    "clipPath",
+    "feBlend",
+    "feColorMatrix",
+    "feComponentTransfer",
+    "feComposite",
+    "feConvolveMatrix",
+    "feDiffuseLighting",
+    "feDisplacementMap",
+    "feDistantLight",
+    "feDropShadow",
+    "feFlood",
+    "feFuncA",
+    "feFuncB",
+    "feFuncG",
+    "feFuncR",
+    "feGaussianBlur",
+    "feImage",
+    "feMerge",
+    "feMergeNode",
+    "feMorphology",
+    "feOffset",
+    "fePointLight",
+    "feSpecularLighting",
+    "feSpotLight",
+    "feTile",
+    "feTurbulence",
+    "foreignObject",
+    "glyphRef",
+    "linearGradient",
+    "radialGradient",
+    "textPath",
+];
+
+const SVG_ATTRIBUTE_NAMES: &[&str] = &[
+    "attributeName",
+    "attributeType",
+    "baseFrequency",
+    "baseProfile",
+    "calcMode",
+    "clipPathUnits",
+    "diffuseConstant",
+    "edgeMode",
+    "filterUnits",
+    "glyphRef",
+    "gradientTransform",
+    "gradientUnits",
+    "kernelMatrix",
+    "kernelUnitLength",
+    "keyPoints",
+    "keySplines",
+    "keyTimes",
+    "lengthAdjust",
+    "limitingConeAngle",
+    "markerHeight",
+    "markerUnits",
+    "markerWidth",
+    "maskContentUnits",
+    "maskUnits",
+    "numOctaves",
+    "pathLength",
+    "patternContentUnits",
+    "patternTransform",
+    "patternUnits",
+    "pointsAtX",
+    "pointsAtY",
+    "pointsAtZ",
+    "preserveAlpha",
+    "preserveAspectRatio",
+    "primitiveUnits",
+    "refX",
+    "refY",
+    "repeatCount",
+    "repeatDur",
+    "requiredExtensions",
+    "requiredFeatures",
+    "specularConstant",
+    "specularExponent",
+    "spreadMethod",
+    "startOffset",
+    "stdDeviation",
+    "stitchTiles",
+    "surfaceScale",
+    "systemLanguage",
+    "tableValues",
+    "targetX",
+    "targetY",
+    "textLength",
+    "viewBox",
+    "viewTarget",
+    "xChannelSelector",
+    "yChannelSelector",
+    "zoomAndPan",
+];
+
+// same length as the given name so estimates do not change
+fn svg_name<'a>(name: &'a str, names: &[&'static str]) -> &'a str {
+    names
+        .iter()
+        .find(|n| n.eq_ignore_ascii_case(name))
+        .copied()
+        .unwrap_or(name)
+}
+
+// how the children of an element are written
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Context {
+    Html,
+    RawText,
+    Svg,
+}
+
+impl Context {
+    fn within(self, tag: &str) -> Self {
+        match self {
+            Context::Svg if tag.eq_ignore_ascii_case("foreignObject") => Context::Html,
+            Context::Svg => Context::Svg,
+            _ if tag.eq_ignore_ascii_case("svg") => Context::Svg,
+            _ if is_raw_text_element(tag) => Context::RawText,
+            _ => Context::Html,
+        }
+    }
+
+    fn needs_xmlns(self, tag: &str, attributes: &[Attribute]) -> bool {
+        self != Context::Svg
+            && tag.eq_ignore_ascii_case("svg")
+            && !attributes
+                .iter()
+                .any(|a| a.name().eq_ignore_ascii_case("xmlns"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Ending {
+    Void,        // <br>, contents can not be represented so children are not written
+    SelfClosing, // <path/>
+    CloseTag,    // <p></p>
+}
+
+impl Ending {
+    // svg elements are always self closing when empty and have no void elements
+    fn of(tag: &str, self_closing: bool, children: &[Node], in_svg: bool) -> Self {
+        match (in_svg, is_void_element(tag)) {
+            (false, true) => Ending::Void,
+            _ if (self_closing || in_svg) && children.is_empty() => Ending::SelfClosing,
+            _ => Ending::CloseTag,
+        }
+    }
+}
+
+// attribute values as they come out of data, written as text once they are part of an attribute
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum AttributeValue {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Bool(bool), // left out when false, boolean attributes are written as just their name when true
+    List(Vec<String>), // token lists like class or rel, joined with spaces
+}
+
+impl fmt::Display for AttributeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttributeValue::String(value) => f.write_str(value),
+            AttributeValue::Integer(value) => write!(f, "{}", value),
+            AttributeValue::Float(value) => write!(f, "{}", value),
+            AttributeValue::Bool(value) => write!(f, "{}", value),
+            AttributeValue::List(values) => f.write_str(&values.join(" ")),
+        }
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        AttributeValue::String(value)
+    }
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        AttributeValue::String(value.to_string())
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> Self {
+        AttributeValue::Integer(value)
+    }
+}
+
+impl From<f64> for AttributeValue {
+    fn from(value: f64) -> Self {
+        AttributeValue::Float(value)
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        AttributeValue::Bool(value)
+    }
+}
+
+impl From<Vec<String>> for AttributeValue {
+    fn from(values: Vec<String>) -> Self {
+        AttributeValue::List(values)
+    }
+}
+
+// values are kept as text apart from booleans, which are written depending on the attribute
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(untagged)]
+enum StoredValue {
+    Bool(bool),
+    Text(String),
+}
+
+impl From<AttributeValue> for StoredValue {
+    fn from(value: AttributeValue) -> Self {
+        match value {
+            AttributeValue::Bool(value) => StoredValue::Bool(value),
+            value => StoredValue::Text(value.to_string()),
+        }
+    }
+}
+
+fn deserialize_attribute_value<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<StoredValue>, D::Error> {
+    Option::<AttributeValue>::deserialize(deserializer).map(|v| v.map(StoredValue::from))
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Attribute {
-    name: String,
-    value: Option<String>,
+    name: Name,
+    #[serde(default, deserialize_with = "deserialize_attribute_value")]
+    value: Option<StoredValue>,
+    #[serde(default)]
+    trusted: bool, // value is written without escaping
 }
 
 impl Attribute {
     pub fn new(name: String, value: String) -> Self {
-        Self { name, value: Some(value) }
+        Self {
+            name: Name::new(name),
+            value: Some(StoredValue::Text(value)),
+            trusted: false,
+        }
     }
 
     pub fn toggle(name: String) -> Self {
-        Self { name, value: None }
+        Self {
+            name: Name::new(name),
+            value: None,
+            trusted: false,
+        }
+    }
+
+    pub fn typed(name: String, value: impl Into<AttributeValue>) -> Self {
+        Self {
+            name: Name::new(name),
+            value: Some(StoredValue::from(value.into())),
+            trusted: false,
+        }
+    }
+
+    // value is already escaped or otherwise known to be safe, it is written as is
+    pub fn trusted(name: String, value: String) -> Self {
+        Self {
+            name: Name::new(name),
+            value: Some(StoredValue::Text(value)),
+            trusted: true,
+        }
+    }
+
+    // declarations written the same way as in a rule, e.g. style="color:red;"
+    pub fn style(declarations: Vec<Declaration>) -> Self {
+        let mut value = String::new();
+        for declaration in &declarations {
+            // writing to a string does not fail
+            let _ = write!(value, "{}", declaration);
+        }
+        Self::new("style".to_string(), value)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> Option<&str> {
+        match self.value.as_ref()? {
+            StoredValue::Bool(true) => Some("true"),
+            StoredValue::Bool(false) => Some("false"),
+            StoredValue::Text(value) => Some(value),
+        }
+    }
+
+    pub fn is_trusted(&self) -> bool {
+        self.trusted
+    }
+
+    // an attribute set to false or a boolean attribute set to "false", nothing is written for it
+    pub(crate) fn is_omitted(&self) -> bool {
+        match &self.value {
+            Some(StoredValue::Bool(value)) => !value,
+            Some(StoredValue::Text(value)) => {
+                is_boolean_attribute(&self.name) && value.eq_ignore_ascii_case("false")
+            }
+            None => false,
+        }
     }
-}
 
-impl ToString for Attribute {
-    fn to_string(&self) -> String {
+    // boolean attributes set to true, to an empty value or to their own name are written as just the name,
+    // other values are kept (hidden="until-found", aria-hidden="true")
+    fn is_valueless(&self) -> bool {
         match &self.value {
-            Some(value) => {
-                format!("{}=\"{}\"", self.name, value)
+            None => true,
+            Some(StoredValue::Bool(_)) => is_boolean_attribute(&self.name),
+            Some(StoredValue::Text(value)) => {
+                is_boolean_attribute(&self.name)
+                    && (value.is_empty()
+                        || value.eq_ignore_ascii_case("true")
+                        || value.eq_ignore_ascii_case(&self.name))
+            }
+        }
+    }
+
+    // duplicate classes are only written once
+    fn written_value(&self) -> Option<Cow<'_, str>> {
+        match self.value() {
+            Some(value)
+                if !self.trusted
+                    && self.name.eq_ignore_ascii_case("class")
+                    && ClassList::has_duplicates(value) =>
+            {
+                Some(Cow::Owned(ClassList::parse(value).to_string()))
+            }
+            value => value.map(Cow::Borrowed),
+        }
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        if self.is_omitted() {
+            return 0;
+        }
+        if self.is_valueless() {
+            return self.name.len();
+        }
+
+        match self.written_value() {
+            Some(value) if self.trusted => self.name.len() + value.len() + 3,
+            Some(value) => self.name.len() + escaped_len(&value, true) + 3,
+            None => self.name.len(),
+        }
+    }
+
+    // values without whitespace, quotes, =, <, > or ` can be written without quotes
+    fn can_unquote(&self) -> bool {
+        self.value().is_some_and(|value| {
+            !value.is_empty()
+                && !value.contains(|c: char| {
+                    c.is_ascii_whitespace() || matches!(c, '"' | '\'' | '=' | '<' | '>' | '`')
+                })
+        })
+    }
+
+    fn render<W: Write>(&self, output: &mut W) -> fmt::Result {
+        self.render_named(&self.name, true, output)
+    }
+
+    fn render_named<W: Write>(&self, name: &str, quoted: bool, output: &mut W) -> fmt::Result {
+        if self.is_omitted() {
+            return Ok(());
+        }
+
+        output.write_str(name)?;
+        if self.is_valueless() {
+            return Ok(());
+        }
+        if let Some(value) = self.written_value() {
+            output.write_char('=')?;
+            if quoted {
+                output.write_char('"')?;
+            }
+            match self.trusted {
+                true => output.write_str(&value)?,
+                false => write_escaped(output, &value, true)?,
+            }
+            if quoted {
+                output.write_char('"')?;
             }
-            None => self.name.to_string()
         }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Node {
     Text(String),
     Comment(String),
+    Raw(String),         // pre-rendered markup written as is
+    Fragment(Vec<Node>), // siblings written without a wrapping element
+    #[cfg(feature = "markdown")]
+    Markdown(String), // commonmark converted to html when written
+    Slot(String), // named placeholder replaced by fill_slots, written as nothing when left empty
     Element {
-        tag: String,
+        tag: Name,
         #[serde(default)]
         attributes: Vec<Attribute>,
         #[serde(default)]
         children: Vec<Node>,
+        #[serde(default)]
+        self_closing: bool, // written as <tag/> when there are no children
+    },
+    Message {
+        key: String,
+        #[serde(default)]
+        arguments: Vec<MessageArgument>,
+        #[serde(default)]
+        count: Option<i64>,
     },
 }
 
 impl Node {
     pub fn element(tag: String, attributes: Vec<Attribute>, children: Vec<Node>) -> Self {
         Self::Element {
-            tag,
+            tag: Name::new(tag),
             attributes,
             children,
+            self_closing: false,
+        }
+    }
+
+    // element written as <tag/>, for custom and foreign elements that are not html void elements
+    pub fn self_closing(tag: String, attributes: Vec<Attribute>) -> Self {
+        Self::Element {
+            tag: Name::new(tag),
+            attributes,
+            children: Vec::new(),
+            self_closing: true,
         }
     }
 
@@ -56,85 +591,512 @@ impl Node {
     pub fn comment(text: String) -> Self {
         Self::Comment(text)
     }
-}
 
-impl ToString for Node {
-    fn to_string(&self) -> String {
+    pub fn fragment(children: Vec<Node>) -> Self {
+        Self::Fragment(children)
+    }
+
+    // markup from other tools, it is not escaped or checked in any way
+    pub fn raw(html: String) -> Self {
+        Self::Raw(html)
+    }
+
+    // inline javascript, written as is apart from closing script tags
+    pub fn script(content: String, attributes: Vec<Attribute>) -> Self {
+        Self::element("script".to_string(), attributes, vec![Self::Text(content)])
+    }
+
+    // a <style> element holding the rules
+    pub fn stylesheet(rule_set: &RuleSet) -> Self {
+        Self::element(
+            "style".to_string(),
+            vec![],
+            vec![Self::Text(rule_set.to_string())],
+        )
+    }
+
+    pub fn message(key: String, arguments: Vec<MessageArgument>, count: Option<i64>) -> Self {
+        Self::Message {
+            key,
+            arguments,
+            count,
+        }
+    }
+
+    // tag name of an element, None for other nodes
+    pub fn tag(&self) -> Option<&str> {
+        match self {
+            Node::Element { tag, .. } => Some(tag),
+            _ => None,
+        }
+    }
+
+    // attributes of an element, empty for other nodes
+    pub fn attributes(&self) -> &[Attribute] {
+        match self {
+            Node::Element { attributes, .. } => attributes,
+            _ => &[],
+        }
+    }
+
+    // children of an element or fragment, empty for other nodes
+    pub fn children(&self) -> &[Node] {
+        match self {
+            Node::Element { children, .. } | Node::Fragment(children) => children,
+            _ => &[],
+        }
+    }
+
+    // children of an element or fragment, None for nodes that can not have any
+    pub fn children_mut(&mut self) -> Option<&mut Vec<Node>> {
+        match self {
+            Node::Element { children, .. } | Node::Fragment(children) => Some(children),
+            _ => None,
+        }
+    }
+
+    // contents of a text or comment node
+    pub fn text_content(&self) -> Option<&str> {
+        match self {
+            Node::Text(text) | Node::Comment(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    // false when the node can not have children
+    pub fn append_child(&mut self, child: Node) -> bool {
+        match self.children_mut() {
+            Some(children) => {
+                children.push(child);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // previous child at the index, None and nothing is replaced when it is out of range
+    pub fn replace_child(&mut self, index: usize, child: Node) -> Option<Node> {
+        self.children_mut()
+            .and_then(|children| children.get_mut(index))
+            .map(|existing| core::mem::replace(existing, child))
+    }
+
+    pub fn retain_children<F: FnMut(&Node) -> bool>(&mut self, keep: F) {
+        if let Some(children) = self.children_mut() {
+            children.retain(keep);
+        }
+    }
+
+    // replaces an attribute with the same name in place, false when the node is not an element
+    pub fn set_attribute(&mut self, attribute: Attribute) -> bool {
+        match self {
+            Node::Element { attributes, .. } => {
+                match attributes
+                    .iter_mut()
+                    .find(|a| a.name().eq_ignore_ascii_case(attribute.name()))
+                {
+                    Some(existing) => *existing = attribute,
+                    None => attributes.push(attribute),
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn remove_attribute(&mut self, name: &str) -> Option<Attribute> {
         match self {
-            Node::Text(s) => s.clone(),
-            Node::Comment(s) => format!("<!-- {} -->", s),
+            Node::Element { attributes, .. } => attributes
+                .iter()
+                .position(|a| a.name().eq_ignore_ascii_case(name))
+                .map(|index| attributes.remove(index)),
+            _ => None,
+        }
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        self.estimated_len_within(Context::Html)
+    }
+
+    fn estimated_len_within(&self, context: Context) -> usize {
+        match self {
+            Node::Text(s) if context == Context::RawText => raw_text_len(s),
+            Node::Text(s) => escaped_len(s, false),
+            Node::Comment(s) => comment_len(s),
+            Node::Raw(s) => s.len(),
+            Node::Slot(_) => 0,
+            #[cfg(feature = "markdown")]
+            Node::Markdown(s) => crate::markdown::estimated_html_len(s),
+            Node::Fragment(children) => children
+                .iter()
+                .map(|c| c.estimated_len_within(context))
+                .sum(),
+            Node::Message { key, .. } => escaped_len(key, false),
             Node::Element {
                 tag,
                 attributes,
                 children,
+                self_closing,
             } => {
-                let child_text = children
+                let mut attributes_len = attributes
                     .iter()
-                    .map(|c| c.to_string())
-                    .collect::<Vec<String>>()
-                    .join("");
-
-                let open_tag = match attributes.is_empty() {
-                    true => format!("<{}>", tag),
-                    false => {
-                        let attribute_text = attributes
+                    .filter(|a| !a.is_omitted())
+                    .map(|a| a.estimated_len() + 1)
+                    .sum::<usize>();
+                if context.needs_xmlns(tag, attributes) {
+                    attributes_len += SVG_XMLNS_ATTRIBUTE.len();
+                }
+
+                let inner = context.within(tag);
+                let in_svg = context == Context::Svg || inner == Context::Svg;
+                match Ending::of(tag, *self_closing, children, in_svg) {
+                    Ending::Void => tag.len() + 2 + attributes_len,
+                    Ending::SelfClosing => tag.len() + 3 + attributes_len,
+                    Ending::CloseTag => {
+                        let children_len = children
                             .iter()
-                            .map(Attribute::to_string)
-                            .collect::<Vec<String>>()
-                            .join(" ");
-                        format!("<{} {}>", tag, attribute_text)
+                            .map(|c| c.estimated_len_within(inner))
+                            .sum::<usize>();
+
+                        tag.len() * 2 + 5 + attributes_len + children_len
                     }
-                };
-                format!("{}{}</{}>", open_tag, child_text, tag)
+                }
             }
         }
     }
-}
 
-#[cfg(test)]
-mod to_string {
-    use crate::html::{Attribute, Node};
+    // streams the output without building it in memory first
+    pub fn write_to<W: Write>(&self, output: &mut W) -> fmt::Result {
+        self.render(output)
+    }
 
-    #[test]
-    fn single_element() {
-        let element = Node::element("body".to_string(), vec![], vec![]);
+    pub(crate) fn render<W: Write>(&self, output: &mut W) -> fmt::Result {
+        self.render_within(output, Context::Html, false)
+    }
 
-        assert_eq!(element.to_string(), "<body></body>");
+    // attribute quotes are left out where the value allows it
+    pub(crate) fn render_minified<W: Write>(&self, output: &mut W) -> fmt::Result {
+        self.render_within(output, Context::Html, true)
     }
 
-    #[test]
-    fn single_element_with_text() {
-        let element = Node::element(
-            "body".to_string(),
-            vec![],
-            vec![Node::text("Some text".to_string())],
-        );
+    fn render_within<W: Write>(
+        &self,
+        output: &mut W,
+        context: Context,
+        minified: bool,
+    ) -> fmt::Result {
+        match self {
+            Node::Text(s) if context == Context::RawText => write_raw_text(output, s),
+            Node::Text(s) => write_escaped(output, s, false),
+            Node::Message { key, .. } => write_escaped(output, key, false),
+            Node::Raw(s) => output.write_str(s),
+            Node::Slot(_) => Ok(()),
+            #[cfg(feature = "markdown")]
+            Node::Markdown(s) => output.write_str(&crate::markdown::markdown_to_html(s)),
+            Node::Fragment(children) => children
+                .iter()
+                .try_for_each(|c| c.render_within(output, context, minified)),
+            Node::Comment(s) => write_comment(output, s),
+            Node::Element {
+                tag,
+                attributes,
+                children,
+                self_closing,
+            } => {
+                let inner = context.within(tag);
+                // foreignObject is an svg element even though its children are html
+                let in_svg = context == Context::Svg || inner == Context::Svg;
+                let tag = match in_svg {
+                    true => svg_name(tag, SVG_TAG_NAMES),
+                    false => tag,
+                };
 
-        assert_eq!(element.to_string(), "<body>Some text</body>");
+                output.write_char('<')?;
+                output.write_str(tag)?;
+                if context.needs_xmlns(tag, attributes) {
+                    output.write_str(SVG_XMLNS_ATTRIBUTE)?;
+                }
+                for attribute in attributes.iter().filter(|a| !a.is_omitted()) {
+                    output.write_char(' ')?;
+                    // svg keeps its quotes, an unquoted value would swallow the / of />
+                    match in_svg {
+                        true => attribute.render_named(
+                            svg_name(attribute.name(), SVG_ATTRIBUTE_NAMES),
+                            true,
+                            output,
+                        )?,
+                        false => attribute.render_named(
+                            attribute.name(),
+                            !(minified && attribute.can_unquote()),
+                            output,
+                        )?,
+                    }
+                }
+
+                match Ending::of(tag, *self_closing, children, in_svg) {
+                    Ending::Void => return output.write_char('>'),
+                    Ending::SelfClosing => return output.write_str("/>"),
+                    Ending::CloseTag => output.write_char('>')?,
+                }
+
+                for child in children {
+                    child.render_within(output, inner, minified)?;
+                }
+
+                output.write_str("</")?;
+                output.write_str(tag)?;
+                output.write_char('>')
+            }
+        }
     }
+}
 
-    #[test]
-    fn attribute() {
-        let attr = Attribute::new("class".to_string(), "my-class".to_string());
-        assert_eq!(attr.to_string(), "class=\"my-class\"")
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
     }
+}
 
-    #[test]
-    fn attribute_no_value() {
-        let attr = Attribute::toggle("class".to_string());
-        assert_eq!(attr.to_string(), "class")
+#[cfg(feature = "std")]
+impl Node {
+    pub fn write_io<W: std::io::Write>(&self, output: &mut W) -> std::io::Result<()> {
+        crate::stream::write_io(output, |writer| self.render(writer))
     }
+}
 
-    #[test]
-    fn single_element_with_attributes() {
-        let element = Node::element(
-            "body".to_string(),
-            vec![
-                Attribute::new("class".to_string(), "my-class".to_string()),
-                Attribute::new("width".to_string(), "100".to_string()),
-            ],
-            vec![],
-        );
+#[cfg(feature = "bytes")]
+impl Node {
+    pub fn to_bytes(&self) -> bytes::Bytes {
+        bytes::Bytes::from(self.to_string())
+    }
+
+    pub fn write_bytes(&self, buffer: &mut bytes::BytesMut) -> bytes::Bytes {
+        buffer.reserve(self.estimated_len());
+        // writing to a BytesMut can not fail
+        let _ = self.render(buffer);
+        buffer.split().freeze()
+    }
+}
+
+#[cfg(feature = "hashes")]
+impl Node {
+    // rendered output along with its hash, computed in the same pass
+    pub fn to_string_with_hash(&self) -> (String, crate::hash::ContentHash) {
+        let mut output = String::with_capacity(self.estimated_len());
+        let mut writer = crate::hash::HashingWriter::new(&mut output);
+        // writing to a String can not fail
+        let _ = self.render(&mut writer);
+        let hash = writer.finish();
+        (output, hash)
+    }
+}
+
+const DOCTYPE: &str = "<!DOCTYPE html>";
+
+fn child_element_mut<'a>(children: &'a mut [Node], tag: &str) -> Option<&'a mut Vec<Node>> {
+    children.iter_mut().find_map(|child| match child {
+        Node::Element {
+            tag: child_tag,
+            children,
+            ..
+        } if child_tag.eq_ignore_ascii_case(tag) => Some(children),
+        _ => None,
+    })
+}
+
+// complete page written with a doctype
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Document {
+    root: Node,
+}
+
+impl Default for Document {
+    fn default() -> Self {
+        Self::new(Node::element("body".to_string(), vec![], vec![]))
+    }
+}
+
+impl Document {
+    // html roots are used as is, a body is given an empty head and anything else is placed in a body
+    pub fn new(root: Node) -> Self {
+        let root = match root.tag() {
+            Some(tag) if tag.eq_ignore_ascii_case("html") => root,
+            Some(tag) if tag.eq_ignore_ascii_case("body") => Node::element(
+                "html".to_string(),
+                vec![],
+                vec![Node::element("head".to_string(), vec![], vec![]), root],
+            ),
+            _ => Node::element(
+                "html".to_string(),
+                vec![],
+                vec![
+                    Node::element("head".to_string(), vec![], vec![]),
+                    Node::element("body".to_string(), vec![], vec![root]),
+                ],
+            ),
+        };
+
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Node {
+        &self.root
+    }
+
+    pub fn into_root(self) -> Node {
+        self.root
+    }
+
+    fn root_children(&mut self) -> &mut Vec<Node> {
+        // deserialized documents can have any root
+        if !self
+            .root
+            .tag()
+            .is_some_and(|t| t.eq_ignore_ascii_case("html"))
+        {
+            let root = core::mem::replace(&mut self.root, Node::text(String::new()));
+            self.root = Self::new(root).root;
+        }
+
+        match &mut self.root {
+            Node::Element { children, .. } => children,
+            _ => unreachable!("document root is not an element"),
+        }
+    }
+
+    // children of the head, added before everything else when the root has none
+    pub fn head_mut(&mut self) -> &mut Vec<Node> {
+        let children = self.root_children();
+        if child_element_mut(children, "head").is_none() {
+            children.insert(0, Node::element("head".to_string(), vec![], vec![]));
+        }
+
+        // just ensured above
+        child_element_mut(self.root_children(), "head").unwrap_or_else(|| unreachable!())
+    }
+
+    // children of the body, added after everything else when the root has none
+    pub fn body_mut(&mut self) -> &mut Vec<Node> {
+        let children = self.root_children();
+        if child_element_mut(children, "body").is_none() {
+            children.push(Node::element("body".to_string(), vec![], vec![]));
+        }
+
+        // just ensured above
+        child_element_mut(self.root_children(), "body").unwrap_or_else(|| unreachable!())
+    }
+
+    // replaces any existing title
+    pub fn set_title(&mut self, title: String) {
+        let head = self.head_mut();
+        head.retain(|n| !n.tag().is_some_and(|t| t.eq_ignore_ascii_case("title")));
+        head.insert(
+            0,
+            Node::element("title".to_string(), vec![], vec![Node::text(title)]),
+        );
+    }
+
+    pub fn add_meta(&mut self, name: String, content: String) {
+        self.head_mut().push(Node::element(
+            "meta".to_string(),
+            vec![
+                Attribute::new("name".to_string(), name),
+                Attribute::new("content".to_string(), content),
+            ],
+            vec![],
+        ));
+    }
+
+    pub fn add_link(&mut self, rel: String, href: String) {
+        self.head_mut().push(Node::element(
+            "link".to_string(),
+            vec![
+                Attribute::new("rel".to_string(), rel),
+                Attribute::new("href".to_string(), href),
+            ],
+            vec![],
+        ));
+    }
+
+    pub fn add_script(&mut self, src: String) {
+        self.head_mut().push(Node::element(
+            "script".to_string(),
+            vec![Attribute::new("src".to_string(), src)],
+            vec![],
+        ));
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        DOCTYPE.len() + self.root.estimated_len()
+    }
+
+    pub fn write_to<W: Write>(&self, output: &mut W) -> fmt::Result {
+        self.render(output)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn write_io<W: std::io::Write>(&self, output: &mut W) -> std::io::Result<()> {
+        crate::stream::write_io(output, |writer| self.render(writer))
+    }
+
+    pub(crate) fn render<W: Write>(&self, output: &mut W) -> fmt::Result {
+        output.write_str(DOCTYPE)?;
+        self.root.render(output)
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
+    }
+}
+
+#[cfg(test)]
+mod to_string {
+    use crate::css::{Declaration, DeclarationValue, Rule, RuleSet, Selector};
+    use crate::html::{Attribute, Node};
+
+    #[test]
+    fn single_element() {
+        let element = Node::element("body".to_string(), vec![], vec![]);
+
+        assert_eq!(element.to_string(), "<body></body>");
+    }
+
+    #[test]
+    fn single_element_with_text() {
+        let element = Node::element(
+            "body".to_string(),
+            vec![],
+            vec![Node::text("Some text".to_string())],
+        );
+
+        assert_eq!(element.to_string(), "<body>Some text</body>");
+    }
+
+    #[test]
+    fn attribute() {
+        let attr = Attribute::new("class".to_string(), "my-class".to_string());
+        assert_eq!(attr.to_string(), "class=\"my-class\"")
+    }
+
+    #[test]
+    fn attribute_no_value() {
+        let attr = Attribute::toggle("class".to_string());
+        assert_eq!(attr.to_string(), "class")
+    }
+
+    #[test]
+    fn single_element_with_attributes() {
+        let element = Node::element(
+            "body".to_string(),
+            vec![
+                Attribute::new("class".to_string(), "my-class".to_string()),
+                Attribute::new("width".to_string(), "100".to_string()),
+            ],
+            vec![],
+        );
 
         assert_eq!(
             element.to_string(),
@@ -184,4 +1146,647 @@ mod to_string {
 
         assert_eq!(element.to_string(), "<!-- Some comments -->");
     }
+
+    #[test]
+    fn comment_can_not_be_closed_early() {
+        let element =
+            Node::comment("x --> <script>alert(1)</script> --!> a---b <!-- -".to_string());
+
+        assert_eq!(
+            element.to_string(),
+            "<!-- x - -> <script>alert(1)</script> - -!> a- - -b <!- - - -->"
+        );
+        assert_eq!(element.estimated_len(), element.to_string().len());
+    }
+
+    #[test]
+    fn text_is_escaped() {
+        let element = Node::element(
+            "p".to_string(),
+            vec![],
+            vec![Node::text("<b>Tom & \"Jerry\"</b>".to_string())],
+        );
+
+        assert_eq!(
+            element.to_string(),
+            "<p>&lt;b&gt;Tom &amp; \"Jerry\"&lt;/b&gt;</p>"
+        );
+    }
+
+    #[test]
+    fn attribute_value_is_escaped() {
+        let attr = Attribute::new("title".to_string(), "say \"hi\" & <wave>".to_string());
+        assert_eq!(
+            attr.to_string(),
+            "title=\"say &quot;hi&quot; &amp; <wave>\""
+        )
+    }
+
+    #[test]
+    fn trusted_attribute_is_not_escaped() {
+        let attr = Attribute::trusted("href".to_string(), "/search?q=a&amp;page=2".to_string());
+        assert_eq!(attr.to_string(), "href=\"/search?q=a&amp;page=2\"")
+    }
+
+    #[test]
+    fn boolean_attributes() {
+        let element = Node::element(
+            "input".to_string(),
+            vec![
+                Attribute::new("disabled".to_string(), "false".to_string()),
+                Attribute::new("required".to_string(), "true".to_string()),
+                Attribute::new("checked".to_string(), "checked".to_string()),
+                Attribute::new("hidden".to_string(), "until-found".to_string()),
+                Attribute::new("value".to_string(), "false".to_string()),
+            ],
+            vec![],
+        );
+
+        assert_eq!(
+            element.to_string(),
+            "<input required checked hidden=\"until-found\" value=\"false\">"
+        );
+        assert_eq!(element.estimated_len(), element.to_string().len());
+    }
+
+    #[test]
+    fn style_attribute() {
+        let attr = Attribute::style(vec![
+            Declaration::new(
+                "color".to_string(),
+                DeclarationValue::Function("rgb".to_string(), vec!["0".to_string(); 3]),
+            ),
+            Declaration::new(
+                "font-family".to_string(),
+                DeclarationValue::Basic("Open Sans".to_string()),
+            ),
+        ]);
+
+        assert_eq!(
+            attr.to_string(),
+            "style=\"color:rgb(0,0,0);font-family:&quot;Open Sans&quot;;\""
+        );
+    }
+
+    #[test]
+    fn script_and_style_contents_are_not_escaped() {
+        let element = Node::element(
+            "head".to_string(),
+            vec![],
+            vec![
+                Node::element(
+                    "script".to_string(),
+                    vec![],
+                    vec![Node::text("if (a < b && c) {}".to_string())],
+                ),
+                Node::element(
+                    "style".to_string(),
+                    vec![],
+                    vec![Node::text("ul>li{color:red;}".to_string())],
+                ),
+            ],
+        );
+
+        assert_eq!(
+            element.to_string(),
+            "<head><script>if (a < b && c) {}</script><style>ul>li{color:red;}</style></head>"
+        );
+    }
+
+    #[test]
+    fn script_and_style_closing_tags_are_escaped() {
+        let script = Node::script(
+            "document.write('</SCRIPT><script>x()</script>');".to_string(),
+            vec![Attribute::new("type".to_string(), "module".to_string())],
+        );
+        let style = Node::stylesheet(&RuleSet::new(
+            vec![Rule::new(
+                Selector::PseudoElement(
+                    Box::new(Selector::Tag("p".to_string())),
+                    "after".to_string(),
+                ),
+                vec![Declaration::new(
+                    "content".to_string(),
+                    DeclarationValue::Basic("\"</style>\"".to_string()),
+                )],
+                vec![],
+            )],
+            vec![],
+            None,
+        ));
+
+        assert_eq!(
+            script.to_string(),
+            "<script type=\"module\">document.write('<\\/SCRIPT><script>x()<\\/script>');</script>"
+        );
+        assert_eq!(script.estimated_len(), script.to_string().len());
+        assert_eq!(
+            style.to_string(),
+            "<style>p::after{content:\"<\\/style>\";}</style>"
+        );
+    }
+
+    #[test]
+    fn raw_is_not_escaped() {
+        let element = Node::element(
+            "pre".to_string(),
+            vec![],
+            vec![
+                Node::raw("<span class=\"kw\">fn</span>".to_string()),
+                Node::text(" <main>".to_string()),
+            ],
+        );
+
+        assert_eq!(
+            element.to_string(),
+            "<pre><span class=\"kw\">fn</span> &lt;main&gt;</pre>"
+        );
+        assert_eq!(element.estimated_len(), element.to_string().len());
+    }
+
+    #[test]
+    fn fragment_has_no_wrapper() {
+        let element = Node::element(
+            "script".to_string(),
+            vec![],
+            vec![Node::fragment(vec![
+                Node::text("a && b;".to_string()),
+                Node::fragment(vec![Node::text("c < d;".to_string())]),
+            ])],
+        );
+        let fragment = Node::fragment(vec![
+            Node::element("p".to_string(), vec![], vec![]),
+            Node::text("a & b".to_string()),
+            element,
+        ]);
+
+        assert_eq!(
+            fragment.to_string(),
+            "<p></p>a &amp; b<script>a && b;c < d;</script>"
+        );
+        assert_eq!(fragment.estimated_len(), fragment.to_string().len());
+    }
+
+    #[test]
+    fn svg() {
+        let icon = Node::element(
+            "svg".to_string(),
+            vec![Attribute::new(
+                "viewbox".to_string(),
+                "0 0 10 10".to_string(),
+            )],
+            vec![
+                Node::element(
+                    "lineargradient".to_string(),
+                    vec![Attribute::new("id".to_string(), "g".to_string())],
+                    vec![Node::element(
+                        "stop".to_string(),
+                        vec![Attribute::new("offset".to_string(), "0".to_string())],
+                        vec![],
+                    )],
+                ),
+                Node::element(
+                    "path".to_string(),
+                    vec![Attribute::new("d".to_string(), "M0 0".to_string())],
+                    vec![],
+                ),
+                Node::element(
+                    "foreignObject".to_string(),
+                    vec![],
+                    vec![Node::element("br".to_string(), vec![], vec![])],
+                ),
+            ],
+        );
+        let element = Node::element("p".to_string(), vec![], vec![icon]);
+
+        assert_eq!(
+            element.to_string(),
+            "<p><svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 10 10\"><linearGradient id=\"g\"><stop offset=\"0\"/></linearGradient><path d=\"M0 0\"/><foreignObject><br></foreignObject></svg></p>"
+        );
+        assert_eq!(element.estimated_len(), element.to_string().len());
+    }
+
+    #[test]
+    fn svg_with_xmlns_and_style() {
+        let icon = Node::element(
+            "svg".to_string(),
+            vec![Attribute::new(
+                "xmlns".to_string(),
+                "http://www.w3.org/2000/svg".to_string(),
+            )],
+            vec![Node::element(
+                "style".to_string(),
+                vec![],
+                vec![Node::text("a>b{}".to_string())],
+            )],
+        );
+
+        assert_eq!(
+            icon.to_string(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\"><style>a&gt;b{}</style></svg>"
+        );
+        assert_eq!(icon.estimated_len(), icon.to_string().len());
+    }
+
+    #[test]
+    fn void_elements_have_no_closing_tag() {
+        let element = Node::element(
+            "p".to_string(),
+            vec![],
+            vec![
+                Node::text("one".to_string()),
+                Node::element("br".to_string(), vec![], vec![]),
+                Node::element(
+                    "IMG".to_string(),
+                    vec![Attribute::new("src".to_string(), "a.png".to_string())],
+                    vec![],
+                ),
+            ],
+        );
+
+        assert_eq!(element.to_string(), "<p>one<br><IMG src=\"a.png\"></p>");
+    }
+
+    #[test]
+    fn void_element_children_are_not_written() {
+        let element = Node::element(
+            "input".to_string(),
+            vec![],
+            vec![Node::text("ignored".to_string())],
+        );
+
+        assert_eq!(element.to_string(), "<input>");
+        assert_eq!(element.estimated_len(), element.to_string().len());
+    }
+
+    #[test]
+    fn forced_self_closing() {
+        let element = Node::self_closing(
+            "my-icon".to_string(),
+            vec![Attribute::new("name".to_string(), "star".to_string())],
+        );
+
+        assert_eq!(element.to_string(), "<my-icon name=\"star\"/>");
+    }
+
+    #[test]
+    fn self_closing_with_children_is_closed_normally() {
+        let mut element = Node::self_closing("g".to_string(), vec![]);
+        if let Node::Element { children, .. } = &mut element {
+            children.push(Node::text("a".to_string()));
+        }
+
+        assert_eq!(element.to_string(), "<g>a</g>");
+    }
+
+    #[test]
+    fn estimated_len_matches_output() {
+        let element = Node::element(
+            "body".to_string(),
+            vec![
+                Attribute::new("class".to_string(), "my-class".to_string()),
+                Attribute::toggle("hidden".to_string()),
+            ],
+            vec![
+                Node::element(
+                    "h1".to_string(),
+                    vec![],
+                    vec![Node::text("Heading".to_string())],
+                ),
+                Node::comment("Some comments".to_string()),
+                Node::text("Some <text> & more".to_string()),
+                Node::element(
+                    "script".to_string(),
+                    vec![Attribute::new(
+                        "data-x".to_string(),
+                        "\"quoted\"".to_string(),
+                    )],
+                    vec![Node::text("a && b".to_string())],
+                ),
+                Node::element(
+                    "hr".to_string(),
+                    vec![Attribute::toggle("hidden".to_string())],
+                    vec![],
+                ),
+                Node::self_closing(
+                    "circle".to_string(),
+                    vec![Attribute::new("r".to_string(), "1".to_string())],
+                ),
+            ],
+        );
+
+        assert_eq!(element.estimated_len(), element.to_string().len());
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn to_bytes() {
+        let element = Node::element(
+            "body".to_string(),
+            vec![],
+            vec![Node::text("Some text".to_string())],
+        );
+
+        assert_eq!(&element.to_bytes()[..], b"<body>Some text</body>");
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn write_bytes_into_buffer() {
+        let mut buffer = bytes::BytesMut::new();
+        let first = Node::text("first".to_string()).write_bytes(&mut buffer);
+        let second = Node::comment("second".to_string()).write_bytes(&mut buffer);
+
+        assert_eq!(&first[..], b"first");
+        assert_eq!(&second[..], b"<!-- second -->");
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn display_in_format() {
+        let element = Node::element("p".to_string(), vec![], vec![Node::text("a".to_string())]);
+
+        assert_eq!(format!("<main>{}</main>", element), "<main><p>a</p></main>");
+    }
+
+    #[test]
+    fn write_to_existing_output() {
+        let mut output = "<!-- header -->".to_string();
+        Node::element("p".to_string(), vec![], vec![Node::text("a".to_string())])
+            .write_to(&mut output)
+            .unwrap();
+
+        assert_eq!(output, "<!-- header --><p>a</p>");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_io() {
+        let mut output: Vec<u8> = vec![];
+        Node::element(
+            "p".to_string(),
+            vec![],
+            vec![Node::text("a & b".to_string())],
+        )
+        .write_io(&mut output)
+        .unwrap();
+
+        assert_eq!(output, b"<p>a &amp; b</p>");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_io_keeps_io_error() {
+        let mut buffer = [0u8; 4];
+        let error = Node::text("longer than the buffer".to_string())
+            .write_io(&mut &mut buffer[..])
+            .unwrap_err();
+
+        assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn equal_nodes_hash_equal() {
+        use std::collections::HashSet;
+
+        let make = |class: &str| {
+            Node::element(
+                "div".to_string(),
+                vec![Attribute::new("class".to_string(), class.to_string())],
+                vec![Node::text("Some text".to_string())],
+            )
+        };
+        let set = [make("a"), make("b"), make("a")]
+            .into_iter()
+            .collect::<HashSet<Node>>();
+
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&make("a")));
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_nodes_estimate_their_length() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let mut seed = 1u32;
+        for _ in 0..200 {
+            let bytes = (0..512)
+                .map(|_| {
+                    seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                    (seed >> 16) as u8
+                })
+                .collect::<Vec<u8>>();
+
+            if let Ok(node) = Node::arbitrary(&mut Unstructured::new(&bytes)) {
+                assert_eq!(node.estimated_len(), node.to_string().len());
+            }
+        }
+    }
+
+    #[cfg(feature = "hashes")]
+    #[test]
+    fn to_string_with_hash() {
+        let element = Node::element(
+            "body".to_string(),
+            vec![],
+            vec![Node::text("Some text".to_string())],
+        );
+        let (html, hash) = element.to_string_with_hash();
+
+        assert_eq!(html, "<body>Some text</body>");
+        assert_eq!(hash, crate::hash::ContentHash::of(html.as_bytes()));
+    }
+}
+
+#[cfg(test)]
+mod accessors {
+    use crate::html::{Attribute, Node};
+
+    #[test]
+    fn element() {
+        let element = Node::element(
+            "a".to_string(),
+            vec![
+                Attribute::new("href".to_string(), "/".to_string()),
+                Attribute::toggle("download".to_string()),
+            ],
+            vec![Node::text("Home".to_string())],
+        );
+
+        assert_eq!(element.tag(), Some("a"));
+        assert_eq!(element.attributes()[0].name(), "href");
+        assert_eq!(element.attributes()[0].value(), Some("/"));
+        assert_eq!(element.attributes()[1].value(), None);
+        assert_eq!(element.children()[0].text_content(), Some("Home"));
+        assert_eq!(element.text_content(), None);
+    }
+
+    #[test]
+    fn non_elements() {
+        let comment = Node::comment("note".to_string());
+
+        assert_eq!(comment.tag(), None);
+        assert!(comment.attributes().is_empty());
+        assert!(comment.children().is_empty());
+        assert_eq!(comment.text_content(), Some("note"));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let node = Node::fragment(vec![
+            Node::element(
+                "svg".to_string(),
+                vec![Attribute::new("viewBox".to_string(), "0 0 1 1".to_string())],
+                vec![Node::self_closing("path".to_string(), vec![])],
+            ),
+            Node::raw("<b>raw</b>".to_string()),
+        ]);
+        let json = serde_json::to_string(&node).unwrap();
+
+        assert!(json.contains("\"tag\":\"svg\""));
+        assert_eq!(serde_json::from_str::<Node>(&json).unwrap(), node);
+    }
+
+    #[test]
+    fn typed_attribute_values() {
+        let json = r#"{"Element": {"tag": "input", "attributes": [
+            {"name": "maxlength", "value": 10},
+            {"name": "step", "value": 0.5},
+            {"name": "required", "value": true},
+            {"name": "disabled", "value": false},
+            {"name": "aria-hidden", "value": false},
+            {"name": "aria-busy", "value": true},
+            {"name": "aria-label", "value": "false"},
+            {"name": "class", "value": ["a", "b"]},
+            {"name": "name", "value": "q"},
+            {"name": "readonly"}
+        ]}}"#;
+        let node = serde_json::from_str::<Node>(json).unwrap();
+
+        assert_eq!(
+            node.to_string(),
+            "<input maxlength=\"10\" step=\"0.5\" required aria-busy=\"true\" aria-label=\"false\" class=\"a b\" name=\"q\" readonly>"
+        );
+        let json = serde_json::to_string(&node).unwrap();
+        assert_eq!(serde_json::from_str::<Node>(&json).unwrap(), node);
+        assert_eq!(
+            Attribute::typed(
+                "rel".to_string(),
+                vec!["noopener".to_string(), "nofollow".to_string()]
+            ),
+            Attribute::new("rel".to_string(), "noopener nofollow".to_string())
+        );
+        assert_eq!(
+            Attribute::typed("width".to_string(), 2.0).value(),
+            Some("2")
+        );
+    }
+}
+
+#[cfg(test)]
+mod mutation {
+    use crate::html::{Attribute, Node};
+
+    fn list() -> Node {
+        Node::element(
+            "ul".to_string(),
+            vec![Attribute::new("class".to_string(), "menu".to_string())],
+            vec![
+                Node::element("li".to_string(), vec![], vec![Node::text("a".to_string())]),
+                Node::comment("b".to_string()),
+            ],
+        )
+    }
+
+    #[test]
+    fn children() {
+        let mut node = list();
+        assert!(node.append_child(Node::text("c".to_string())));
+        node.retain_children(|c| !matches!(c, Node::Comment(_)));
+        let replaced = node.replace_child(1, Node::text("d".to_string()));
+
+        assert_eq!(replaced, Some(Node::text("c".to_string())));
+        assert_eq!(node.replace_child(5, Node::text("e".to_string())), None);
+        assert_eq!(node.to_string(), "<ul class=\"menu\"><li>a</li>d</ul>");
+    }
+
+    #[test]
+    fn attributes() {
+        let mut node = list();
+        node.set_attribute(Attribute::new("id".to_string(), "nav".to_string()));
+        node.set_attribute(Attribute::new("CLASS".to_string(), "links".to_string()));
+        let removed = node.remove_attribute("id");
+
+        assert_eq!(
+            removed,
+            Some(Attribute::new("id".to_string(), "nav".to_string()))
+        );
+        assert_eq!(node.remove_attribute("id"), None);
+        assert_eq!(node.attributes().len(), 1);
+        assert_eq!(node.attributes()[0].value(), Some("links"));
+    }
+
+    #[test]
+    fn non_elements() {
+        let mut node = Node::text("a".to_string());
+
+        assert!(!node.append_child(Node::text("b".to_string())));
+        assert!(!node.set_attribute(Attribute::toggle("hidden".to_string())));
+        assert_eq!(node, Node::text("a".to_string()));
+    }
+}
+
+#[cfg(test)]
+mod document {
+    use crate::html::{Attribute, Document, Node};
+
+    #[test]
+    fn default_skeleton() {
+        assert_eq!(
+            Document::default().to_string(),
+            "<!DOCTYPE html><html><head></head><body></body></html>"
+        );
+    }
+
+    #[test]
+    fn fragments_are_placed_in_a_body() {
+        let document = Document::new(Node::element("main".to_string(), vec![], vec![]));
+
+        assert_eq!(
+            document.to_string(),
+            "<!DOCTYPE html><html><head></head><body><main></main></body></html>"
+        );
+    }
+
+    #[test]
+    fn html_root_without_head() {
+        let mut document = Document::new(Node::element(
+            "html".to_string(),
+            vec![Attribute::new("lang".to_string(), "en".to_string())],
+            vec![Node::element("body".to_string(), vec![], vec![])],
+        ));
+        document.add_meta("viewport".to_string(), "width=device-width".to_string());
+
+        assert_eq!(
+            document.to_string(),
+            "<!DOCTYPE html><html lang=\"en\"><head><meta name=\"viewport\" content=\"width=device-width\"></head><body></body></html>"
+        );
+    }
+
+    #[test]
+    fn head_helpers() {
+        let mut document = Document::default();
+        document.set_title("First".to_string());
+        document.add_link("stylesheet".to_string(), "/site.css".to_string());
+        document.add_script("/app.js".to_string());
+        document.set_title("Second".to_string());
+        document.body_mut().push(Node::text("content".to_string()));
+
+        assert_eq!(
+            document.to_string(),
+            "<!DOCTYPE html><html><head><title>Second</title><link rel=\"stylesheet\" href=\"/site.css\"><script src=\"/app.js\"></script></head><body>content</body></html>"
+        );
+        assert_eq!(document.estimated_len(), document.to_string().len());
+    }
 }
@@ -0,0 +1,280 @@
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::html::Node;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct MessageArgument {
+    name: String,
+    value: String,
+}
+
+impl MessageArgument {
+    pub fn new(name: String, value: String) -> Self {
+        Self { name, value }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct PluralForms {
+    #[serde(default)]
+    zero: Option<String>,
+    #[serde(default)]
+    one: Option<String>,
+    other: String,
+}
+
+impl PluralForms {
+    pub fn new(zero: Option<String>, one: Option<String>, other: String) -> Self {
+        Self { zero, one, other }
+    }
+
+    fn select(&self, count: i64) -> &str {
+        match (count, &self.zero, &self.one) {
+            (0, Some(zero), _) => zero,
+            (1, _, Some(one)) => one,
+            _ => &self.other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum Message {
+    Text(String),
+    Plural(PluralForms),
+}
+
+impl Message {
+    pub fn format(&self, arguments: &[MessageArgument], count: Option<i64>) -> String {
+        let template = match (self, count) {
+            (Message::Text(template), _) => template.as_str(),
+            (Message::Plural(forms), Some(count)) => forms.select(count),
+            (Message::Plural(forms), None) => &forms.other,
+        };
+
+        interpolate(template, arguments, count)
+    }
+}
+
+// replaces {name} placeholders with the matching argument value, {count} with the plural count
+// unknown placeholders are left as is
+fn interpolate(template: &str, arguments: &[MessageArgument], count: Option<i64>) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        let end = match after.find('}') {
+            Some(end) => end,
+            None => {
+                rest = &rest[start..];
+                break;
+            }
+        };
+
+        let name = &after[..end];
+        match arguments.iter().find(|a| a.name == name) {
+            Some(argument) => output.push_str(&argument.value),
+            None => match (name, count) {
+                ("count", Some(count)) => output.push_str(&count.to_string()),
+                _ => output.push_str(&rest[start..start + end + 2]),
+            },
+        }
+
+        rest = &after[end + 1..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct Catalog {
+    locales: BTreeMap<String, BTreeMap<String, Message>>,
+    fallback: Option<String>,
+}
+
+impl Catalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_fallback(mut self, locale: String) -> Self {
+        self.fallback = Some(locale);
+        self
+    }
+
+    pub fn insert(&mut self, locale: String, key: String, message: Message) {
+        self.locales.entry(locale).or_default().insert(key, message);
+    }
+
+    // looks in the exact locale (e.g. en-US), then its language (en), then the fallback locale
+    pub fn get(&self, locale: &str, key: &str) -> Option<&Message> {
+        let language = locale.split('-').next().unwrap_or(locale);
+
+        [Some(locale), Some(language), self.fallback.as_deref()]
+            .into_iter()
+            .flatten()
+            .find_map(|l| self.locales.get(l).and_then(|messages| messages.get(key)))
+    }
+
+    pub fn format(
+        &self,
+        locale: &str,
+        key: &str,
+        arguments: &[MessageArgument],
+        count: Option<i64>,
+    ) -> Option<String> {
+        self.get(locale, key)
+            .map(|message| message.format(arguments, count))
+    }
+
+    // returns a copy of the tree with every Node::Message resolved to text for the given locale
+    // messages missing from the catalog are left in place and render as their key
+    pub fn localize(&self, node: &Node, locale: &str) -> Node {
+        match node {
+            Node::Message {
+                key,
+                arguments,
+                count,
+            } => match self.format(locale, key, arguments, *count) {
+                Some(text) => Node::Text(text),
+                None => node.clone(),
+            },
+            Node::Element {
+                tag,
+                attributes,
+                children,
+                self_closing,
+            } => Node::Element {
+                tag: tag.clone(),
+                attributes: attributes.clone(),
+                children: children
+                    .iter()
+                    .map(|child| self.localize(child, locale))
+                    .collect::<Vec<Node>>(),
+                self_closing: *self_closing,
+            },
+            Node::Fragment(children) => Node::Fragment(
+                children
+                    .iter()
+                    .map(|child| self.localize(child, locale))
+                    .collect::<Vec<Node>>(),
+            ),
+            _ => node.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::html::Node;
+    use crate::i18n::{Catalog, Message, MessageArgument, PluralForms};
+
+    fn make_catalog() -> Catalog {
+        let mut catalog = Catalog::new().with_fallback("en".to_string());
+        catalog.insert(
+            "en".to_string(),
+            "greeting".to_string(),
+            Message::Text("Hello, {name}!".to_string()),
+        );
+        catalog.insert(
+            "fr".to_string(),
+            "greeting".to_string(),
+            Message::Text("Bonjour, {name} !".to_string()),
+        );
+        catalog.insert(
+            "en".to_string(),
+            "items".to_string(),
+            Message::Plural(PluralForms::new(
+                Some("No items".to_string()),
+                Some("One item".to_string()),
+                "{count} items".to_string(),
+            )),
+        );
+        catalog
+    }
+
+    fn name_argument() -> Vec<MessageArgument> {
+        vec![MessageArgument::new("name".to_string(), "Ada".to_string())]
+    }
+
+    #[test]
+    fn format_with_argument() {
+        let catalog = make_catalog();
+
+        assert_eq!(
+            catalog.format("fr", "greeting", &name_argument(), None),
+            Some("Bonjour, Ada !".to_string())
+        );
+    }
+
+    #[test]
+    fn format_unknown_placeholder_kept() {
+        let message = Message::Text("{missing} and {name".to_string());
+
+        assert_eq!(
+            message.format(&name_argument(), None),
+            "{missing} and {name"
+        );
+    }
+
+    #[test]
+    fn format_plural_forms() {
+        let catalog = make_catalog();
+
+        assert_eq!(
+            catalog.format("en", "items", &[], Some(0)),
+            Some("No items".to_string())
+        );
+        assert_eq!(
+            catalog.format("en", "items", &[], Some(1)),
+            Some("One item".to_string())
+        );
+        assert_eq!(
+            catalog.format("en", "items", &[], Some(5)),
+            Some("5 items".to_string())
+        );
+    }
+
+    #[test]
+    fn lookup_falls_back_to_language_then_fallback() {
+        let catalog = make_catalog();
+
+        assert_eq!(
+            catalog.format("fr-CA", "greeting", &name_argument(), None),
+            Some("Bonjour, Ada !".to_string())
+        );
+        assert_eq!(
+            catalog.format("de", "greeting", &name_argument(), None),
+            Some("Hello, Ada!".to_string())
+        );
+    }
+
+    #[test]
+    fn localize_tree() {
+        let catalog = make_catalog();
+        let node = Node::element(
+            "p".to_string(),
+            vec![],
+            vec![
+                Node::message("greeting".to_string(), name_argument(), None),
+                Node::message("unknown".to_string(), vec![], None),
+            ],
+        );
+
+        assert_eq!(
+            catalog.localize(&node, "fr").to_string(),
+            "<p>Bonjour, Ada !unknown</p>"
+        );
+        assert_eq!(
+            catalog.localize(&node, "en").to_string(),
+            "<p>Hello, Ada!unknown</p>"
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::builder::Element;
+use crate::html::{Attribute, Node};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Descriptor {
+    Width(u32),   // 480w, the image's width in pixels, used together with sizes
+    Density(f64), // 2x, for fixed size images on high density screens
+}
+
+impl fmt::Display for Descriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Descriptor::Width(width) => write!(f, "{}w", width),
+            Descriptor::Density(density) => write!(f, "{}x", density),
+        }
+    }
+}
+
+// candidates written as "url descriptor" separated by commas
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SrcSet {
+    candidates: Vec<(String, Descriptor)>,
+}
+
+impl SrcSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn width(mut self, url: impl Into<String>, width: u32) -> Self {
+        self.candidates.push((url.into(), Descriptor::Width(width)));
+        self
+    }
+
+    pub fn density(mut self, url: impl Into<String>, density: f64) -> Self {
+        self.candidates
+            .push((url.into(), Descriptor::Density(density)));
+        self
+    }
+
+    pub fn widths<U: Into<String>>(self, candidates: impl IntoIterator<Item = (U, u32)>) -> Self {
+        candidates
+            .into_iter()
+            .fold(self, |set, (url, width)| set.width(url, width))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.candidates.is_empty()
+    }
+
+    fn first_url(&self) -> Option<&str> {
+        self.candidates.first().map(|(url, _)| url.as_str())
+    }
+}
+
+impl fmt::Display for SrcSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (url, descriptor)) in self.candidates.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            // spaces and commas would split the candidate
+            for c in url.chars() {
+                match c {
+                    ' ' => f.write_str("%20")?,
+                    ',' => f.write_str("%2C")?,
+                    c => fmt::Write::write_char(f, c)?,
+                }
+            }
+            write!(f, " {}", descriptor)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponsiveImage {
+    srcset: SrcSet,
+    sizes: Option<String>,
+    src: Option<String>,
+    attributes: Vec<Attribute>,
+}
+
+impl ResponsiveImage {
+    pub fn new(srcset: SrcSet, alt: impl Into<String>) -> Self {
+        Self {
+            srcset,
+            sizes: None,
+            src: None,
+            attributes: vec![Attribute::new("alt".to_string(), alt.into())],
+        }
+    }
+
+    // e.g. "(max-width: 600px) 100vw, 50vw"
+    pub fn sizes(mut self, sizes: impl Into<String>) -> Self {
+        self.sizes = Some(sizes.into());
+        self
+    }
+
+    // for browsers without srcset support, defaults to the first candidate
+    pub fn src(mut self, src: impl Into<String>) -> Self {
+        self.src = Some(src.into());
+        self
+    }
+
+    pub fn attr(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let attribute = Attribute::new(name.into(), value.into());
+        self.attributes
+            .retain(|a| !a.name().eq_ignore_ascii_case(attribute.name()));
+        self.attributes.push(attribute);
+        self
+    }
+
+    pub fn build(self) -> Node {
+        let mut attributes = Vec::new();
+        if let Some(src) = self.src.as_deref().or(self.srcset.first_url()) {
+            attributes.push(Attribute::new("src".to_string(), src.to_string()));
+        }
+        if !self.srcset.is_empty() {
+            attributes.push(Attribute::new(
+                "srcset".to_string(),
+                self.srcset.to_string(),
+            ));
+        }
+        if let Some(sizes) = self.sizes {
+            attributes.push(Attribute::new("sizes".to_string(), sizes));
+        }
+        // alt and the other attributes come after the image sources
+        attributes.extend(self.attributes);
+        Node::element("img".to_string(), attributes, Vec::new())
+    }
+}
+
+// a <source> of a picture, picked by the browser when its media and type match
+#[derive(Debug, Clone, PartialEq)]
+pub struct Source {
+    srcset: SrcSet,
+    sizes: Option<String>,
+    media: Option<String>,
+    mime_type: Option<String>,
+}
+
+impl Source {
+    pub fn new(srcset: SrcSet) -> Self {
+        Self {
+            srcset,
+            sizes: None,
+            media: None,
+            mime_type: None,
+        }
+    }
+
+    pub fn sizes(mut self, sizes: impl Into<String>) -> Self {
+        self.sizes = Some(sizes.into());
+        self
+    }
+
+    pub fn media(mut self, media: impl Into<String>) -> Self {
+        self.media = Some(media.into());
+        self
+    }
+
+    // e.g. image/avif
+    pub fn mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+
+    pub fn build(self) -> Node {
+        let mut element = Element::new("source");
+        if let Some(mime_type) = self.mime_type {
+            element = element.attr("type", mime_type);
+        }
+        if let Some(media) = self.media {
+            element = element.attr("media", media);
+        }
+        element = element.attr("srcset", self.srcset.to_string());
+        if let Some(sizes) = self.sizes {
+            element = element.attr("sizes", sizes);
+        }
+        element.build()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Picture {
+    sources: Vec<Source>,
+    image: ResponsiveImage,
+}
+
+impl Picture {
+    // the image is the fallback when no source matches
+    pub fn new(image: ResponsiveImage) -> Self {
+        Self {
+            sources: Vec::new(),
+            image,
+        }
+    }
+
+    // sources are tried in the order they are added
+    pub fn source(mut self, source: Source) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    pub fn build(self) -> Node {
+        Element::new("picture")
+            .children(self.sources.into_iter().map(Source::build))
+            .child(self.image.build())
+            .build()
+    }
+}
+
+impl From<ResponsiveImage> for Node {
+    fn from(image: ResponsiveImage) -> Self {
+        image.build()
+    }
+}
+
+impl From<Source> for Node {
+    fn from(source: Source) -> Self {
+        source.build()
+    }
+}
+
+impl From<Picture> for Node {
+    fn from(picture: Picture) -> Self {
+        picture.build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::images::{Picture, ResponsiveImage, Source, SrcSet};
+
+    #[test]
+    fn image_with_widths() {
+        let image = ResponsiveImage::new(
+            SrcSet::new().widths([("/a-480.jpg", 480), ("/a 800.jpg", 800)]),
+            "A \"photo\"",
+        )
+        .sizes("(max-width: 600px) 100vw, 50vw")
+        .attr("loading", "lazy")
+        .build();
+
+        assert_eq!(
+            image.to_string(),
+            "<img src=\"/a-480.jpg\" srcset=\"/a-480.jpg 480w, /a%20800.jpg 800w\" sizes=\"(max-width: 600px) 100vw, 50vw\" alt=\"A &quot;photo&quot;\" loading=\"lazy\">"
+        );
+    }
+
+    #[test]
+    fn picture_with_sources() {
+        let picture = Picture::new(
+            ResponsiveImage::new(
+                SrcSet::new()
+                    .density("/logo.png", 1.0)
+                    .density("/logo@2x.png", 2.0),
+                "Logo",
+            )
+            .src("/logo.png"),
+        )
+        .source(
+            Source::new(
+                SrcSet::new()
+                    .density("/logo.avif", 1.0)
+                    .density("/logo@2x.avif", 2.0),
+            )
+            .mime_type("image/avif"),
+        )
+        .build();
+
+        assert_eq!(
+            picture.to_string(),
+            "<picture><source type=\"image/avif\" srcset=\"/logo.avif 1x, /logo@2x.avif 2x\"><img src=\"/logo.png\" srcset=\"/logo.png 1x, /logo@2x.png 2x\" alt=\"Logo\"></picture>"
+        );
+    }
+}
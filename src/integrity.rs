@@ -0,0 +1,161 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use crate::html::{Attribute, Node};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum IntegrityAlgorithm {
+    Sha256,
+    #[default]
+    Sha384,
+    Sha512,
+}
+
+impl IntegrityAlgorithm {
+    // value for an integrity attribute
+    pub fn integrity(&self, content: &[u8]) -> String {
+        match self {
+            IntegrityAlgorithm::Sha256 => {
+                format!("sha256-{}", STANDARD.encode(Sha256::digest(content)))
+            }
+            IntegrityAlgorithm::Sha384 => {
+                format!("sha384-{}", STANDARD.encode(Sha384::digest(content)))
+            }
+            IntegrityAlgorithm::Sha512 => {
+                format!("sha512-{}", STANDARD.encode(Sha512::digest(content)))
+            }
+        }
+    }
+}
+
+fn attribute_value<'a>(attributes: &'a [Attribute], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|a| a.name().eq_ignore_ascii_case(name))
+        .and_then(Attribute::value)
+}
+
+fn set_attribute(attributes: &mut Vec<Attribute>, name: &str, value: String) {
+    attributes.retain(|a| !a.name().eq_ignore_ascii_case(name));
+    attributes.push(Attribute::new(name.to_string(), value));
+}
+
+// url of the subresource the element loads, if integrity applies to it
+fn subresource_url(tag: &str, attributes: &[Attribute]) -> Option<String> {
+    match tag.to_ascii_lowercase().as_str() {
+        "script" => attribute_value(attributes, "src").map(str::to_string),
+        "link" => {
+            let rel = attribute_value(attributes, "rel")
+                .unwrap_or("")
+                .to_ascii_lowercase();
+            let kind = attribute_value(attributes, "as").unwrap_or("");
+            let applies = rel.split_whitespace().any(|r| match r {
+                "stylesheet" | "modulepreload" => true,
+                "preload" => kind == "script" || kind == "style",
+                _ => false,
+            });
+
+            match applies {
+                true => attribute_value(attributes, "href").map(str::to_string),
+                false => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// adds integrity and crossorigin attributes to scripts and stylesheets whose content the resolver returns
+// urls the resolver has no content for are left untouched and returned
+pub fn add_integrity<R>(
+    node: &mut Node,
+    algorithm: IntegrityAlgorithm,
+    mut resolve: R,
+) -> Vec<String>
+where
+    R: FnMut(&str) -> Option<Vec<u8>>,
+{
+    let mut unresolved = Vec::new();
+    add_integrity_to(node, algorithm, &mut resolve, &mut unresolved);
+    unresolved
+}
+
+fn add_integrity_to<R>(
+    node: &mut Node,
+    algorithm: IntegrityAlgorithm,
+    resolve: &mut R,
+    unresolved: &mut Vec<String>,
+) where
+    R: FnMut(&str) -> Option<Vec<u8>>,
+{
+    if let Node::Element {
+        tag, attributes, ..
+    } = node
+    {
+        if let Some(url) = subresource_url(tag, attributes) {
+            match resolve(&url) {
+                Some(content) => {
+                    set_attribute(attributes, "integrity", algorithm.integrity(&content));
+                    // integrity is only checked on cross origin responses fetched with cors
+                    if attribute_value(attributes, "crossorigin").is_none() {
+                        set_attribute(attributes, "crossorigin", "anonymous".to_string());
+                    }
+                }
+                None => unresolved.push(url),
+            }
+        }
+    }
+
+    for child in node.children_mut().into_iter().flatten() {
+        add_integrity_to(child, algorithm, resolve, unresolved);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::integrity::{add_integrity, IntegrityAlgorithm};
+
+    #[test]
+    fn integrity_values() {
+        assert_eq!(
+            IntegrityAlgorithm::Sha256.integrity(b"init();"),
+            "sha256-GEnM5q1nYY/iACnyMTdov+tNp9OFcBnnDgNXUXaVNXc="
+        );
+        assert_eq!(
+            IntegrityAlgorithm::Sha384.integrity(b"init();"),
+            "sha384-NudqU2qbWIeAK5N3uAsFrzjIUjHIGeo1tr2d5+FXRSD+q7ZcNbfKgQw8zz/4E7od"
+        );
+    }
+
+    #[test]
+    fn adds_attributes() {
+        let mut page = crate::html! {
+            head {
+                script src="/app.js" {}
+                link rel="stylesheet" href="/site.css" crossorigin="use-credentials";
+                link rel="icon" href="/favicon.ico";
+                script src="/missing.js" {}
+            }
+        };
+
+        let unresolved = add_integrity(&mut page, IntegrityAlgorithm::Sha256, |url| match url {
+            "/app.js" => Some(b"init();".to_vec()),
+            "/site.css" => Some(b"body{margin:0;}".to_vec()),
+            _ => None,
+        });
+
+        assert_eq!(unresolved, vec!["/missing.js".to_string()]);
+        assert_eq!(
+            page.to_string(),
+            "<head>\
+            <script src=\"/app.js\" integrity=\"sha256-GEnM5q1nYY/iACnyMTdov+tNp9OFcBnnDgNXUXaVNXc=\" crossorigin=\"anonymous\"></script>\
+            <link rel=\"stylesheet\" href=\"/site.css\" crossorigin=\"use-credentials\" integrity=\"sha256-g+EAjyd74jr1J8e/0FxwKydnJCCTgwTO87sHqCbhlWk=\">\
+            <link rel=\"icon\" href=\"/favicon.ico\">\
+            <script src=\"/missing.js\"></script>\
+            </head>"
+        );
+    }
+}
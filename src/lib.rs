@@ -1,7 +1,122 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 pub mod html;
 pub mod css;
+pub mod animation;
+pub mod arena;
+pub mod at_rule;
+pub mod audit;
+pub mod builder;
+pub mod bundle;
+pub mod class;
+pub mod color;
+pub mod csp;
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml"))]
+pub mod data;
+pub mod dataset;
+pub mod diff;
+#[cfg(feature = "scraper")]
+pub mod dom;
+pub mod emmet;
+pub mod error;
+pub mod flex;
+pub mod forms;
+pub mod gradient;
+pub mod grid;
+#[cfg(feature = "hashes")]
+pub mod hash;
+pub mod head;
+pub mod i18n;
+pub mod images;
+#[cfg(feature = "hashes")]
+pub mod integrity;
+#[cfg(feature = "lightningcss")]
+pub mod lightning;
+#[cfg(feature = "markdown")]
+pub mod markdown;
+#[cfg(feature = "maud")]
+pub mod markup;
+pub mod minify;
+pub mod name;
+pub mod navigation;
+pub mod order;
+pub mod parse;
+pub mod prefix;
+pub mod query;
+pub mod sanitize;
+pub mod scope;
+pub mod selector;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod slots;
+pub mod snapshot;
+pub mod social;
+pub mod stylesheet;
+pub mod table;
+pub mod tag;
+pub mod tokens;
+pub mod transform;
+pub mod validate;
+pub mod values;
+#[cfg(feature = "yew")]
+pub mod views;
+pub mod walk;
+#[cfg(feature = "std")]
 mod serialize;
+#[cfg(feature = "std")]
+mod stream;
 
 pub use html::*;
 pub use css::*;
-pub use serialize::*;
\ No newline at end of file
+pub use animation::*;
+pub use arena::*;
+pub use at_rule::*;
+pub use audit::*;
+pub use builder::*;
+pub use bundle::*;
+pub use class::*;
+pub use color::*;
+pub use csp::*;
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml"))]
+pub use data::*;
+pub use diff::*;
+#[cfg(feature = "scraper")]
+pub use dom::*;
+pub use error::*;
+pub use forms::*;
+pub use gradient::*;
+pub use grid::*;
+#[cfg(feature = "hashes")]
+pub use hash::*;
+pub use head::*;
+pub use i18n::*;
+pub use images::*;
+#[cfg(feature = "hashes")]
+pub use integrity::*;
+#[cfg(feature = "lightningcss")]
+pub use lightning::*;
+#[cfg(all(feature = "maud", feature = "scraper"))]
+pub use markup::*;
+pub use name::*;
+pub use navigation::*;
+pub use order::*;
+pub use prefix::*;
+pub use sanitize::*;
+pub use scope::*;
+#[cfg(feature = "server")]
+pub use server::*;
+pub use slots::*;
+pub use snapshot::*;
+pub use social::*;
+pub use stylesheet::*;
+pub use table::*;
+pub use tag::*;
+pub use tokens::*;
+pub use transform::*;
+pub use validate::*;
+pub use values::*;
+pub use walk::*;
+#[cfg(feature = "std")]
+pub use serialize::*;
@@ -0,0 +1,753 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use cssparser::ToCss as _;
+use lightningcss::media_query::{
+    MediaCondition as LightningCondition, MediaFeatureComparison, MediaList, MediaType, Operator,
+    Qualifier, QueryFeature,
+};
+use lightningcss::printer::PrinterOptions;
+use lightningcss::properties::Property;
+use lightningcss::rules::keyframes::{
+    KeyframeSelector as LightningKeyframeSelector, KeyframesName, KeyframesRule,
+};
+use lightningcss::rules::page::{
+    PagePseudoClass as LightningPagePseudoClass, PageRule as LightningPageRule,
+};
+use lightningcss::rules::style::StyleRule;
+use lightningcss::rules::{CssRule, CssRuleList};
+use lightningcss::selector::{Combinator as LightningCombinator, Component, SelectorList};
+use lightningcss::stylesheet::{MinifyOptions, ParserOptions, StyleSheet};
+use lightningcss::targets::Targets;
+use lightningcss::traits::ToCss;
+use lightningcss::vendor_prefix::VendorPrefix;
+
+use crate::at_rule::{
+    AtRule, Keyframe, KeyframeSelector, Keyframes, MarginBox, PagePseudoClass, PageRule,
+    PageSelector,
+};
+use crate::css::{
+    AttributeCase, Combinator, Declaration, DeclarationValue, MediaCondition, MediaConstraint,
+    MediaFeature, MediaQuery, NthKind, PseudoFunction, Rule, RuleSet, Selector,
+};
+use crate::error::Error;
+use crate::stylesheet;
+
+fn css_error<E: ToString>(error: E) -> Error {
+    Error::Css(error.to_string())
+}
+
+fn unsupported(what: &str) -> Error {
+    Error::Css(format!("{} can not be represented by the css types", what))
+}
+
+pub fn stylesheet_from_css(css: &str) -> Result<StyleSheet<'_>, Error> {
+    StyleSheet::parse(css, ParserOptions::default()).map_err(css_error)
+}
+
+// minified css with vendor prefixes added and syntax lowered for the given browser targets
+pub fn minify_css(set: &RuleSet, targets: Targets) -> Result<String, Error> {
+    let css = set.to_string();
+    let mut sheet = stylesheet_from_css(&css)?;
+    sheet
+        .minify(MinifyOptions {
+            targets,
+            ..MinifyOptions::default()
+        })
+        .map_err(css_error)?;
+
+    sheet
+        .to_css(PrinterOptions {
+            minify: true,
+            targets,
+            ..PrinterOptions::default()
+        })
+        .map(|result| result.code)
+        .map_err(css_error)
+}
+
+// same as minify_css but brought back into a RuleSet so it can be combined with other rules
+pub fn optimize_rule_set(set: &RuleSet, targets: Targets) -> Result<RuleSet, Error> {
+    let css = minify_css(set, targets)?;
+    let sheet = stylesheet_from_css(&css)?;
+    rule_set_from_stylesheet(&sheet)
+}
+
+pub fn rule_set_from_stylesheet(sheet: &StyleSheet) -> Result<RuleSet, Error> {
+    convert_rules(&sheet.rules, None)
+}
+
+fn convert_rules(list: &CssRuleList, media_query: Option<MediaQuery>) -> Result<RuleSet, Error> {
+    let mut rules = vec![];
+    let mut sub_sets = vec![];
+    let mut at_rules = vec![];
+
+    for rule in &list.0 {
+        match rule {
+            CssRule::Style(style) => rules.push(convert_style_rule(style)?),
+            CssRule::Media(media) => {
+                sub_sets.push(convert_rules(
+                    &media.rules,
+                    Some(convert_media_list(&media.query)?),
+                )?);
+            }
+            CssRule::Keyframes(keyframes) => {
+                at_rules.push(AtRule::Keyframes(convert_keyframes(keyframes)?))
+            }
+            CssRule::LayerStatement(statement) => at_rules.push(AtRule::LayerOrder(
+                statement
+                    .names
+                    .iter()
+                    .map(|name| name.to_css_string(PrinterOptions::default()))
+                    .collect::<Result<_, _>>()
+                    .map_err(css_error)?,
+            )),
+            CssRule::LayerBlock(block) => at_rules.push(AtRule::Layer(
+                match &block.name {
+                    Some(name) => Some(
+                        name.to_css_string(PrinterOptions::default())
+                            .map_err(css_error)?,
+                    ),
+                    None => None,
+                },
+                convert_rules(&block.rules, None)?,
+            )),
+            CssRule::Page(page) => at_rules.push(AtRule::Page(convert_page(page)?)),
+            // at-rules without a dedicated type are kept as generic ones
+            CssRule::Property(_)
+            | CssRule::Scope(_)
+            | CssRule::StartingStyle(_)
+            | CssRule::CounterStyle(_)
+            | CssRule::Namespace(_)
+            | CssRule::ViewTransition(_)
+            | CssRule::PositionTry(_)
+            | CssRule::Unknown(_) => at_rules.push(AtRule::parse(
+                &rule
+                    .to_css_string(PrinterOptions::default())
+                    .map_err(css_error)?,
+            )?),
+            _ => {
+                return Err(unsupported(
+                    "at-rules other than @media, @keyframes, @layer and @page",
+                ))
+            }
+        }
+    }
+
+    Ok(at_rules
+        .into_iter()
+        .fold(RuleSet::new(rules, sub_sets, media_query), RuleSet::at_rule))
+}
+
+// nested style rules become sub rules, their selectors keep the & of the parent
+fn convert_style_rule(style: &StyleRule) -> Result<Rule, Error> {
+    let sub_rules = style
+        .rules
+        .0
+        .iter()
+        .map(|rule| match rule {
+            CssRule::Style(style) => convert_style_rule(style),
+            _ => Err(unsupported("nested at-rules")),
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Rule::new(
+        convert_selector_list(&style.selectors)?,
+        convert_declarations(
+            &style.declarations.declarations,
+            &style.declarations.important_declarations,
+        )?,
+        sub_rules,
+    ))
+}
+
+fn convert_keyframes(rule: &KeyframesRule) -> Result<Keyframes, Error> {
+    if rule.vendor_prefix != VendorPrefix::None {
+        return Err(unsupported("prefixed @keyframes"));
+    }
+    let name = match &rule.name {
+        KeyframesName::Ident(ident) => ident.0.to_string(),
+        KeyframesName::Custom(name) => name.to_string(),
+    };
+
+    let mut keyframes = vec![];
+    for keyframe in &rule.keyframes {
+        let selectors = keyframe
+            .selectors
+            .iter()
+            .map(|selector| match selector {
+                LightningKeyframeSelector::From => Ok(KeyframeSelector::From),
+                LightningKeyframeSelector::To => Ok(KeyframeSelector::To),
+                LightningKeyframeSelector::Percentage(percentage) => {
+                    Ok(KeyframeSelector::percentage(percentage.0 as f64 * 100.0))
+                }
+                _ => Err(unsupported("timeline range keyframes")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        keyframes.push(Keyframe::new(
+            selectors,
+            convert_declarations(
+                &keyframe.declarations.declarations,
+                &keyframe.declarations.important_declarations,
+            )?,
+        ));
+    }
+    Ok(Keyframes::new(name, keyframes))
+}
+
+fn convert_page(rule: &LightningPageRule) -> Result<PageRule, Error> {
+    let selectors = rule
+        .selectors
+        .iter()
+        .map(|selector| {
+            PageSelector::new(
+                selector.name.as_ref().map(|name| name.to_string()),
+                selector
+                    .pseudo_classes
+                    .iter()
+                    .map(|pseudo_class| match pseudo_class {
+                        LightningPagePseudoClass::First => PagePseudoClass::First,
+                        LightningPagePseudoClass::Left => PagePseudoClass::Left,
+                        LightningPagePseudoClass::Right => PagePseudoClass::Right,
+                        LightningPagePseudoClass::Last => PagePseudoClass::Last,
+                        LightningPagePseudoClass::Blank => PagePseudoClass::Blank,
+                    })
+                    .collect(),
+            )
+        })
+        .collect();
+
+    let mut page = PageRule::new(
+        selectors,
+        convert_declarations(
+            &rule.declarations.declarations,
+            &rule.declarations.important_declarations,
+        )?,
+    );
+    for margin_rule in &rule.rules {
+        let name = margin_rule
+            .margin_box
+            .to_css_string(PrinterOptions::default())
+            .map_err(css_error)?;
+        let margin_box = MarginBox::from_name(&name)
+            .ok_or_else(|| unsupported(&format!("margin box {}", name)))?;
+        page = page.margin_box(
+            margin_box,
+            convert_declarations(
+                &margin_rule.declarations.declarations,
+                &margin_rule.declarations.important_declarations,
+            )?,
+        );
+    }
+    Ok(page)
+}
+
+fn convert_declarations(
+    declarations: &[Property],
+    important: &[Property],
+) -> Result<Vec<Declaration>, Error> {
+    if !important.is_empty() {
+        return Err(unsupported("!important declarations"));
+    }
+
+    let mut converted = vec![];
+    for property in declarations {
+        let id = property.property_id();
+        let value = property
+            .value_to_css_string(PrinterOptions::default())
+            .map_err(css_error)?;
+
+        // values with several parts are split into lists, anything else stays as lightning wrote it
+        let value = match value.contains([' ', ',']) {
+            true => stylesheet::value(&value)?,
+            false => DeclarationValue::Basic(value),
+        };
+
+        // a property prefixed for several vendors is held as a single Property
+        let prefixes = match id.prefix().is_empty() {
+            true => VendorPrefix::None,
+            false => id.prefix(),
+        };
+        for prefix in prefixes {
+            let vendor = match prefix {
+                VendorPrefix::WebKit => "-webkit-",
+                VendorPrefix::Moz => "-moz-",
+                VendorPrefix::Ms => "-ms-",
+                VendorPrefix::O => "-o-",
+                _ => "",
+            };
+
+            converted.push(Declaration::new(
+                format!("{}{}", vendor, id.name()),
+                value.clone(),
+            ));
+        }
+    }
+
+    Ok(converted)
+}
+
+fn convert_selector_list(list: &SelectorList) -> Result<Selector, Error> {
+    let mut selectors = list
+        .0
+        .iter()
+        .map(convert_selector)
+        .collect::<Result<Vec<Selector>, Error>>()?;
+
+    match selectors.len() {
+        1 => Ok(selectors.remove(0)),
+        _ => Ok(Selector::Group(selectors)),
+    }
+}
+
+fn compound(items: Vec<Selector>) -> Selector {
+    let mut items = items;
+    match items.len() {
+        1 => items.remove(0),
+        _ => Selector::Chain(items),
+    }
+}
+
+fn close(left: Option<(Selector, Combinator)>, items: Vec<Selector>) -> Selector {
+    match left {
+        Some((base, combinator)) => {
+            Selector::Combinator(Box::new(base), combinator, Box::new(compound(items)))
+        }
+        None => compound(items),
+    }
+}
+
+// the nth type is not exported by lightningcss, so it is recognized by what it allows
+fn nth_kind(allows_of: bool, of_type: bool, from_end: bool) -> Result<NthKind, Error> {
+    match (allows_of, of_type, from_end) {
+        (true, _, false) => Ok(NthKind::Child),
+        (true, _, true) => Ok(NthKind::LastChild),
+        (_, true, false) => Ok(NthKind::OfType),
+        (_, true, true) => Ok(NthKind::LastOfType),
+        _ => Err(unsupported("column selectors")),
+    }
+}
+
+fn convert_selector(selector: &lightningcss::selector::Selector) -> Result<Selector, Error> {
+    let mut left: Option<(Selector, Combinator)> = None;
+    let mut items: Vec<Selector> = vec![];
+
+    // compounds are stored right to left but their own components left to right
+    let mut ordered: Vec<&Component> = vec![];
+    let mut current: Vec<&Component> = vec![];
+    for component in selector.iter_raw_match_order() {
+        if let Component::Combinator(_) = component {
+            ordered.splice(0..0, current.drain(..));
+            ordered.insert(0, component);
+        } else {
+            current.push(component);
+        }
+    }
+    ordered.splice(0..0, current);
+
+    for component in ordered {
+        match component {
+            Component::Combinator(combinator) => {
+                let combinator = match combinator {
+                    LightningCombinator::Descendant => Combinator::Descendant,
+                    LightningCombinator::Child => Combinator::Child,
+                    LightningCombinator::NextSibling => Combinator::AdjacentSibling,
+                    LightningCombinator::LaterSibling => Combinator::GeneralSibling,
+                    // marks the start of a pseudo element within the same compound
+                    LightningCombinator::PseudoElement => continue,
+                    _ => return Err(unsupported("shadow dom combinators")),
+                };
+
+                let base = close(left.take(), core::mem::take(&mut items));
+                left = Some((base, combinator));
+            }
+            Component::ExplicitUniversalType => items.push(Selector::Universal),
+            Component::Nesting => items.push(Selector::Parent),
+            Component::LocalName(name) => items.push(Selector::Tag(name.name.0.to_string())),
+            Component::ID(id) => items.push(Selector::Id(id.0.to_string())),
+            Component::Class(class) => items.push(Selector::Class(class.0.to_string())),
+            Component::AttributeInNoNamespaceExists { local_name, .. } => {
+                items.push(Selector::Attribute(local_name.0.to_string()))
+            }
+            Component::AttributeInNoNamespace {
+                local_name,
+                operator,
+                value,
+                ..
+            } => {
+                let name = local_name.0.to_string();
+                let value = value.0.to_string();
+                // the case sensitivity type is not exported, the flag is read from the output
+                let css = component.to_css_string();
+                let case = match css.trim_end_matches(']') {
+                    css if css.ends_with(" i") => AttributeCase::Insensitive,
+                    css if css.ends_with(" s") => AttributeCase::Sensitive,
+                    _ => AttributeCase::Default,
+                };
+                match operator.to_css_string().as_str() {
+                    "=" => items.push(Selector::AttributeValue(name, value, case)),
+                    "~=" => items.push(Selector::AttributeContains(name, value, case)),
+                    "^=" => items.push(Selector::AttributePrefix(name, value, case)),
+                    "$=" => items.push(Selector::AttributeSuffix(name, value, case)),
+                    "*=" => items.push(Selector::AttributeSubstring(name, value, case)),
+                    "|=" => items.push(Selector::AttributeDash(name, value, case)),
+                    other => return Err(unsupported(&format!("attribute operator `{}`", other))),
+                }
+            }
+            Component::Root => {
+                let base = compound(core::mem::take(&mut items));
+                items.push(Selector::PseudoClass(Box::new(base), "root".to_string()));
+            }
+            Component::Empty => {
+                let base = compound(core::mem::take(&mut items));
+                items.push(Selector::PseudoClass(Box::new(base), "empty".to_string()));
+            }
+            Component::Negation(list) | Component::Is(list) | Component::Where(list) => {
+                let function = match component {
+                    Component::Negation(_) => PseudoFunction::Not,
+                    Component::Is(_) => PseudoFunction::Is,
+                    _ => PseudoFunction::Where,
+                };
+                let mut arguments = list
+                    .iter()
+                    .map(convert_selector)
+                    .collect::<Result<Vec<Selector>, Error>>()?;
+                let argument = match arguments.len() {
+                    1 => arguments.remove(0),
+                    _ => Selector::Group(arguments),
+                };
+                let base = compound(core::mem::take(&mut items));
+                items.push(Selector::Functional(
+                    Box::new(base),
+                    function,
+                    Box::new(argument),
+                ));
+            }
+            Component::Nth(data) if data.is_function => {
+                let ty = data.ty;
+                let base = compound(core::mem::take(&mut items));
+                items.push(Selector::Nth {
+                    base: Box::new(base),
+                    kind: nth_kind(ty.allows_of_selector(), ty.is_of_type(), ty.is_from_end())?,
+                    a: data.a,
+                    b: data.b,
+                    of: None,
+                });
+            }
+            Component::NthOf(nth_of) => {
+                let base = compound(core::mem::take(&mut items));
+                let mut selectors = nth_of
+                    .selectors()
+                    .iter()
+                    .map(convert_selector)
+                    .collect::<Result<Vec<Selector>, Error>>()?;
+                let of = match selectors.len() {
+                    1 => selectors.remove(0),
+                    _ => Selector::Group(selectors),
+                };
+                let (data, ty) = (nth_of.nth_data(), nth_of.nth_data().ty);
+                items.push(Selector::Nth {
+                    base: Box::new(base),
+                    kind: nth_kind(ty.allows_of_selector(), ty.is_of_type(), ty.is_from_end())?,
+                    a: data.a,
+                    b: data.b,
+                    of: Some(Box::new(of)),
+                });
+            }
+            Component::NonTSPseudoClass(class) => {
+                let name = class.to_css_string();
+                let base = compound(core::mem::take(&mut items));
+                items.push(Selector::PseudoClass(
+                    Box::new(base),
+                    name.trim_start_matches(':').to_string(),
+                ));
+            }
+            Component::PseudoElement(element) => {
+                let name = element.to_css_string();
+                let base = compound(core::mem::take(&mut items));
+                items.push(Selector::PseudoElement(
+                    Box::new(base),
+                    name.trim_start_matches(':').to_string(),
+                ));
+            }
+            _ => {
+                return Err(unsupported(&format!(
+                    "selector `{}`",
+                    ToCss::to_css_string(selector, PrinterOptions::default()).unwrap_or_default()
+                )))
+            }
+        }
+    }
+
+    Ok(close(left, items))
+}
+
+fn convert_media_list(list: &MediaList) -> Result<MediaQuery, Error> {
+    let query = match list.media_queries.as_slice() {
+        [query] => query,
+        _ => return Err(unsupported("media query lists")),
+    };
+
+    let constraint = match query.qualifier {
+        Some(Qualifier::Only) => MediaConstraint::Only,
+        Some(Qualifier::Not) => MediaConstraint::Not,
+        None => MediaConstraint::None,
+    };
+
+    let media_type = match &query.media_type {
+        // lightningcss reads a query with only a condition as all
+        MediaType::All if query.qualifier.is_none() && query.condition.is_some() => None,
+        MediaType::All => Some("all".to_string()),
+        MediaType::Print => Some("print".to_string()),
+        MediaType::Screen => Some("screen".to_string()),
+        MediaType::Custom(name) => Some(name.to_string()),
+    };
+
+    let features = match &query.condition {
+        None => vec![],
+        Some(condition) => match convert_media_condition(condition)? {
+            MediaCondition::And(conditions) => conditions,
+            condition => vec![condition],
+        },
+    };
+
+    Ok(match media_type {
+        Some(media_type) => MediaQuery::new(constraint, media_type, features),
+        None => MediaQuery::without_type(constraint, features),
+    })
+}
+
+fn convert_media_condition(condition: &LightningCondition) -> Result<MediaCondition, Error> {
+    Ok(match condition {
+        LightningCondition::Feature(_) => MediaCondition::Lone(convert_media_feature(condition)?),
+        LightningCondition::Not(condition) => {
+            MediaCondition::negate(convert_media_condition(condition)?)
+        }
+        LightningCondition::Operation {
+            operator,
+            conditions,
+        } => {
+            let conditions = conditions
+                .iter()
+                .map(convert_media_condition)
+                .collect::<Result<_, _>>()?;
+            match operator {
+                Operator::And => MediaCondition::And(conditions),
+                Operator::Or => MediaCondition::Or(conditions),
+            }
+        }
+        LightningCondition::Unknown(_) => return Err(unsupported("unknown media conditions")),
+    })
+}
+
+fn convert_media_feature(condition: &LightningCondition) -> Result<MediaFeature, Error> {
+    let (name, prefix, value) = match condition {
+        LightningCondition::Feature(QueryFeature::Plain { name, value }) => (name, "", value),
+        // range syntax is how lightningcss holds min- and max- features
+        LightningCondition::Feature(QueryFeature::Range {
+            name,
+            operator,
+            value,
+        }) => match operator {
+            MediaFeatureComparison::Equal => (name, "", value),
+            MediaFeatureComparison::GreaterThanEqual => (name, "min-", value),
+            MediaFeatureComparison::LessThanEqual => (name, "max-", value),
+            _ => return Err(unsupported("exclusive media ranges")),
+        },
+        LightningCondition::Feature(_) => {
+            return Err(unsupported("boolean and interval media features"))
+        }
+        _ => return Err(unsupported("nested media conditions")),
+    };
+
+    Ok(MediaFeature::new(
+        format!(
+            "{}{}",
+            prefix,
+            name.to_css_string(PrinterOptions::default())
+                .map_err(css_error)?
+        ),
+        value
+            .to_css_string(PrinterOptions::default())
+            .map_err(css_error)?,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use lightningcss::targets::{Browsers, Targets};
+
+    use crate::css::{Declaration, DeclarationValue, Rule, RuleSet, Selector};
+    use crate::lightning::{
+        minify_css, optimize_rule_set, rule_set_from_stylesheet, stylesheet_from_css,
+    };
+
+    fn rule_set(property: &str, value: &str) -> RuleSet {
+        RuleSet::new(
+            vec![Rule::new(
+                Selector::Class("card".to_string()),
+                vec![Declaration::new(
+                    property.to_string(),
+                    DeclarationValue::Basic(value.to_string()),
+                )],
+                vec![],
+            )],
+            vec![],
+            None,
+        )
+    }
+
+    #[test]
+    fn minify() {
+        let set = rule_set("color", "#ff0000");
+
+        assert_eq!(
+            minify_css(&set, Targets::default()).unwrap(),
+            ".card{color:red}"
+        );
+    }
+
+    #[test]
+    fn prefix_for_targets() {
+        let targets = Targets::from(Browsers {
+            safari: Some(12 << 16),
+            ..Browsers::default()
+        });
+        let set = optimize_rule_set(&rule_set("user-select", "none"), targets).unwrap();
+
+        assert_eq!(
+            set.to_string(),
+            ".card{-webkit-user-select:none;user-select:none;}"
+        );
+    }
+
+    #[test]
+    fn convert_stylesheet() {
+        let sheet = stylesheet_from_css(
+            "body > .card:hover, a[href] p::before { color: red } @media screen and (min-width: 600px) { #main { margin: 0 } }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            rule_set_from_stylesheet(&sheet).unwrap().to_string(),
+            "body>.card:hover,a[href] p::before{color:red;}@media screen and (min-width:600px){#main{margin:0;}}"
+        );
+    }
+
+    #[test]
+    fn convert_selector_arguments() {
+        let sheet = stylesheet_from_css(
+            "li:not(.a, .b) :is(h1, h2) { color: red } tr:nth-child(odd of .x) td:nth-last-of-type(2) { color: red } input[type=email i] { color: red }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            rule_set_from_stylesheet(&sheet).unwrap().to_string(),
+            "li:not(.a,.b) :is(h1,h2){color:red;}tr:nth-child(2n+1 of .x) td:nth-last-of-type(2){color:red;}input[type=\"email\" i]{color:red;}"
+        );
+    }
+
+    #[test]
+    fn keyframes() {
+        let sheet = stylesheet_from_css(
+            "@keyframes spin { from { rotate: 0deg } 50%, to { rotate: 1turn } }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            rule_set_from_stylesheet(&sheet).unwrap().to_string(),
+            "@keyframes spin{from{rotate:0deg;}50%,to{rotate:1turn;}}"
+        );
+    }
+
+    #[test]
+    fn layers() {
+        let sheet = stylesheet_from_css(
+            "@layer reset, ui.base; @layer ui.base { a { color: red } } @layer { p { margin: 0 } }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            rule_set_from_stylesheet(&sheet).unwrap().to_string(),
+            "@layer reset,ui.base;@layer ui.base{a{color:red;}}@layer{p{margin:0;}}"
+        );
+    }
+
+    #[test]
+    fn nested_media_conditions() {
+        let sheet = stylesheet_from_css(
+            "@media screen and (min-width: 40em) and ((hover: hover) or (not (pointer: coarse))) { a { color: red } }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            rule_set_from_stylesheet(&sheet).unwrap().to_string(),
+            "@media screen and (min-width:40em) and ((hover:hover) or (not (pointer:coarse))){a{color:red;}}"
+        );
+    }
+
+    #[test]
+    fn media_queries_without_type() {
+        let sheet = stylesheet_from_css(
+            "@media (max-width: 600px) { a { color: red } } @media not (hover: hover) { b { color: blue } }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            rule_set_from_stylesheet(&sheet).unwrap().to_string(),
+            "@media (max-width:600px){a{color:red;}}@media not (hover:hover){b{color:#00f;}}"
+        );
+    }
+
+    #[test]
+    fn nested_rules() {
+        let sheet = stylesheet_from_css(
+            ".tab { color: gray; &.active { color: black } & + & { margin: 0 } .dark & { color: white } }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            rule_set_from_stylesheet(&sheet).unwrap().to_string(),
+            ".tab{color:gray;}.tab.active{color:#000;}.tab+.tab{margin:0;}.dark .tab{color:#fff;}"
+        );
+    }
+
+    #[test]
+    fn pages() {
+        let sheet = stylesheet_from_css(
+            "@page :first, toc:left { margin: 1in; @bottom-center { content: counter(page) } }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            rule_set_from_stylesheet(&sheet).unwrap().to_string(),
+            "@page :first,toc:left{margin:1in;@bottom-center{content:counter(page);}}"
+        );
+    }
+
+    #[test]
+    fn generic_at_rules() {
+        let sheet = stylesheet_from_css(
+            "@property --angle { syntax: '<angle>'; inherits: false; initial-value: 0deg } @unknown-rule foo { bar: baz }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            rule_set_from_stylesheet(&sheet).unwrap().to_string(),
+            "@property --angle{syntax:\"<angle>\";inherits:false;initial-value:0deg;}@unknown-rule foo{bar:baz;}"
+        );
+    }
+
+    #[test]
+    fn multi_part_values() {
+        let sheet =
+            stylesheet_from_css(".card { margin: 0 auto; font-family: Fira Sans, serif }").unwrap();
+
+        assert_eq!(
+            rule_set_from_stylesheet(&sheet).unwrap().to_string(),
+            ".card{margin:0 auto;font-family:Fira Sans,serif;}"
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use alloc::string::String;
+use pulldown_cmark::{html, Options, Parser};
+
+use crate::html::Node;
+
+// converting to find the exact length would mean converting twice, html is usually a bit longer
+pub(crate) fn estimated_html_len(text: &str) -> usize {
+    text.len() * 3 / 2
+}
+
+// tables, strikethrough and task lists on top of plain commonmark
+pub(crate) fn markdown_to_html(text: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut output = String::with_capacity(estimated_html_len(text));
+    html::push_html(&mut output, Parser::new_ext(text, options));
+    output
+}
+
+impl Node {
+    // html in the markdown is kept as is, sanitize the node when the text is not trusted
+    pub fn markdown(text: String) -> Self {
+        Self::Markdown(text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::html::Node;
+    use crate::sanitize::Sanitizer;
+
+    #[test]
+    fn rendered_as_html() {
+        let node = Node::element(
+            "article".to_string(),
+            vec![],
+            vec![Node::markdown(
+                "# Title\n\nSome *text* & [a link](/x).".to_string(),
+            )],
+        );
+        let html = node.to_string();
+
+        assert_eq!(
+            html,
+            "<article><h1>Title</h1>\n<p>Some <em>text</em> &amp; <a href=\"/x\">a link</a>.</p>\n</article>"
+        );
+    }
+
+    #[test]
+    fn sanitized() {
+        let node = Node::markdown("hi <img src=x onerror=alert(1)>".to_string());
+
+        assert_eq!(
+            Sanitizer::default().sanitize(&node).to_string(),
+            "<p>hi <img src=\"x\"></p>"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use alloc::string::String;
+#[cfg(feature = "scraper")]
+use alloc::vec::Vec;
+use maud::{Markup, Render};
+
+use crate::html::Node;
+
+// kept as rendered, for splicing maud output into a tree without parsing it
+impl From<Markup> for Node {
+    fn from(markup: Markup) -> Self {
+        Node::Raw(markup.into_string())
+    }
+}
+
+impl Render for Node {
+    fn render_to(&self, buffer: &mut String) {
+        buffer.reserve(self.estimated_len());
+        // writing to a String can not fail
+        let _ = self.render(buffer);
+    }
+}
+
+// top level nodes of the markup, parsed the same way as dom::nodes_from_fragment
+#[cfg(feature = "scraper")]
+pub fn nodes_from_markup(markup: &Markup) -> Vec<Node> {
+    crate::dom::nodes_from_fragment(&scraper::Html::parse_fragment(&markup.0))
+}
+
+#[cfg(test)]
+mod test {
+    use maud::html;
+
+    use crate::html::{Attribute, Node};
+
+    #[test]
+    fn render_inside_template() {
+        let card = Node::element(
+            "div".to_string(),
+            vec![Attribute::new("class".to_string(), "card".to_string())],
+            vec![Node::text("Some text".to_string())],
+        );
+
+        let page = html! {
+            main { (card) }
+        };
+
+        assert_eq!(
+            page.into_string(),
+            "<main><div class=\"card\">Some text</div></main>"
+        );
+    }
+
+    #[test]
+    fn markup_into_raw() {
+        let node = Node::element(
+            "section".to_string(),
+            vec![],
+            vec![html! { em { "a & b" } }.into()],
+        );
+
+        assert_eq!(node.to_string(), "<section><em>a &amp; b</em></section>");
+    }
+
+    #[cfg(feature = "scraper")]
+    #[test]
+    fn nodes_from_markup() {
+        let markup = html! {
+            p.intro { "Hello" }
+            hr;
+        };
+
+        let nodes = crate::markup::nodes_from_markup(&markup)
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(nodes, vec!["<p class=\"intro\">Hello</p>", "<hr>"]);
+    }
+}
@@ -0,0 +1,163 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write;
+use core::slice;
+
+use crate::html::Node;
+
+// whitespace inside these is part of the content
+const PRESERVED_TAGS: &[&str] = &["pre", "script", "style", "textarea"];
+
+// elements whose whitespace only children are not rendered by browsers
+#[rustfmt::skip]
+const BLOCK_TAGS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "body", "colgroup", "datalist", "details",
+    "dialog", "div", "dl", "fieldset", "figure", "footer", "form", "head", "header", "hgroup",
+    "html", "main", "menu", "nav", "ol", "optgroup", "picture", "section", "select", "table",
+    "tbody", "tfoot", "thead", "tr", "ul",
+];
+
+fn is_one_of(tag: &str, tags: &[&str]) -> bool {
+    tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut in_whitespace = false;
+    for c in text.chars() {
+        match c.is_ascii_whitespace() {
+            true if in_whitespace => (),
+            true => output.push(' '),
+            false => output.push(c),
+        }
+        in_whitespace = c.is_ascii_whitespace();
+    }
+    output
+}
+
+// comments are dropped, fragments are flattened and neighbouring text is joined
+fn flatten(children: &[Node], output: &mut Vec<Node>) {
+    for child in children {
+        match (child, output.last_mut()) {
+            (Node::Comment(_), _) => (),
+            (Node::Fragment(children), _) => flatten(children, output),
+            (Node::Text(text), Some(Node::Text(previous))) => previous.push_str(text),
+            (child, _) => output.push(child.clone()),
+        }
+    }
+}
+
+fn minify_children(children: &[Node], preserve: bool, block: bool) -> Vec<Node> {
+    let mut flattened = Vec::new();
+    flatten(children, &mut flattened);
+
+    flattened
+        .into_iter()
+        .filter_map(|child| match child {
+            Node::Text(text) if preserve => Some(Node::Text(text)),
+            Node::Text(text) => {
+                let text = collapse_whitespace(&text);
+                match text.is_empty() || (block && text == " ") {
+                    true => None,
+                    false => Some(Node::Text(text)),
+                }
+            }
+            Node::Element {
+                tag,
+                attributes,
+                children,
+                self_closing,
+            } => {
+                let preserve = preserve || is_one_of(&tag, PRESERVED_TAGS);
+                let block = is_one_of(&tag, BLOCK_TAGS);
+                Some(Node::Element {
+                    children: minify_children(&children, preserve, block),
+                    tag,
+                    attributes,
+                    self_closing,
+                })
+            }
+            child => Some(child),
+        })
+        .collect()
+}
+
+impl Node {
+    // comments are dropped and whitespace is collapsed, except inside pre, script, style and textarea
+    pub fn minify(&self) -> Node {
+        let mut nodes = minify_children(slice::from_ref(self), false, true);
+        match nodes.len() {
+            1 => nodes.remove(0),
+            _ => Node::Fragment(nodes),
+        }
+    }
+
+    // minified and written with attribute quotes left out where the value allows it
+    pub fn write_minified<W: Write>(&self, output: &mut W) -> fmt::Result {
+        self.minify().render_minified(output)
+    }
+
+    pub fn to_minified_string(&self) -> String {
+        let mut output = String::new();
+        // writing to a string does not fail
+        let _ = self.write_minified(&mut output);
+        output
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::html::Node;
+
+    #[test]
+    fn collapses_whitespace_and_drops_comments() {
+        let node = crate::html! {
+            ul {
+                "\n  "
+                li { "  one\n   two " }
+                (Node::comment("item".to_string()))
+                "\n  "
+                li {
+                    b { "a" }
+                    (crate::html! { " " "\t" })
+                    i { "b" }
+                }
+            }
+        };
+
+        assert_eq!(
+            node.to_minified_string(),
+            "<ul><li> one two </li><li><b>a</b> <i>b</i></li></ul>"
+        );
+    }
+
+    #[test]
+    fn preserved_content() {
+        let node = crate::html! {
+            div {
+                pre { code { "a\n    b" } }
+                script { "if (a  <  b) {\n}" }
+            }
+        };
+
+        assert_eq!(
+            node.to_minified_string(),
+            "<div><pre><code>a\n    b</code></pre><script>if (a  <  b) {\n}</script></div>"
+        );
+    }
+
+    #[test]
+    fn unquoted_attributes() {
+        let node = crate::html! {
+            a href="/docs/intro" class="button primary" title="" data-query="a&b" download {
+                svg viewBox="0" {}
+            }
+        };
+
+        assert_eq!(
+            node.to_minified_string(),
+            "<a href=/docs/intro class=\"button primary\" title=\"\" data-query=a&amp;b download><svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0\"/></a>"
+        );
+    }
+}
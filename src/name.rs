@@ -0,0 +1,227 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::fmt;
+use core::ops::Deref;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Sorted list of common HTML tag, HTML attribute and CSS property names.
+// Names found here are stored as static references instead of heap allocated strings.
+const KNOWN_NAMES: &[&str] = &[
+    "a", "abbr", "accept", "accept-charset", "accesskey", "action", "address", "align-content",
+    "align-items", "align-self", "all", "allow", "alt", "animation", "animation-delay",
+    "animation-direction", "animation-duration", "animation-fill-mode",
+    "animation-iteration-count", "animation-name", "animation-play-state",
+    "animation-timing-function", "appearance", "area", "aria-controls", "aria-current",
+    "aria-describedby", "aria-expanded", "aria-hidden", "aria-label", "aria-labelledby", "article",
+    "aside", "aspect-ratio", "async", "audio", "autocapitalize", "autocomplete", "autofocus",
+    "autoplay", "b", "backdrop-filter", "backface-visibility", "background",
+    "background-attachment", "background-blend-mode", "background-clip", "background-color",
+    "background-image", "background-origin", "background-position", "background-repeat",
+    "background-size", "base", "bdi", "bdo", "blockquote", "body", "border", "border-bottom",
+    "border-bottom-color", "border-bottom-left-radius", "border-bottom-right-radius",
+    "border-bottom-style", "border-bottom-width", "border-collapse", "border-color",
+    "border-image", "border-left", "border-left-color", "border-left-style", "border-left-width",
+    "border-radius", "border-right", "border-right-color", "border-right-style",
+    "border-right-width", "border-spacing", "border-style", "border-top", "border-top-color",
+    "border-top-left-radius", "border-top-right-radius", "border-top-style", "border-top-width",
+    "border-width", "bottom", "box-shadow", "box-sizing", "br", "button", "canvas", "caption",
+    "caption-side", "charset", "checked", "cite", "class", "clear", "clip", "clip-path", "code",
+    "col", "colgroup", "color", "cols", "colspan", "column-count", "column-gap", "columns",
+    "container", "container-name", "container-type", "content", "contenteditable", "controls",
+    "coords", "counter-increment", "counter-reset", "crossorigin", "cursor", "data", "datalist",
+    "datetime", "dd", "decoding", "default", "defer", "del", "details", "dfn", "dialog", "dir",
+    "direction", "dirname", "disabled", "display", "div", "dl", "download", "draggable", "dt",
+    "em", "embed", "empty-cells", "enctype", "enterkeyhint", "fieldset", "figcaption", "figure",
+    "fill", "filter", "flex", "flex-basis", "flex-direction", "flex-flow", "flex-grow",
+    "flex-shrink", "flex-wrap", "float", "font", "font-display", "font-family",
+    "font-feature-settings", "font-size", "font-stretch", "font-style", "font-variant",
+    "font-weight", "footer", "for", "form", "formaction", "formenctype", "formmethod",
+    "formnovalidate", "formtarget", "gap", "grid", "grid-area", "grid-auto-columns",
+    "grid-auto-flow", "grid-auto-rows", "grid-column", "grid-column-end", "grid-column-start",
+    "grid-row", "grid-row-end", "grid-row-start", "grid-template", "grid-template-areas",
+    "grid-template-columns", "grid-template-rows", "h1", "h2", "h3", "h4", "h5", "h6", "head",
+    "header", "headers", "height", "hgroup", "hidden", "high", "hr", "href", "hreflang", "html",
+    "http-equiv", "hyphens", "i", "id", "iframe", "img", "inert", "input", "inputmode", "ins",
+    "inset", "integrity", "is", "ismap", "isolation", "itemid", "itemprop", "itemref", "itemscope",
+    "itemtype", "justify-content", "justify-items", "justify-self", "kbd", "kind", "label", "lang",
+    "left", "legend", "letter-spacing", "li", "line-height", "link", "list", "list-style",
+    "list-style-image", "list-style-position", "list-style-type", "loading", "loop", "low", "main",
+    "map", "margin", "margin-bottom", "margin-left", "margin-right", "margin-top", "mark", "mask",
+    "max", "max-height", "max-width", "maxlength", "media", "menu", "meta", "meter", "method",
+    "min", "min-height", "min-width", "minlength", "mix-blend-mode", "multiple", "muted", "name",
+    "nav", "nomodule", "nonce", "noscript", "novalidate", "object", "object-fit",
+    "object-position", "ol", "onchange", "onclick", "oninput", "onload", "onsubmit", "opacity",
+    "open", "optgroup", "optimum", "option", "order", "outline", "outline-color", "outline-offset",
+    "outline-style", "outline-width", "output", "overflow", "overflow-wrap", "overflow-x",
+    "overflow-y", "p", "padding", "padding-bottom", "padding-left", "padding-right", "padding-top",
+    "page-break-after", "page-break-before", "pattern", "perspective", "picture", "ping",
+    "place-content", "place-items", "place-self", "placeholder", "playsinline", "pointer-events",
+    "popover", "position", "poster", "pre", "preload", "progress", "q", "quotes", "readonly",
+    "referrerpolicy", "rel", "required", "resize", "reversed", "right", "role", "rotate",
+    "row-gap", "rows", "rowspan", "rp", "rt", "ruby", "s", "samp", "sandbox", "scale", "scope",
+    "script", "scroll-behavior", "scroll-margin", "scroll-padding", "search", "section", "select",
+    "selected", "shape", "size", "sizes", "slot", "small", "source", "span", "spellcheck", "src",
+    "srcdoc", "srclang", "srcset", "start", "step", "stroke", "stroke-width", "strong", "style",
+    "sub", "summary", "sup", "svg", "tab-size", "tabindex", "table", "table-layout", "target",
+    "tbody", "td", "template", "text-align", "text-decoration", "text-decoration-color",
+    "text-decoration-line", "text-decoration-style", "text-indent", "text-overflow", "text-shadow",
+    "text-transform", "textarea", "tfoot", "th", "thead", "time", "title", "top", "tr", "track",
+    "transform", "transform-origin", "transition", "transition-delay", "transition-duration",
+    "transition-property", "transition-timing-function", "translate", "type", "u", "ul",
+    "unicode-bidi", "usemap", "user-select", "value", "var", "vertical-align", "video",
+    "visibility", "wbr", "white-space", "width", "will-change", "word-break", "word-spacing",
+    "word-wrap", "wrap", "writing-mode", "z-index",
+];
+
+fn known_name(name: &str) -> Option<&'static str> {
+    KNOWN_NAMES
+        .binary_search(&name)
+        .ok()
+        .map(|index| KNOWN_NAMES[index])
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Name(Cow<'static, str>);
+
+impl Name {
+    pub fn new(name: String) -> Self {
+        match known_name(&name) {
+            Some(known) => Self(Cow::Borrowed(known)),
+            None => Self(Cow::Owned(name)),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_known(&self) -> bool {
+        matches!(self.0, Cow::Borrowed(_))
+    }
+}
+
+impl From<String> for Name {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<&str> for Name {
+    fn from(name: &str) -> Self {
+        match known_name(name) {
+            Some(known) => Self(Cow::Borrowed(known)),
+            None => Self(Cow::Owned(String::from(name))),
+        }
+    }
+}
+
+impl Deref for Name {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Name {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Name {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Name {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Name {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(Name::new)
+    }
+}
+
+impl Serialize for Name {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+// known names are picked half the time so generated trees look like real markup
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Name {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        match bool::arbitrary(u)? {
+            true => u.choose(KNOWN_NAMES).map(|name| Self(Cow::Borrowed(name))),
+            false => String::arbitrary(u).map(Name::new),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::name::{Name, KNOWN_NAMES};
+
+    #[test]
+    fn known_names_are_sorted_and_unique() {
+        assert!(KNOWN_NAMES.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn known_name_is_borrowed() {
+        let name = Name::new("div".to_string());
+
+        assert!(name.is_known());
+        assert_eq!(name, "div");
+    }
+
+    #[test]
+    fn custom_name_is_owned() {
+        let name = Name::from("my-element");
+
+        assert!(!name.is_known());
+        assert_eq!(name, "my-element");
+    }
+
+    #[test]
+    fn known_and_custom_compare_by_value() {
+        assert_eq!(Name::from("color"), Name::new("color".to_string()));
+        assert_ne!(Name::from("color"), Name::from("colour"));
+    }
+
+    #[test]
+    fn equal_names_hash_equal() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let hash = |name: &Name| {
+            let mut hasher = DefaultHasher::new();
+            name.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        assert_eq!(hash(&Name::from("div")), hash(&Name::new("div".to_string())));
+        assert_eq!(
+            hash(&Name::from("my-element")),
+            hash(&Name::new("my-element".to_string()))
+        );
+    }
+}
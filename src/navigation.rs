@@ -0,0 +1,243 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::builder::Element;
+use crate::html::Node;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct NavLink {
+    label: String,
+    href: String,
+}
+
+impl NavLink {
+    pub fn new(label: String, href: String) -> Self {
+        Self { label, href }
+    }
+}
+
+fn anchor(label: &str, href: &str) -> Element {
+    Element::new("a").attr("href", href).text(label)
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PageItem {
+    Page(usize),
+    Gap,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Pagination {
+    current: usize,
+    total: usize,
+    window: usize,
+}
+
+impl Pagination {
+    // pages are numbered from 1, window is how many pages to show on each side of the current page
+    pub fn new(current: usize, total: usize, window: usize) -> Self {
+        Self {
+            current: current.clamp(1, total.max(1)),
+            total,
+            window,
+        }
+    }
+
+    // first and last pages are always included, skipped ranges are represented by a single Gap
+    pub fn page_window(&self) -> Vec<PageItem> {
+        if self.total == 0 {
+            return vec![];
+        }
+
+        let start = self.current.saturating_sub(self.window).max(1);
+        let end = (self.current + self.window).min(self.total);
+        let mut items = vec![];
+
+        if start > 1 {
+            items.push(PageItem::Page(1));
+            if start > 2 {
+                items.push(PageItem::Gap);
+            }
+        }
+
+        items.extend((start..=end).map(PageItem::Page));
+
+        if end < self.total {
+            if end < self.total - 1 {
+                items.push(PageItem::Gap);
+            }
+            items.push(PageItem::Page(self.total));
+        }
+
+        items
+    }
+
+    pub fn to_node<F: Fn(usize) -> String>(&self, href: F) -> Node {
+        let mut items = vec![];
+
+        items.push(self.step_item("Previous", "prev", self.current - 1, &href));
+
+        for item in self.page_window() {
+            let child = match item {
+                PageItem::Gap => Element::new("span").text("…"),
+                PageItem::Page(page) if page == self.current => {
+                    anchor(&page.to_string(), &href(page)).attr("aria-current", "page")
+                }
+                PageItem::Page(page) => anchor(&page.to_string(), &href(page)),
+            };
+            items.push(Element::new("li").child(child));
+        }
+
+        items.push(self.step_item("Next", "next", self.current + 1, &href));
+
+        Element::new("nav")
+            .attr("aria-label", "Pagination")
+            .child(Element::new("ul").class("pagination").children(items))
+            .build()
+    }
+
+    fn step_item<F: Fn(usize) -> String>(
+        &self,
+        label: &str,
+        rel: &str,
+        page: usize,
+        href: &F,
+    ) -> Element {
+        let child = match page >= 1 && page <= self.total {
+            true => anchor(label, &href(page)).attr("rel", rel),
+            false => Element::new("span")
+                .attr("aria-disabled", "true")
+                .text(label),
+        };
+
+        Element::new("li").child(child)
+    }
+}
+
+// last link is treated as the current page
+pub fn breadcrumbs(links: &[NavLink]) -> Node {
+    let items = links.iter().enumerate().map(|(i, link)| {
+        let anchor = anchor(&link.label, &link.href);
+        let anchor = match i + 1 == links.len() {
+            true => anchor.attr("aria-current", "page"),
+            false => anchor,
+        };
+        Element::new("li").child(anchor)
+    });
+
+    Element::new("nav")
+        .attr("aria-label", "Breadcrumb")
+        .child(Element::new("ol").children(items))
+        .build()
+}
+
+// links whose href matches the current path get an active class and aria-current
+pub fn navigation_menu(links: &[NavLink], current_path: &str, active_class: &str) -> Node {
+    let items = links.iter().map(|link| {
+        let anchor = anchor(&link.label, &link.href);
+        let anchor = match link.href == current_path {
+            true => anchor.class(active_class).attr("aria-current", "page"),
+            false => anchor,
+        };
+        Element::new("li").child(anchor)
+    });
+
+    Element::new("nav")
+        .child(Element::new("ul").children(items))
+        .build()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::navigation::{breadcrumbs, navigation_menu, NavLink, PageItem, Pagination};
+
+    #[test]
+    fn page_window_middle() {
+        let pagination = Pagination::new(5, 10, 1);
+
+        assert_eq!(
+            pagination.page_window(),
+            vec![
+                PageItem::Page(1),
+                PageItem::Gap,
+                PageItem::Page(4),
+                PageItem::Page(5),
+                PageItem::Page(6),
+                PageItem::Gap,
+                PageItem::Page(10)
+            ]
+        );
+    }
+
+    #[test]
+    fn page_window_no_gap_for_single_skipped_edge() {
+        let pagination = Pagination::new(3, 4, 1);
+
+        assert_eq!(
+            pagination.page_window(),
+            vec![
+                PageItem::Page(1),
+                PageItem::Page(2),
+                PageItem::Page(3),
+                PageItem::Page(4)
+            ]
+        );
+    }
+
+    #[test]
+    fn page_window_empty() {
+        assert_eq!(Pagination::new(1, 0, 2).page_window(), vec![]);
+    }
+
+    #[test]
+    fn pagination_node() {
+        let node = Pagination::new(1, 2, 1).to_node(|page| format!("/page/{}", page));
+
+        assert_eq!(
+            node.to_string(),
+            "<nav aria-label=\"Pagination\"><ul class=\"pagination\">\
+            <li><span aria-disabled=\"true\">Previous</span></li>\
+            <li><a href=\"/page/1\" aria-current=\"page\">1</a></li>\
+            <li><a href=\"/page/2\">2</a></li>\
+            <li><a href=\"/page/2\" rel=\"next\">Next</a></li>\
+            </ul></nav>"
+        );
+    }
+
+    #[test]
+    fn breadcrumb_node() {
+        let node = breadcrumbs(&[
+            NavLink::new("Home".to_string(), "/".to_string()),
+            NavLink::new("Docs".to_string(), "/docs".to_string()),
+        ]);
+
+        assert_eq!(
+            node.to_string(),
+            "<nav aria-label=\"Breadcrumb\"><ol>\
+            <li><a href=\"/\">Home</a></li>\
+            <li><a href=\"/docs\" aria-current=\"page\">Docs</a></li>\
+            </ol></nav>"
+        );
+    }
+
+    #[test]
+    fn navigation_menu_node() {
+        let node = navigation_menu(
+            &[
+                NavLink::new("Home".to_string(), "/".to_string()),
+                NavLink::new("Blog".to_string(), "/blog".to_string()),
+            ],
+            "/blog",
+            "active",
+        );
+
+        assert_eq!(
+            node.to_string(),
+            "<nav><ul>\
+            <li><a href=\"/\">Home</a></li>\
+            <li><a href=\"/blog\" class=\"active\" aria-current=\"page\">Blog</a></li>\
+            </ul></nav>"
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::at_rule::{AtRule, AtRuleBlock, Keyframe, Keyframes};
+use crate::css::{Declaration, Rule, RuleSet};
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum DeclarationOrder {
+    #[default]
+    Written,
+    // by shorthand family ignoring vendor prefixes, e.g. margin-top and margin are one family
+    Alphabetical,
+    // positioning, box model, typography, visuals then motion
+    Grouped,
+}
+
+// property families in the order of DeclarationOrder::Grouped, matched by prefix
+#[rustfmt::skip]
+const GROUPS: &[&str] = &[
+    "position", "inset", "top", "right", "bottom", "left", "z-index",
+    "display", "flex", "grid", "place", "align", "justify", "order", "gap", "row-gap",
+    "column-gap", "float", "clear", "box-sizing", "width", "min-width", "max-width", "height",
+    "min-height", "max-height", "aspect-ratio", "margin", "padding", "overflow",
+    "font", "line-height", "letter-spacing", "word", "text", "white-space", "color",
+    "background", "border", "outline", "box-shadow", "opacity", "filter",
+    "transform", "transition", "animation",
+];
+
+fn unprefixed(property: &str) -> &str {
+    ["-webkit-", "-moz-", "-ms-", "-o-"]
+        .iter()
+        .find_map(|prefix| property.strip_prefix(prefix))
+        .unwrap_or(property)
+}
+
+// the shorthand setting a property, or the property itself where the shorthand shares its prefix
+fn shorthand(property: &str) -> &str {
+    let property = unprefixed(property);
+    match property {
+        "top" | "right" | "bottom" | "left" => "inset",
+        "row-gap" | "column-gap" => "gap",
+        "line-height" => "font",
+        _ if property.starts_with("align-") || property.starts_with("justify-") => "place",
+        _ => property,
+    }
+}
+
+// first part of the shorthand name, empty for custom properties so they sort first
+pub(crate) fn family(property: &str) -> &str {
+    shorthand(property).split('-').next().unwrap_or_default()
+}
+
+// custom properties first since the rest may refer to them, unknown families last
+fn group(property: &str) -> usize {
+    if property.starts_with("--") {
+        return 0;
+    }
+    let property = shorthand(property);
+    GROUPS
+        .iter()
+        .position(|family| {
+            property == *family
+                || property
+                    .strip_prefix(family)
+                    .is_some_and(|rest| rest.starts_with('-'))
+        })
+        .map_or(GROUPS.len() + 1, |position| position + 1)
+}
+
+// how regenerated stylesheets are ordered so they diff well under version control
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct OutputOrder {
+    declarations: DeclarationOrder,
+    rules_by_selector: bool,
+}
+
+impl OutputOrder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn declarations(mut self, order: DeclarationOrder) -> Self {
+        self.declarations = order;
+        self
+    }
+
+    // only keeps the cascade when rules of the same specificity don't set the same
+    // properties on the same elements
+    pub fn rules_by_selector(mut self) -> Self {
+        self.rules_by_selector = true;
+        self
+    }
+
+    // declarations of one family keep their written order so shorthands and longhands still
+    // override each other the same way, all resets the rest so nothing is moved across it
+    fn sort_declarations(&self, declarations: &[Declaration]) -> Vec<Declaration> {
+        let mut resets = 0;
+        let mut declarations = declarations
+            .iter()
+            .map(|declaration| match unprefixed(declaration.property()) {
+                "all" => {
+                    resets += 1;
+                    (resets * 2 - 1, declaration)
+                }
+                _ => (resets * 2, declaration),
+            })
+            .collect::<Vec<_>>();
+        match self.declarations {
+            DeclarationOrder::Written => (),
+            DeclarationOrder::Alphabetical => {
+                declarations.sort_by_key(|(run, d)| (*run, family(d.property())))
+            }
+            DeclarationOrder::Grouped => {
+                declarations.sort_by_key(|(run, d)| (*run, group(d.property())))
+            }
+        }
+        declarations.into_iter().map(|(_, d)| d.clone()).collect()
+    }
+
+    fn sort_rules(&self, rules: &[Rule]) -> Vec<Rule> {
+        let mut rules = rules
+            .iter()
+            .map(|rule| {
+                Rule::new(
+                    rule.selector().clone(),
+                    self.sort_declarations(rule.declarations()),
+                    self.sort_rules(rule.sub_rules()),
+                )
+            })
+            .collect::<Vec<_>>();
+        if self.rules_by_selector {
+            rules.sort_by_cached_key(|rule| rule.selector().to_string());
+        }
+        rules
+    }
+
+    fn sort_at_rule(&self, at_rule: &AtRule) -> AtRule {
+        match at_rule {
+            AtRule::Keyframes(keyframes) => AtRule::Keyframes(Keyframes::new(
+                keyframes.name().to_string(),
+                keyframes
+                    .keyframes()
+                    .iter()
+                    .map(|keyframe| {
+                        Keyframe::new(
+                            keyframe.selectors().to_vec(),
+                            self.sort_declarations(keyframe.declarations()),
+                        )
+                    })
+                    .collect(),
+            )),
+            AtRule::Layer(name, set) => AtRule::Layer(name.clone(), set.sorted(self)),
+            AtRule::Container(query, set) => AtRule::Container(query.clone(), set.sorted(self)),
+            AtRule::Generic {
+                name,
+                prelude,
+                block,
+            } => AtRule::Generic {
+                name: name.clone(),
+                prelude: prelude.clone(),
+                block: block.as_ref().map(|block| match block {
+                    AtRuleBlock::Declarations(declarations) => {
+                        AtRuleBlock::Declarations(self.sort_declarations(declarations))
+                    }
+                    AtRuleBlock::Rules(set) => AtRuleBlock::Rules(set.sorted(self)),
+                }),
+            },
+            at_rule => at_rule.clone(),
+        }
+    }
+}
+
+impl RuleSet {
+    // copy with declarations and rules ordered, in nested sets and at-rules too,
+    // sets and at-rules themselves keep their order
+    pub fn sorted(&self, order: &OutputOrder) -> RuleSet {
+        let set = RuleSet::new(
+            order.sort_rules(self.rules()),
+            self.sub_sets()
+                .iter()
+                .map(|set| set.sorted(order))
+                .collect(),
+            self.media_query().cloned(),
+        );
+        self.at_rules()
+            .iter()
+            .map(|at_rule| order.sort_at_rule(at_rule))
+            .fold(set, RuleSet::at_rule)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::css::RuleSet;
+    use crate::order::{DeclarationOrder, OutputOrder};
+
+    const CSS: &str = ".b { color: red; margin-top: 0; -webkit-user-select: none; margin: 4px; --accent: blue; user-select: none; position: relative } .a { z-index: 1; width: 2px } @media print { .d { top: 0 } .c { left: 0 } }";
+
+    #[test]
+    fn declarations() {
+        let set = RuleSet::parse(CSS).unwrap();
+
+        assert_eq!(
+            set.sorted(&OutputOrder::new().declarations(DeclarationOrder::Alphabetical))
+                .sub_sets()[0]
+                .rules()[0]
+                .to_string(),
+            ".b{--accent:blue;color:red;margin-top:0;margin:4px;position:relative;-webkit-user-select:none;user-select:none;}"
+        );
+        assert_eq!(
+            set.sorted(&OutputOrder::new().declarations(DeclarationOrder::Grouped))
+                .sub_sets()[0]
+                .rules()[0]
+                .to_string(),
+            ".b{--accent:blue;position:relative;margin-top:0;margin:4px;color:red;-webkit-user-select:none;user-select:none;}"
+        );
+        assert_eq!(set.sorted(&OutputOrder::new()), set);
+    }
+
+    #[test]
+    fn shorthands_keep_overriding() {
+        let set = RuleSet::parse(
+            ".a { top: 0; inset: 1px; row-gap: 1px; gap: 0; line-height: 2; font: 12px serif; align-items: end; place-items: start; color: red; all: unset; z-index: 1; color: blue }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            set.sorted(&OutputOrder::new().declarations(DeclarationOrder::Alphabetical))
+                .to_string(),
+            ".a{color:red;line-height:2;font:12px serif;row-gap:1px;gap:0;top:0;inset:1px;align-items:end;place-items:start;all:unset;color:blue;z-index:1;}"
+        );
+        assert_eq!(
+            set.sorted(&OutputOrder::new().declarations(DeclarationOrder::Grouped))
+                .to_string(),
+            ".a{top:0;inset:1px;align-items:end;place-items:start;row-gap:1px;gap:0;line-height:2;font:12px serif;color:red;all:unset;z-index:1;color:blue;}"
+        );
+    }
+
+    #[test]
+    fn rules() {
+        let set = RuleSet::parse(CSS).unwrap();
+        let sorted = set.sorted(&OutputOrder::new().rules_by_selector());
+
+        assert_eq!(
+            sorted.to_string(),
+            ".a{z-index:1;width:2px;}.b{color:red;margin-top:0;-webkit-user-select:none;margin:4px;--accent:blue;user-select:none;position:relative;}@media print{.c{left:0;}.d{top:0;}}"
+        );
+        assert_eq!(
+            sorted.sorted(&OutputOrder::new().rules_by_selector()),
+            sorted
+        );
+    }
+}
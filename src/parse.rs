@@ -0,0 +1,448 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::error::Error;
+use crate::html::{is_void_element, Attribute, Node};
+
+const ENTITIES: &[(&str, char)] = &[
+    ("amp", '&'),
+    ("apos", '\''),
+    ("gt", '>'),
+    ("lt", '<'),
+    ("nbsp", '\u{a0}'),
+    ("quot", '"'),
+];
+
+// character and length of the reference at the start of the text
+fn decode_entity(text: &str) -> Option<(char, usize)> {
+    let end = text.find(';')?;
+    let name = &text[1..end];
+    let c = match name.strip_prefix('#') {
+        Some(number) => match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok(),
+            None => number.parse::<u32>().ok(),
+        }
+        .and_then(char::from_u32),
+        None => ENTITIES.iter().find(|(n, _)| *n == name).map(|(_, c)| *c),
+    }?;
+
+    Some((c, end + 1))
+}
+
+// unknown references are kept as written
+fn decode_entities(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match decode_entity(rest) {
+            Some((c, len)) => {
+                output.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+// open elements closed by the start of another, a small part of the html parsing rules
+fn closed_by(open: &str, tag: &str) -> bool {
+    let tag = tag.to_ascii_lowercase();
+    match open.to_ascii_lowercase().as_str() {
+        "p" => matches!(
+            tag.as_str(),
+            "address"
+                | "article"
+                | "aside"
+                | "blockquote"
+                | "div"
+                | "dl"
+                | "fieldset"
+                | "footer"
+                | "form"
+                | "h1"
+                | "h2"
+                | "h3"
+                | "h4"
+                | "h5"
+                | "h6"
+                | "header"
+                | "hr"
+                | "main"
+                | "nav"
+                | "ol"
+                | "p"
+                | "pre"
+                | "section"
+                | "table"
+                | "ul"
+        ),
+        "li" => tag == "li",
+        "dt" | "dd" => tag == "dt" || tag == "dd",
+        "option" => tag == "option" || tag == "optgroup",
+        "tr" => tag == "tr",
+        "td" | "th" => matches!(tag.as_str(), "td" | "th" | "tr"),
+        _ => false,
+    }
+}
+
+enum Token {
+    Text(String),
+    Comment(String),
+    Start(String, Vec<Attribute>, bool),
+    End(String),
+}
+
+struct OpenElement {
+    tag: String,
+    attributes: Vec<Attribute>,
+    children: Vec<Node>,
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+    open: Vec<OpenElement>,
+    roots: Vec<Node>,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::Parse(format!("{} at byte {}", message, self.position))
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    // reads up to the first character matching the predicate or the end of input
+    fn take_until<P: Fn(char) -> bool>(&mut self, predicate: P) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(predicate).unwrap_or(rest.len());
+        self.position += len;
+        &rest[..len]
+    }
+
+    // consumes through the terminator, the contents before it are returned
+    fn take_through(&mut self, terminator: &str, message: &str) -> Result<&'a str, Error> {
+        let rest = self.rest();
+        match rest.find(terminator) {
+            Some(end) => {
+                self.position += end + terminator.len();
+                Ok(&rest[..end])
+            }
+            None => Err(self.error(message)),
+        }
+    }
+
+    fn starts_markup(text: &str) -> bool {
+        let mut chars = text.chars();
+        chars.next() == Some('<')
+            && match chars.next() {
+                Some('/') => chars.next().is_some_and(|c| c.is_ascii_alphabetic()),
+                Some(c) => c.is_ascii_alphabetic() || c == '!' || c == '?',
+                None => false,
+            }
+    }
+
+    fn attribute(&mut self) -> Result<Option<Attribute>, Error> {
+        let name = self.take_until(|c| c.is_whitespace() || matches!(c, '=' | '>' | '/'));
+        if name.is_empty() {
+            // stray characters such as a lone = are skipped
+            self.position += self.rest().chars().next().map_or(0, char::len_utf8);
+            return Ok(None);
+        }
+
+        self.skip_whitespace();
+        if !self.rest().starts_with('=') {
+            return Ok(Some(Attribute::toggle(name.to_string())));
+        }
+        self.position += 1;
+        self.skip_whitespace();
+
+        let value = match self.rest().chars().next() {
+            Some(quote) if quote == '"' || quote == '\'' => {
+                self.position += 1;
+                let mut terminator = [0u8; 4];
+                self.take_through(
+                    quote.encode_utf8(&mut terminator),
+                    "unterminated attribute value",
+                )?
+            }
+            _ => self.take_until(|c| c.is_whitespace() || c == '>'),
+        };
+
+        Ok(Some(Attribute::new(
+            name.to_string(),
+            decode_entities(value),
+        )))
+    }
+
+    fn start_tag(&mut self) -> Result<Token, Error> {
+        self.position += 1;
+        let tag = self
+            .take_until(|c| c.is_whitespace() || c == '/' || c == '>')
+            .to_string();
+        let mut attributes: Vec<Attribute> = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.position += 2;
+                return Ok(Token::Start(tag, attributes, true));
+            } else if rest.starts_with('>') {
+                self.position += 1;
+                return Ok(Token::Start(tag, attributes, false));
+            } else if rest.is_empty() {
+                return Err(self.error("unterminated tag"));
+            } else if rest.starts_with('/') {
+                self.position += 1;
+            } else if let Some(attribute) = self.attribute()? {
+                // the first of repeated attributes wins, same as browsers
+                if !attributes
+                    .iter()
+                    .any(|a| a.name().eq_ignore_ascii_case(attribute.name()))
+                {
+                    attributes.push(attribute);
+                }
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, Error> {
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return Ok(None);
+            }
+
+            if rest.starts_with("<!--") {
+                self.position += 4;
+                let comment = self.take_through("-->", "unterminated comment")?;
+                return Ok(Some(Token::Comment(comment.trim().to_string())));
+            } else if rest.starts_with("<!") || rest.starts_with("<?") {
+                // doctypes and processing instructions are not kept
+                self.take_through(">", "unterminated declaration")?;
+            } else if Self::starts_markup(rest) && rest.starts_with("</") {
+                self.position += 2;
+                let tag = self.take_until(|c| c.is_whitespace() || c == '>');
+                let tag = tag.to_string();
+                self.take_through(">", "unterminated end tag")?;
+                return Ok(Some(Token::End(tag)));
+            } else if Self::starts_markup(rest) {
+                return self.start_tag().map(Some);
+            } else {
+                // a < that does not start markup is text
+                let first = rest.chars().next().map_or(0, char::len_utf8);
+                let len = rest[first..]
+                    .char_indices()
+                    .find(|(i, _)| Self::starts_markup(&rest[first + i..]))
+                    .map_or(rest.len(), |(i, _)| i + first);
+                self.position += len;
+                return Ok(Some(Token::Text(decode_entities(&rest[..len]))));
+            }
+        }
+    }
+
+    // contents of script and style up to their end tag, without decoding
+    fn raw_text(&mut self, tag: &str) -> Option<String> {
+        let rest = self.rest();
+        let end = rest
+            .to_ascii_lowercase()
+            .find(&format!("</{}", tag.to_ascii_lowercase()))
+            .unwrap_or(rest.len());
+        self.position += end;
+
+        match end {
+            0 => None,
+            _ => Some(rest[..end].to_string()),
+        }
+    }
+
+    fn children(&mut self) -> &mut Vec<Node> {
+        match self.open.last_mut() {
+            Some(open) => &mut open.children,
+            None => &mut self.roots,
+        }
+    }
+
+    fn close(&mut self) {
+        if let Some(open) = self.open.pop() {
+            let node = Node::element(open.tag, open.attributes, open.children);
+            self.children().push(node);
+        }
+    }
+
+    fn parse(mut self) -> Result<Node, Error> {
+        while let Some(token) = self.next_token()? {
+            match token {
+                Token::Text(text) => self.children().push(Node::Text(text)),
+                Token::Comment(comment) => self.children().push(Node::Comment(comment)),
+                Token::Start(tag, attributes, self_closing) => {
+                    while self.open.last().is_some_and(|o| closed_by(&o.tag, &tag)) {
+                        self.close();
+                    }
+
+                    if is_void_element(&tag) {
+                        self.children()
+                            .push(Node::element(tag, attributes, Vec::new()));
+                    } else if self_closing {
+                        self.children().push(Node::self_closing(tag, attributes));
+                    } else {
+                        let raw_text = match tag.eq_ignore_ascii_case("script")
+                            || tag.eq_ignore_ascii_case("style")
+                        {
+                            true => self.raw_text(&tag),
+                            false => None,
+                        };
+                        self.open.push(OpenElement {
+                            tag,
+                            attributes,
+                            children: raw_text.map(Node::Text).into_iter().collect(),
+                        });
+                    }
+                }
+                // end tags without a matching open element are ignored
+                Token::End(tag) => {
+                    if let Some(index) = self
+                        .open
+                        .iter()
+                        .rposition(|o| o.tag.eq_ignore_ascii_case(&tag))
+                    {
+                        while self.open.len() > index {
+                            self.close();
+                        }
+                    }
+                }
+            }
+        }
+
+        while !self.open.is_empty() {
+            self.close();
+        }
+
+        let mut roots = self.roots;
+        roots.retain(|n| !matches!(n, Node::Text(text) if text.trim().is_empty()));
+        Ok(match roots.len() {
+            1 => roots.remove(0),
+            _ => Node::Fragment(roots),
+        })
+    }
+}
+
+impl Node {
+    // lenient parser for snippets and templates, unclosed elements are closed at the end of input
+    // and stray end tags are ignored. Several top level nodes are returned as a fragment.
+    pub fn parse(html: &str) -> Result<Node, Error> {
+        Parser {
+            input: html,
+            position: 0,
+            open: Vec::new(),
+            roots: Vec::new(),
+        }
+        .parse()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Error;
+    use crate::html::{Attribute, Node};
+
+    #[test]
+    fn round_trip() {
+        let html = "<div class=\"card\" hidden><h1>Title &amp; more</h1><!-- note --><img src=\"a.png\"><p>a &lt; b</p></div>";
+
+        assert_eq!(Node::parse(html).unwrap().to_string(), html);
+    }
+
+    #[test]
+    fn attributes() {
+        let node =
+            Node::parse("<input type=text value='a &quot;b&quot;' disabled type=email>").unwrap();
+
+        assert_eq!(
+            node,
+            Node::element(
+                "input".to_string(),
+                vec![
+                    Attribute::new("type".to_string(), "text".to_string()),
+                    Attribute::new("value".to_string(), "a \"b\"".to_string()),
+                    Attribute::toggle("disabled".to_string()),
+                ],
+                vec![]
+            )
+        );
+    }
+
+    #[test]
+    fn raw_text() {
+        let node = Node::parse("<script>if (a < b && c) { x = '</div>'; }</SCRIPT>").unwrap();
+
+        assert_eq!(
+            node.children(),
+            &[Node::text("if (a < b && c) { x = '</div>'; }".to_string())]
+        );
+    }
+
+    #[test]
+    fn lenient() {
+        let node =
+            Node::parse("<!DOCTYPE html>\n<ul><li>a<li>b</span></ul><p>one<p>two <3").unwrap();
+
+        assert_eq!(
+            node.to_string(),
+            "<ul><li>a</li><li>b</li></ul><p>one</p><p>two &lt;3</p>"
+        );
+    }
+
+    #[test]
+    fn non_ascii_text() {
+        assert_eq!(Node::parse("é<p>x</p>").unwrap().to_string(), "é<p>x</p>");
+        assert_eq!(
+            Node::parse("<p>x</p>élan").unwrap().to_string(),
+            "<p>x</p>élan"
+        );
+    }
+
+    #[test]
+    fn self_closing() {
+        let node = Node::parse("<svg><path d=\"M0 0\"/></svg><br/>").unwrap();
+
+        assert_eq!(
+            node.to_string(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\"><path d=\"M0 0\"/></svg><br>"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Node::parse("<p>a</p><!-- open"),
+            Err(Error::Parse("unterminated comment at byte 12".to_string()))
+        );
+        assert_eq!(
+            Node::parse("<a href=\"x>"),
+            Err(Error::Parse(
+                "unterminated attribute value at byte 9".to_string()
+            ))
+        );
+        assert_eq!(
+            Node::parse("<a href"),
+            Err(Error::Parse("unterminated tag at byte 7".to_string()))
+        );
+    }
+}
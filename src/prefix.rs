@@ -0,0 +1,322 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::at_rule::{AtRule, AtRuleBlock, Keyframe, Keyframes};
+use crate::css::{Declaration, DeclarationValue, Rule, RuleSet, Selector};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Vendor {
+    Webkit,
+    Moz,
+    Ms,
+}
+
+impl Vendor {
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Vendor::Webkit => "-webkit-",
+            Vendor::Moz => "-moz-",
+            Vendor::Ms => "-ms-",
+        }
+    }
+}
+
+// placeholder was named differently by each vendor
+fn prefixed_pseudo_element(vendor: Vendor, name: &str) -> String {
+    match (vendor, name) {
+        (Vendor::Webkit, "placeholder") => "-webkit-input-placeholder".to_string(),
+        (Vendor::Ms, "placeholder") => "-ms-input-placeholder".to_string(),
+        _ => format!("{}{}", vendor.prefix(), name),
+    }
+}
+
+type VendorList = Vec<(String, Vec<Vendor>)>;
+
+fn vendors<'a>(list: &'a VendorList, name: &str) -> &'a [Vendor] {
+    list.iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map_or(&[], |(_, vendors)| vendors)
+}
+
+// which properties, keyword or function values and pseudo elements get prefixed copies,
+// the copies are written before the standard form so it wins where it is supported
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Prefixer {
+    #[serde(default)]
+    properties: VendorList,
+    #[serde(default)]
+    values: VendorList,
+    #[serde(default)]
+    pseudo_elements: VendorList,
+}
+
+impl Prefixer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // prefixes still needed by browsers in use
+    pub fn common() -> Self {
+        use Vendor::*;
+        Self::new()
+            .property("user-select", [Webkit, Moz, Ms])
+            .property("appearance", [Webkit, Moz])
+            .property("backdrop-filter", [Webkit])
+            .property("background-clip", [Webkit])
+            .property("box-decoration-break", [Webkit])
+            .property("hyphens", [Webkit, Ms])
+            .property("mask", [Webkit])
+            .property("mask-image", [Webkit])
+            .property("text-size-adjust", [Webkit, Moz, Ms])
+            .value("sticky", [Webkit])
+            .value("fit-content", [Moz])
+            .value("image-set", [Webkit])
+            .pseudo_element("placeholder", [Webkit, Moz, Ms])
+            .pseudo_element("selection", [Moz])
+    }
+
+    pub fn property(
+        mut self,
+        name: impl Into<String>,
+        vendors: impl IntoIterator<Item = Vendor>,
+    ) -> Self {
+        self.properties
+            .push((name.into(), vendors.into_iter().collect()));
+        self
+    }
+
+    // a keyword like sticky or the name of a function like image-set
+    pub fn value(
+        mut self,
+        name: impl Into<String>,
+        vendors: impl IntoIterator<Item = Vendor>,
+    ) -> Self {
+        self.values
+            .push((name.into(), vendors.into_iter().collect()));
+        self
+    }
+
+    pub fn pseudo_element(
+        mut self,
+        name: impl Into<String>,
+        vendors: impl IntoIterator<Item = Vendor>,
+    ) -> Self {
+        self.pseudo_elements
+            .push((name.into(), vendors.into_iter().collect()));
+        self
+    }
+
+    fn declarations(&self, declarations: &[Declaration]) -> Vec<Declaration> {
+        let mut output = Vec::with_capacity(declarations.len());
+        for declaration in declarations {
+            let (property, value) = (declaration.property(), declaration.value());
+            let mut copies = vendors(&self.properties, property)
+                .iter()
+                .map(|vendor| {
+                    Declaration::new(format!("{}{}", vendor.prefix(), property), value.clone())
+                })
+                .collect::<Vec<_>>();
+            match value {
+                DeclarationValue::Keyword(keyword) => {
+                    copies.extend(vendors(&self.values, keyword).iter().map(|vendor| {
+                        Declaration::new(
+                            property.to_string(),
+                            DeclarationValue::keyword(format!("{}{}", vendor.prefix(), keyword)),
+                        )
+                    }))
+                }
+                DeclarationValue::Function(name, arguments) => {
+                    copies.extend(vendors(&self.values, name).iter().map(|vendor| {
+                        Declaration::new(
+                            property.to_string(),
+                            DeclarationValue::Function(
+                                format!("{}{}", vendor.prefix(), name),
+                                arguments.clone(),
+                            ),
+                        )
+                    }))
+                }
+                _ => (),
+            }
+            // prefixed forms written by hand are not repeated
+            output.extend(
+                copies
+                    .into_iter()
+                    .filter(|copy| !declarations.contains(copy)),
+            );
+            output.push(declaration.clone());
+        }
+        output
+    }
+
+    // a copy of the selector for each vendor, only with the parts of a group using the element
+    fn selectors(&self, selector: &Selector) -> Vec<Selector> {
+        let items = match selector {
+            Selector::Group(items) => items.as_slice(),
+            selector => core::slice::from_ref(selector),
+        };
+        let mut selectors = vec![];
+        for (name, vendors) in &self.pseudo_elements {
+            let is_element = |s: &Selector| match s {
+                Selector::PseudoElement(_, element) => element.eq_ignore_ascii_case(name),
+                _ => false,
+            };
+            let using = items
+                .iter()
+                .filter(|item| item.any(&is_element))
+                .collect::<Vec<_>>();
+            if using.is_empty() {
+                continue;
+            }
+            for vendor in vendors {
+                let replace = |s: &Selector| match s {
+                    Selector::PseudoElement(base, _) if is_element(s) => {
+                        Some(Selector::PseudoElement(
+                            base.clone(),
+                            prefixed_pseudo_element(*vendor, &name.to_ascii_lowercase()),
+                        ))
+                    }
+                    _ => None,
+                };
+                let mut copies = using
+                    .iter()
+                    .map(|item| item.replace(&replace))
+                    .collect::<Vec<_>>();
+                selectors.push(match copies.len() {
+                    1 => copies.remove(0),
+                    _ => Selector::Group(copies),
+                });
+            }
+        }
+        selectors
+    }
+
+    // a prefixed pseudo element is unknown to other browsers which drops the whole rule,
+    // so each gets a rule of its own
+    fn rules(&self, rules: &[Rule]) -> Vec<Rule> {
+        let mut output = Vec::with_capacity(rules.len());
+        for rule in rules {
+            let declarations = self.declarations(rule.declarations());
+            let sub_rules = self.rules(rule.sub_rules());
+            for selector in self.selectors(rule.selector()) {
+                output.push(Rule::new(selector, declarations.clone(), sub_rules.clone()));
+            }
+            output.push(Rule::new(rule.selector().clone(), declarations, sub_rules));
+        }
+        output
+    }
+
+    fn at_rule(&self, at_rule: &AtRule) -> AtRule {
+        match at_rule {
+            AtRule::Keyframes(keyframes) => AtRule::Keyframes(Keyframes::new(
+                keyframes.name().to_string(),
+                keyframes
+                    .keyframes()
+                    .iter()
+                    .map(|keyframe| {
+                        Keyframe::new(
+                            keyframe.selectors().to_vec(),
+                            self.declarations(keyframe.declarations()),
+                        )
+                    })
+                    .collect(),
+            )),
+            AtRule::Layer(name, set) => AtRule::Layer(name.clone(), set.prefixed(self)),
+            AtRule::Container(query, set) => AtRule::Container(query.clone(), set.prefixed(self)),
+            AtRule::Generic {
+                name,
+                prelude,
+                block,
+            } => AtRule::Generic {
+                name: name.clone(),
+                prelude: prelude.clone(),
+                block: block.as_ref().map(|block| match block {
+                    AtRuleBlock::Declarations(declarations) => {
+                        AtRuleBlock::Declarations(self.declarations(declarations))
+                    }
+                    AtRuleBlock::Rules(set) => AtRuleBlock::Rules(set.prefixed(self)),
+                }),
+            },
+            at_rule => at_rule.clone(),
+        }
+    }
+}
+
+impl RuleSet {
+    // copy with vendor prefixed duplicates added, in nested sets and at-rules too
+    pub fn prefixed(&self, prefixer: &Prefixer) -> RuleSet {
+        let set = RuleSet::new(
+            prefixer.rules(self.rules()),
+            self.sub_sets()
+                .iter()
+                .map(|set| set.prefixed(prefixer))
+                .collect(),
+            self.media_query().cloned(),
+        );
+        self.at_rules()
+            .iter()
+            .map(|at_rule| prefixer.at_rule(at_rule))
+            .fold(set, RuleSet::at_rule)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::at_rule::{AtRule, Keyframe, KeyframeSelector, Keyframes};
+    use crate::css::{Declaration, DeclarationValue, RuleSet};
+    use crate::prefix::{Prefixer, Vendor};
+
+    #[test]
+    fn properties_and_values() {
+        let set = RuleSet::parse(
+            ".bar { user-select: none; -webkit-user-select: none; position: sticky; background: image-set(url(a.png) 1x) }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            set.prefixed(&Prefixer::common()).to_string(),
+            ".bar{-moz-user-select:none;-ms-user-select:none;user-select:none;-webkit-user-select:none;position:-webkit-sticky;position:sticky;background:-webkit-image-set(url(a.png) 1x);background:image-set(url(a.png) 1x);}"
+        );
+    }
+
+    #[test]
+    fn pseudo_elements() {
+        let set =
+            RuleSet::parse("input::placeholder, .hint { color: gray } p::selection { color: red }")
+                .unwrap();
+
+        assert_eq!(
+            set.prefixed(&Prefixer::common()).to_string(),
+            "input::-webkit-input-placeholder{color:gray;}input::-moz-placeholder{color:gray;}input::-ms-input-placeholder{color:gray;}input::placeholder,.hint{color:gray;}p::-moz-selection{color:red;}p::selection{color:red;}"
+        );
+    }
+
+    #[test]
+    fn nested_sets_and_at_rules() {
+        let prefixer = Prefixer::new().property("backdrop-filter", [Vendor::Webkit]);
+        let set = RuleSet::parse("@media print { .a { backdrop-filter: blur(2px) } }")
+            .unwrap()
+            .at_rule(AtRule::from(Keyframes::new(
+                "fade".to_string(),
+                vec![Keyframe::new(
+                    vec![KeyframeSelector::To],
+                    vec![Declaration::new(
+                        "backdrop-filter".to_string(),
+                        DeclarationValue::keyword("none"),
+                    )],
+                )],
+            )));
+
+        let prefixed = set.prefixed(&prefixer);
+        assert_eq!(
+            prefixed.to_string(),
+            "@keyframes fade{to{-webkit-backdrop-filter:none;backdrop-filter:none;}}@media print{.a{-webkit-backdrop-filter:blur(2px);backdrop-filter:blur(2px);}}"
+        );
+        assert_eq!(prefixed.estimated_len(), prefixed.to_string().len());
+        assert_eq!(set.prefixed(&Prefixer::new()), set);
+    }
+}
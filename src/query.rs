@@ -0,0 +1,452 @@
+use alloc::vec::Vec;
+
+use crate::css::{AttributeCase, Combinator, NthKind, PseudoFunction, Selector};
+use crate::html::{Attribute, Node};
+
+// elements in document order with the positions selectors need, fragments are see through
+struct Located<'a> {
+    node: &'a Node,
+    parent: Option<usize>,
+    previous: Option<usize>, // element siblings
+    next: Option<usize>,
+}
+
+fn locate<'a>(
+    node: &'a Node,
+    parent: Option<usize>,
+    previous: &mut Option<usize>,
+    located: &mut Vec<Located<'a>>,
+) {
+    match node {
+        Node::Element { children, .. } => {
+            let index = located.len();
+            located.push(Located {
+                node,
+                parent,
+                previous: *previous,
+                next: None,
+            });
+            if let Some(previous) = previous {
+                located[*previous].next = Some(index);
+            }
+            *previous = Some(index);
+
+            let mut child_previous = None;
+            for child in children {
+                locate(child, Some(index), &mut child_previous, located);
+            }
+        }
+        Node::Fragment(children) => {
+            for child in children {
+                locate(child, parent, previous, located);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn find_attribute<'a>(node: &'a Node, name: &str) -> Option<&'a Attribute> {
+    node.attributes()
+        .iter()
+        .find(|a| a.name().eq_ignore_ascii_case(name))
+}
+
+// the attribute's value against the selector's, ascii case insensitive with the i flag
+fn attribute_matches(
+    node: &Node,
+    name: &str,
+    expected: &str,
+    case: &AttributeCase,
+    test: fn(&str, &str) -> bool,
+) -> bool {
+    let value = match find_attribute(node, name) {
+        Some(attribute) => attribute.value().unwrap_or(""),
+        None => return false,
+    };
+    match case {
+        AttributeCase::Insensitive => {
+            test(&value.to_ascii_lowercase(), &expected.to_ascii_lowercase())
+        }
+        _ => test(value, expected),
+    }
+}
+
+fn has_word(node: &Node, name: &str, word: &str) -> bool {
+    find_attribute(node, name)
+        .and_then(Attribute::value)
+        .is_some_and(|v| v.split_whitespace().any(|w| w == word))
+}
+
+// pseudo classes matches_pseudo_class can decide from the tree alone
+pub(crate) fn depends_on_tree(class: &str) -> bool {
+    [
+        "root",
+        "first-child",
+        "last-child",
+        "only-child",
+        "empty",
+        "checked",
+        "disabled",
+    ]
+    .iter()
+    .any(|c| c.eq_ignore_ascii_case(class))
+}
+
+fn matches_pseudo_class(class: &str, index: usize, located: &[Located]) -> bool {
+    let position = &located[index];
+    match class.to_ascii_lowercase().as_str() {
+        "root" => position.parent.is_none(),
+        "first-child" => position.previous.is_none(),
+        "last-child" => position.next.is_none(),
+        "only-child" => position.previous.is_none() && position.next.is_none(),
+        "empty" => position.node.children().iter().all(|c| match c {
+            Node::Comment(_) => true,
+            Node::Text(text) => text.is_empty(),
+            _ => false,
+        }),
+        "checked" => find_attribute(position.node, "checked").is_some(),
+        "disabled" => find_attribute(position.node, "disabled").is_some(),
+        // anything depending on user interaction or document state never matches
+        _ => false,
+    }
+}
+
+// next element to try against the base of a combinator
+type Step = fn(&Located) -> Option<usize>;
+
+fn matches(selector: &Selector, index: usize, located: &[Located]) -> bool {
+    let node = located[index].node;
+    match selector {
+        Selector::Universal => true,
+        // & only has a meaning within the sub rules of a rule
+        Selector::Parent => false,
+        Selector::Tag(tag) => node.tag().is_some_and(|t| t.eq_ignore_ascii_case(tag)),
+        Selector::Class(class) => has_word(node, "class", class),
+        Selector::Id(id) => {
+            find_attribute(node, "id").and_then(Attribute::value) == Some(id.as_str())
+        }
+        Selector::Attribute(name) => find_attribute(node, name).is_some(),
+        Selector::AttributeValue(name, value, case) => {
+            attribute_matches(node, name, value, case, |v, expected| v == expected)
+        }
+        Selector::AttributeContains(name, word, case) => {
+            attribute_matches(node, name, word, case, |v, word| {
+                v.split_whitespace().any(|w| w == word)
+            })
+        }
+        Selector::AttributePrefix(name, prefix, case) => {
+            attribute_matches(node, name, prefix, case, |v, prefix| {
+                !prefix.is_empty() && v.starts_with(prefix)
+            })
+        }
+        Selector::AttributeSuffix(name, suffix, case) => {
+            attribute_matches(node, name, suffix, case, |v, suffix| {
+                !suffix.is_empty() && v.ends_with(suffix)
+            })
+        }
+        Selector::AttributeSubstring(name, part, case) => {
+            attribute_matches(node, name, part, case, |v, part| {
+                !part.is_empty() && v.contains(part)
+            })
+        }
+        Selector::AttributeDash(name, value, case) => {
+            attribute_matches(node, name, value, case, |v, value| {
+                v.strip_prefix(value)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+            })
+        }
+        Selector::Chain(items) => items.iter().all(|s| matches(s, index, located)),
+        Selector::Group(items) => items.iter().any(|s| matches(s, index, located)),
+        Selector::PseudoClass(base, class) => {
+            matches(base, index, located) && matches_pseudo_class(class, index, located)
+        }
+        // pseudo elements are not part of the tree
+        Selector::PseudoElement(_, _) => false,
+        Selector::Combinator(base, combinator, relative) => {
+            matches(relative, index, located)
+                && related(index, combinator, located, |other| {
+                    matches(base, other, located)
+                })
+        }
+        Selector::Functional(base, function, argument) => {
+            matches(base, index, located)
+                && match function {
+                    PseudoFunction::Not => !matches(argument, index, located),
+                    PseudoFunction::Is | PseudoFunction::Where => matches(argument, index, located),
+                    PseudoFunction::Has => (0..located.len())
+                        .any(|other| matches_relative(argument, other, located, index)),
+                }
+        }
+        // only has a meaning as the argument of :has
+        Selector::Relative(_, _) => false,
+        Selector::Nth {
+            base,
+            kind,
+            a,
+            b,
+            of,
+        } => {
+            if !matches(base, index, located)
+                || of.as_ref().is_some_and(|of| !matches(of, index, located))
+            {
+                return false;
+            }
+
+            // siblings before the element, or after it for the last variants
+            let step: Step = match kind {
+                NthKind::Child | NthKind::OfType => |l| l.previous,
+                NthKind::LastChild | NthKind::LastOfType => |l| l.next,
+            };
+            let counts = |other: usize| match kind {
+                NthKind::OfType | NthKind::LastOfType => located[other].node.tag() == node.tag(),
+                _ => of.as_ref().is_none_or(|of| matches(of, other, located)),
+            };
+            let mut position = 1;
+            let mut candidate = step(&located[index]);
+            while let Some(other) = candidate {
+                position += counts(other) as i64;
+                candidate = step(&located[other]);
+            }
+            nth_matches(*a as i64, *b as i64, position)
+        }
+    }
+}
+
+// whether position is a*n+b for some n >= 0
+fn nth_matches(a: i64, b: i64, position: i64) -> bool {
+    match a {
+        0 => position == b,
+        a => (position - b) % a == 0 && (position - b) / a >= 0,
+    }
+}
+
+// whether an element related to index by the combinator passes the check
+fn related<F: Fn(usize) -> bool>(
+    index: usize,
+    combinator: &Combinator,
+    located: &[Located],
+    check: F,
+) -> bool {
+    let position = &located[index];
+    let (mut candidate, step): (Option<usize>, Step) = match combinator {
+        Combinator::Descendant => (position.parent, |l| l.parent),
+        Combinator::Child => (position.parent, |_| None),
+        Combinator::AdjacentSibling => (position.previous, |_| None),
+        Combinator::GeneralSibling => (position.previous, |l| l.previous),
+    };
+
+    while let Some(other) = candidate {
+        if check(other) {
+            return true;
+        }
+        candidate = step(&located[other]);
+    }
+    false
+}
+
+// a :has argument matching at index, with its leftmost part related to the subject
+fn matches_relative(
+    selector: &Selector,
+    index: usize,
+    located: &[Located],
+    subject: usize,
+) -> bool {
+    match selector {
+        Selector::Group(items) => items
+            .iter()
+            .any(|s| matches_relative(s, index, located, subject)),
+        Selector::Relative(combinator, selector) => {
+            matches_anchored(selector, index, located, subject, combinator)
+        }
+        selector => matches_anchored(selector, index, located, subject, &Combinator::Descendant),
+    }
+}
+
+fn matches_anchored(
+    selector: &Selector,
+    index: usize,
+    located: &[Located],
+    subject: usize,
+    anchor: &Combinator,
+) -> bool {
+    match selector {
+        Selector::Combinator(base, combinator, relative) => {
+            matches(relative, index, located)
+                && related(index, combinator, located, |other| {
+                    matches_anchored(base, other, located, subject, anchor)
+                })
+        }
+        selector => {
+            matches(selector, index, located)
+                && related(index, anchor, located, |other| other == subject)
+        }
+    }
+}
+
+impl Node {
+    // matching elements in document order, including this node
+    pub fn select(&self, selector: &Selector) -> Vec<&Node> {
+        let mut located = Vec::new();
+        locate(self, None, &mut None, &mut located);
+
+        (0..located.len())
+            .filter(|index| matches(selector, *index, &located))
+            .map(|index| located[index].node)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::boxed::Box;
+
+    use crate::builder::Element;
+    use crate::css::{Combinator, Selector};
+    use crate::html::{Attribute, Node};
+
+    fn page() -> Node {
+        crate::html! {
+            body {
+                nav id="menu" {
+                    a href="/" { "Home" }
+                    a class="active external" {}
+                }
+                main { (crate::html! { h1 {} p { a href="/x" {} } }) }
+            }
+        }
+    }
+
+    fn hrefs(nodes: Vec<&Node>) -> Vec<String> {
+        nodes
+            .iter()
+            .map(|n| {
+                n.attributes()
+                    .iter()
+                    .find(|a| a.name() == "href")
+                    .and_then(Attribute::value)
+                    .unwrap_or("-")
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn attribute_selector() {
+        let page = page();
+        let selector = Selector::Chain(vec![
+            Selector::Tag("a".to_string()),
+            Selector::Attribute("href".to_string()),
+        ]);
+
+        assert_eq!(hrefs(page.select(&selector)), vec!["/", "/x"]);
+    }
+
+    #[test]
+    fn class_and_id() {
+        let page = page();
+        let selector = Selector::Combinator(
+            Box::new(Selector::Id("menu".to_string())),
+            Combinator::Child,
+            Box::new(Selector::Class("external".to_string())),
+        );
+
+        assert_eq!(hrefs(page.select(&selector)), vec!["-"]);
+    }
+
+    #[test]
+    fn combinators_see_through_fragments() {
+        let page = page();
+        let sibling = Selector::Combinator(
+            Box::new(Selector::Tag("h1".to_string())),
+            Combinator::AdjacentSibling,
+            Box::new(Selector::Tag("p".to_string())),
+        );
+        let descendant = Selector::Combinator(
+            Box::new(Selector::Tag("main".to_string())),
+            Combinator::Descendant,
+            Box::new(Selector::Tag("a".to_string())),
+        );
+
+        assert_eq!(page.select(&sibling).len(), 1);
+        assert_eq!(hrefs(page.select(&descendant)), vec!["/x"]);
+    }
+
+    #[test]
+    fn pseudo_classes_and_groups() {
+        let page = page();
+        let first = Selector::PseudoClass(
+            Box::new(Selector::Tag("a".to_string())),
+            "first-child".to_string(),
+        );
+        let group = Selector::Group(vec![
+            Selector::Tag("h1".to_string()),
+            Selector::Tag("nav".to_string()),
+        ]);
+
+        assert_eq!(hrefs(page.select(&first)), vec!["/", "/x"]);
+        assert_eq!(
+            page.select(&group)
+                .iter()
+                .filter_map(|n| n.tag())
+                .collect::<Vec<&str>>(),
+            vec!["nav", "h1"]
+        );
+    }
+
+    #[test]
+    fn selector_arguments() {
+        let page = page();
+        let select = |selector: &str| {
+            page.select(&Selector::parse(selector).unwrap())
+                .iter()
+                .filter_map(|n| n.tag())
+                .collect::<Vec<&str>>()
+        };
+
+        assert_eq!(select("a:not([href])"), vec!["a"]);
+        assert_eq!(select(":is(h1, nav)"), vec!["nav", "h1"]);
+        assert_eq!(select("body > :has(> a.active)"), vec!["nav"]);
+        assert_eq!(select(":has(+ p, > [href=\"/x\"])"), vec!["h1", "p"]);
+        assert_eq!(select("main:has(h1 + p a)"), vec!["main"]);
+        assert_eq!(select("p:has(h1)"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn attribute_operators() {
+        let page = page();
+        let select = |selector: &str| hrefs(page.select(&Selector::parse(selector).unwrap()));
+
+        assert_eq!(select("[href^=\"/\"]"), vec!["/", "/x"]);
+        assert_eq!(select("[href$=x]"), vec!["/x"]);
+        assert_eq!(select("[class*=\"ive ext\"]"), vec!["-"]);
+        assert_eq!(select("[class|=active]"), Vec::<String>::new());
+        assert_eq!(select("[id|=menu]").len(), 1);
+        assert_eq!(select("[href^=\"\"]"), Vec::<String>::new());
+        assert_eq!(select("[href=\"/X\" i]"), vec!["/x"]);
+        assert_eq!(select("[href=\"/X\"]"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn nth_positions() {
+        let list = Element::new("ul")
+            .children((1..=6).map(|i| {
+                let class = if i % 3 == 0 { "x" } else { "" };
+                Element::new("li").attr("class", class).id(i.to_string())
+            }))
+            .build();
+        let ids = |selector: &str| {
+            list.select(&Selector::parse(selector).unwrap())
+                .iter()
+                .filter_map(|n| n.attributes().iter().find(|a| a.name() == "id"))
+                .filter_map(Attribute::value)
+                .collect::<Vec<&str>>()
+                .join(",")
+        };
+
+        assert_eq!(ids("li:nth-child(odd)"), "1,3,5");
+        assert_eq!(ids("li:nth-last-child(-n+2)"), "5,6");
+        assert_eq!(ids("li:nth-of-type(3n+1)"), "1,4");
+        assert_eq!(ids("li:nth-child(2 of .x)"), "6");
+        assert_eq!(ids("li:nth-child(4)"), "4");
+    }
+}
@@ -0,0 +1,253 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::html::{Attribute, Node};
+
+#[rustfmt::skip]
+const DEFAULT_TAGS: &[&str] = &[
+    "a", "abbr", "b", "blockquote", "br", "caption", "cite", "code", "col", "colgroup", "dd",
+    "del", "div", "dl", "dt", "em", "figcaption", "figure", "h1", "h2", "h3", "h4", "h5", "h6",
+    "hr", "i", "img", "ins", "kbd", "li", "mark", "ol", "p", "pre", "q", "s", "small", "span",
+    "strong", "sub", "sup", "table", "tbody", "td", "tfoot", "th", "thead", "time", "tr", "u",
+    "ul",
+];
+
+const DEFAULT_ATTRIBUTES: &[&str] = &[
+    "alt", "cite", "class", "colspan", "datetime", "dir", "height", "href", "lang", "rowspan",
+    "scope", "src", "title", "width",
+];
+
+const DEFAULT_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+// removed together with their content instead of being unwrapped, their text is not page content
+const DROPPED_TAGS: &[&str] = &[
+    "embed", "frame", "frameset", "iframe", "math", "noembed", "noframes", "noscript", "object",
+    "script", "style", "svg", "template", "textarea", "title",
+];
+
+#[rustfmt::skip]
+const URL_ATTRIBUTES: &[&str] = &[
+    "action", "background", "cite", "formaction", "href", "poster", "src", "xlink:href",
+];
+
+fn to_strings(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Sanitizer {
+    tags: Vec<String>,
+    attributes: Vec<String>,
+    schemes: Vec<String>,
+}
+
+impl Default for Sanitizer {
+    fn default() -> Self {
+        Self {
+            tags: to_strings(DEFAULT_TAGS),
+            attributes: to_strings(DEFAULT_ATTRIBUTES),
+            schemes: to_strings(DEFAULT_SCHEMES),
+        }
+    }
+}
+
+impl Sanitizer {
+    // nothing allowed, for building a list from scratch
+    pub fn empty() -> Self {
+        Self {
+            tags: Vec::new(),
+            attributes: Vec::new(),
+            schemes: Vec::new(),
+        }
+    }
+
+    pub fn allow_tags<S: Into<String>>(mut self, tags: impl IntoIterator<Item = S>) -> Self {
+        self.tags
+            .extend(tags.into_iter().map(|t| t.into().to_ascii_lowercase()));
+        self
+    }
+
+    // event handlers are never allowed, even when listed here
+    pub fn allow_attrs<S: Into<String>>(mut self, attributes: impl IntoIterator<Item = S>) -> Self {
+        self.attributes.extend(
+            attributes
+                .into_iter()
+                .map(|a| a.into().to_ascii_lowercase()),
+        );
+        self
+    }
+
+    // schemes allowed in url attributes, relative urls are always allowed
+    pub fn allow_schemes<S: Into<String>>(mut self, schemes: impl IntoIterator<Item = S>) -> Self {
+        self.schemes
+            .extend(schemes.into_iter().map(|s| s.into().to_ascii_lowercase()));
+        self
+    }
+
+    fn allows_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    // browsers ignore whitespace and control characters inside the scheme (java\tscript:)
+    fn allows_url(&self, url: &str) -> bool {
+        let url = url
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace() && !c.is_control())
+            .collect::<String>();
+        match url.find(':') {
+            Some(index) if !url[..index].contains(['/', '?', '#']) => self
+                .schemes
+                .iter()
+                .any(|s| s.eq_ignore_ascii_case(&url[..index])),
+            _ => true,
+        }
+    }
+
+    fn allows_attribute(&self, attribute: &Attribute) -> bool {
+        let name = attribute.name().to_ascii_lowercase();
+        if name.starts_with("on") || !self.attributes.contains(&name) {
+            return false;
+        }
+
+        match (name.as_str(), attribute.value()) {
+            ("srcset", Some(value)) => value
+                .split(',')
+                .filter_map(|candidate| candidate.split_whitespace().next())
+                .all(|url| self.allows_url(url)),
+            (name, Some(value)) if URL_ATTRIBUTES.contains(&name) => self.allows_url(value),
+            _ => true,
+        }
+    }
+
+    // trusted values are written unescaped, so they are kept as plain values
+    fn attribute(&self, attribute: &Attribute) -> Attribute {
+        match attribute.value() {
+            Some(value) => Attribute::new(attribute.name().to_string(), value.to_string()),
+            None => Attribute::toggle(attribute.name().to_string()),
+        }
+    }
+
+    fn children(&self, children: &[Node]) -> Vec<Node> {
+        children.iter().filter_map(|c| self.node(c)).collect()
+    }
+
+    fn node(&self, node: &Node) -> Option<Node> {
+        match node {
+            Node::Text(_) | Node::Message { .. } | Node::Slot(_) => Some(node.clone()),
+            // comments can hold conditional comments and markup for old parsers
+            Node::Comment(_) => None,
+            // raw markup is parsed so it gets the same treatment, anything unparsable is dropped
+            Node::Raw(html) => Node::parse(html).ok().and_then(|n| self.node(&n)),
+            #[cfg(feature = "markdown")]
+            Node::Markdown(text) => Node::parse(&crate::markdown::markdown_to_html(text))
+                .ok()
+                .and_then(|n| self.node(&n)),
+            Node::Fragment(children) => Some(Node::Fragment(self.children(children))),
+            Node::Element {
+                tag,
+                attributes,
+                children,
+                self_closing,
+            } => {
+                if self.allows_tag(tag) {
+                    Some(Node::Element {
+                        tag: tag.clone(),
+                        attributes: attributes
+                            .iter()
+                            .filter(|a| self.allows_attribute(a))
+                            .map(|a| self.attribute(a))
+                            .collect(),
+                        children: self.children(children),
+                        self_closing: *self_closing,
+                    })
+                } else if DROPPED_TAGS.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                    None
+                } else {
+                    Some(Node::Fragment(self.children(children)))
+                }
+            }
+        }
+    }
+
+    // elements that are not allowed are replaced by their sanitized children
+    pub fn sanitize(&self, node: &Node) -> Node {
+        self.node(node)
+            .unwrap_or_else(|| Node::Fragment(Vec::new()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::html::{Attribute, Node};
+    use crate::sanitize::Sanitizer;
+
+    #[test]
+    fn strips_scripts_and_unknown_tags() {
+        let node = crate::html! {
+            div {
+                script { "alert(1)" }
+                blink { b { "bold" } }
+                (Node::comment("[if IE]><script>x</script><![endif]".to_string()))
+            }
+        };
+
+        assert_eq!(
+            Sanitizer::default().sanitize(&node).to_string(),
+            "<div><b>bold</b></div>"
+        );
+    }
+
+    #[test]
+    fn strips_event_handlers_and_urls() {
+        let node = crate::html! {
+            p onclick="steal()" class="intro" {
+                a href=" java\tscript:alert(1)" { "x" }
+                a href="/about?a=b:c" { "y" }
+                a href="https://x.test" { "z" }
+                img src="data:image/png;base64,AAAA";
+            }
+        };
+
+        assert_eq!(
+            Sanitizer::default().sanitize(&node).to_string(),
+            "<p class=\"intro\"><a>x</a><a href=\"/about?a=b:c\">y</a><a href=\"https://x.test\">z</a><img></p>"
+        );
+    }
+
+    #[test]
+    fn configured_lists() {
+        let node = crate::html! {
+            section data-id="1" onload="x()" { img src="data:image/png;base64,AAAA"; }
+        };
+        let sanitizer = Sanitizer::default()
+            .allow_tags(["section"])
+            .allow_attrs(["data-id", "onload"])
+            .allow_schemes(["data"]);
+
+        assert_eq!(
+            sanitizer.sanitize(&node).to_string(),
+            "<section data-id=\"1\"><img src=\"data:image/png;base64,AAAA\"></section>"
+        );
+        assert_eq!(Sanitizer::empty().sanitize(&node).to_string(), "");
+    }
+
+    #[test]
+    fn raw_markup_and_trusted_values() {
+        let node = Node::fragment(vec![
+            Node::raw("<em onmouseover=\"x()\">hi</em><script>x()</script>".to_string()),
+            Node::element(
+                "a".to_string(),
+                vec![Attribute::trusted(
+                    "title".to_string(),
+                    "\"><script>".to_string(),
+                )],
+                vec![],
+            ),
+        ]);
+
+        assert_eq!(
+            Sanitizer::default().sanitize(&node).to_string(),
+            "<em>hi</em><a title=\"&quot;><script>\"></a>"
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use serde::{Deserialize, Serialize};
+
+use crate::at_rule::{AtRule, AtRuleBlock};
+use crate::class::ClassList;
+use crate::css::{Rule, RuleSet, Selector};
+use crate::html::Node;
+
+// fnv-1a, stable across builds and platforms unlike the hasher of std
+fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u32 {
+    bytes.into_iter().fold(0x811c9dc5, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x01000193)
+    })
+}
+
+// the class with five base 36 characters of a hash of the scope and the class, e.g. card_x3f9a
+pub fn scoped_class(scope: &str, class: &str) -> String {
+    let mut hash = fnv1a(scope.bytes().chain([0]).chain(class.bytes()));
+    let mut suffix = String::with_capacity(5);
+    for _ in 0..5 {
+        suffix.push(char::from_digit(hash % 36, 36).unwrap_or('0'));
+        hash /= 36;
+    }
+    format!("{}_{}", class, suffix)
+}
+
+// classes of a scoped rule set and the names they were rewritten to
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ClassMap {
+    classes: BTreeMap<String, String>,
+}
+
+impl ClassMap {
+    pub fn get(&self, class: &str) -> Option<&str> {
+        self.classes.get(class).map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.classes
+            .iter()
+            .map(|(class, scoped)| (class.as_str(), scoped.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    // copy of the tree with the class attributes rewritten,
+    // classes the styles don't use are kept so global classes still apply
+    pub fn apply(&self, node: &Node) -> Node {
+        let mut node = node.clone();
+        self.apply_mut(&mut node);
+        node
+    }
+
+    fn apply_mut(&self, node: &mut Node) {
+        let list = node.class_list();
+        if list.iter().any(|class| self.classes.contains_key(class)) {
+            let mut scoped = ClassList::default();
+            for class in list.iter() {
+                scoped.add(self.get(class).unwrap_or(class));
+            }
+            node.set_class_list(&scoped);
+        }
+        if let Some(children) = node.children_mut() {
+            children.iter_mut().for_each(|child| self.apply_mut(child));
+        }
+    }
+}
+
+struct Scoping<'a> {
+    scope: &'a str,
+    classes: RefCell<BTreeMap<String, String>>,
+}
+
+impl Scoping<'_> {
+    fn selector(&self, selector: &Selector) -> Selector {
+        selector.replace(&|selector| match selector {
+            Selector::Class(class) => {
+                let scoped = scoped_class(self.scope, class);
+                self.classes
+                    .borrow_mut()
+                    .insert(class.clone(), scoped.clone());
+                Some(Selector::Class(scoped))
+            }
+            _ => None,
+        })
+    }
+
+    fn rules(&self, rules: &[Rule]) -> Vec<Rule> {
+        rules
+            .iter()
+            .map(|rule| {
+                Rule::new(
+                    self.selector(rule.selector()),
+                    rule.declarations().to_vec(),
+                    self.rules(rule.sub_rules()),
+                )
+            })
+            .collect()
+    }
+
+    fn at_rule(&self, at_rule: &AtRule) -> AtRule {
+        match at_rule {
+            AtRule::Layer(name, set) => AtRule::Layer(name.clone(), self.set(set)),
+            AtRule::Container(query, set) => AtRule::Container(query.clone(), self.set(set)),
+            AtRule::Generic {
+                name,
+                prelude,
+                block: Some(AtRuleBlock::Rules(set)),
+            } => AtRule::Generic {
+                name: name.clone(),
+                prelude: prelude.clone(),
+                block: Some(AtRuleBlock::Rules(self.set(set))),
+            },
+            at_rule => at_rule.clone(),
+        }
+    }
+
+    fn set(&self, set: &RuleSet) -> RuleSet {
+        let scoped = RuleSet::new(
+            self.rules(set.rules()),
+            set.sub_sets().iter().map(|set| self.set(set)).collect(),
+            set.media_query().cloned(),
+        );
+        set.at_rules()
+            .iter()
+            .map(|at_rule| self.at_rule(at_rule))
+            .fold(scoped, RuleSet::at_rule)
+    }
+}
+
+impl RuleSet {
+    // copy with every class selector renamed for the scope, e.g. the name of a component,
+    // and the renamed classes to rewrite the markup with
+    pub fn scoped(&self, scope: &str) -> (RuleSet, ClassMap) {
+        let scoping = Scoping {
+            scope,
+            classes: RefCell::new(BTreeMap::new()),
+        };
+        let set = scoping.set(self);
+        (
+            set,
+            ClassMap {
+                classes: scoping.classes.into_inner(),
+            },
+        )
+    }
+}
+
+// styles and markup of a component with its classes scoped together
+pub fn scope_component(styles: &RuleSet, node: &Node, scope: &str) -> (RuleSet, Node, ClassMap) {
+    let (styles, classes) = styles.scoped(scope);
+    let node = classes.apply(node);
+    (styles, node, classes)
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::format;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use crate::css::RuleSet;
+    use crate::html::{Attribute, Node};
+    use crate::scope::{scope_component, scoped_class};
+
+    #[test]
+    fn scoped_classes() {
+        let card = scoped_class("profile", "card");
+
+        assert!(card.starts_with("card_"));
+        assert_eq!(card.len(), 10);
+        assert_eq!(scoped_class("profile", "card"), card);
+        assert_ne!(scoped_class("settings", "card"), card);
+    }
+
+    #[test]
+    fn styles_and_markup() {
+        let styles = RuleSet::parse(
+            ".card { padding: 4px } .card > .title:not(.muted) { color: red } @media print { .card { padding: 0 } }",
+        )
+        .unwrap();
+        let node = Node::element(
+            "div".to_string(),
+            vec![Attribute::new(
+                "class".to_string(),
+                "card shadow".to_string(),
+            )],
+            vec![Node::element(
+                "h2".to_string(),
+                vec![Attribute::new("class".to_string(), "title".to_string())],
+                vec![],
+            )],
+        );
+        let (styles, node, classes) = scope_component(&styles, &node, "profile");
+        let (card, title, muted) = (
+            scoped_class("profile", "card"),
+            scoped_class("profile", "title"),
+            scoped_class("profile", "muted"),
+        );
+
+        assert_eq!(
+            styles.to_string(),
+            format!(
+                ".{card}{{padding:4px;}}.{card}>.{title}:not(.{muted}){{color:red;}}@media print{{.{card}{{padding:0;}}}}"
+            )
+        );
+        assert_eq!(classes.len(), 3);
+        assert_eq!(classes.get("title"), Some(title.as_str()));
+        assert_eq!(node.class_list().to_string(), [&card, " shadow"].concat());
+        assert_eq!(node.children()[0].class_list().to_string(), title);
+    }
+}
@@ -0,0 +1,738 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use serde::{Deserialize, Deserializer};
+
+use crate::css::{
+    escape_len, unescape, AttributeCase, Combinator, NthKind, PseudoFunction, Selector,
+};
+use crate::error::Error;
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_') || !c.is_ascii()
+}
+
+// what a pseudo class or element written without a type applies to, e.g. the base of :root,
+// an empty chain so it is written back the same way
+pub(crate) fn implicit_base() -> Selector {
+    Selector::Chain(Vec::new())
+}
+
+// several simple selectors written together (e.g. a.b[c]) become a chain
+fn compound(mut items: Vec<Selector>) -> Selector {
+    match items.len() {
+        0 => implicit_base(),
+        1 => items.remove(0),
+        _ => Selector::Chain(items),
+    }
+}
+
+// :not(, :is(, :where( or :has( and the length up to the argument
+fn pseudo_function(text: &str) -> Option<(PseudoFunction, usize)> {
+    let (name, _) = text.split_once('(')?;
+    let function = match name.to_ascii_lowercase().as_str() {
+        "not" => PseudoFunction::Not,
+        "is" => PseudoFunction::Is,
+        "where" => PseudoFunction::Where,
+        "has" => PseudoFunction::Has,
+        _ => return None,
+    };
+    Some((function, name.len() + 1))
+}
+
+fn nth_kind(text: &str) -> Option<(NthKind, usize)> {
+    let (name, _) = text.split_once('(')?;
+    let kind = match name.to_ascii_lowercase().as_str() {
+        "nth-child" => NthKind::Child,
+        "nth-last-child" => NthKind::LastChild,
+        "nth-of-type" => NthKind::OfType,
+        "nth-last-of-type" => NthKind::LastOfType,
+        _ => return None,
+    };
+    Some((kind, name.len() + 1))
+}
+
+// an+b, odd or even
+fn nth_formula(text: &str) -> Option<(i32, i32)> {
+    let text = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    match text.as_str() {
+        "odd" => return Some((2, 1)),
+        "even" => return Some((2, 0)),
+        _ => (),
+    }
+    let (a, b) = match text.split_once('n') {
+        None => return Some((0, text.parse().ok()?)),
+        Some(parts) => parts,
+    };
+    let a = match a {
+        "" | "+" => 1,
+        "-" => -1,
+        a => a.parse().ok()?,
+    };
+    let b = match b {
+        "" => 0,
+        b if b.starts_with(['+', '-']) => b.parse().ok()?,
+        _ => return None,
+    };
+    Some((a, b))
+}
+
+struct SelectorParser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> SelectorParser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::Css(format!("{} at byte {}", message, self.position))
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let rest = self.rest();
+        let len = rest.len() - rest.trim_start().len();
+        self.position += len;
+        len > 0
+    }
+
+    fn take_while<P: Fn(char) -> bool>(&mut self, predicate: P) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.position += len;
+        &rest[..len]
+    }
+
+    // a name with its escapes resolved, e.g. md\:flex is md:flex
+    fn name(&mut self, what: &str) -> Result<String, Error> {
+        let start = self.position;
+        loop {
+            match self.peek() {
+                Some('\\') if escape_len(&self.rest()[1..]) > 0 => {
+                    self.position += 1 + escape_len(&self.rest()[1..]);
+                }
+                Some(c) if is_name_char(c) => self.position += c.len_utf8(),
+                _ => break,
+            }
+        }
+        match &self.input[start..self.position] {
+            "" => Err(self.error(&format!("expected {} name", what))),
+            name => Ok(unescape(name)),
+        }
+    }
+
+    // name of a pseudo class or element with its arguments, e.g. nth-child(2n+1)
+    fn pseudo(&mut self) -> Result<String, Error> {
+        let start = self.position;
+        self.name("pseudo class")?;
+        if self.peek() == Some('(') {
+            let mut depth = 0;
+            for (i, c) in self.rest().char_indices() {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => continue,
+                }
+                if depth == 0 {
+                    self.position += i + 1;
+                    return Ok(self.input[start..self.position].to_string());
+                }
+            }
+            return Err(self.error("unclosed ("));
+        }
+        Ok(self.input[start..self.position].to_string())
+    }
+
+    // the selector list of :not(), :is(), :where() or :has(), the last may start with a combinator
+    fn argument(&mut self, function: &PseudoFunction) -> Result<Selector, Error> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let combinator = match self.peek() {
+                Some('>') => Some(Combinator::Child),
+                Some('+') => Some(Combinator::AdjacentSibling),
+                Some('~') => Some(Combinator::GeneralSibling),
+                _ => None,
+            };
+            items.push(match combinator {
+                Some(_) if *function != PseudoFunction::Has => {
+                    return Err(self.error("expected a selector"))
+                }
+                Some(combinator) => {
+                    self.position += 1;
+                    Selector::Relative(combinator, Box::new(self.complex()?))
+                }
+                None => self.complex()?,
+            });
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(')') => {
+                    self.position += 1;
+                    break;
+                }
+                _ => return Err(self.error("expected )")),
+            }
+        }
+        Ok(match items.len() {
+            1 => items.remove(0),
+            _ => Selector::Group(items),
+        })
+    }
+
+    // the an+b formula of :nth-child() and the like, with an optional "of selector"
+    fn nth(&mut self, base: Box<Selector>, kind: NthKind) -> Result<Selector, Error> {
+        self.skip_whitespace();
+        let start = self.position;
+        let formula =
+            self.take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | ' '));
+        let (formula, has_of) = match formula.to_ascii_lowercase().find(" of ") {
+            Some(at) => {
+                self.position = start + at + 4;
+                (&formula[..at], true)
+            }
+            None => (formula, false),
+        };
+        let (a, b) = match nth_formula(formula) {
+            Some(formula) => formula,
+            None => {
+                self.position = start;
+                return Err(self.error("invalid nth formula"));
+            }
+        };
+
+        let of = match has_of {
+            true if !matches!(kind, NthKind::Child | NthKind::LastChild) => {
+                return Err(self.error("of is only allowed in nth-child and nth-last-child"))
+            }
+            true => Some(Box::new(self.selector()?)),
+            false => None,
+        };
+        self.skip_whitespace();
+        if self.peek() != Some(')') {
+            return Err(self.error("expected )"));
+        }
+        self.position += 1;
+        Ok(Selector::Nth {
+            base,
+            kind,
+            a,
+            b,
+            of,
+        })
+    }
+
+    // [name] or [name op value flag] with =, ~=, ^=, $=, *= or |= and an optional i or s flag,
+    // values can be quoted
+    fn attribute(&mut self) -> Result<Selector, Error> {
+        self.skip_whitespace();
+        let name = self.name("attribute")?;
+        self.skip_whitespace();
+        let operator = match self.peek() {
+            Some(']') => {
+                self.position += 1;
+                return Ok(Selector::Attribute(name));
+            }
+            Some('=') => '=',
+            Some(c @ ('~' | '^' | '$' | '*' | '|')) if self.rest()[1..].starts_with('=') => {
+                self.position += 1;
+                c
+            }
+            _ => return Err(self.error("unsupported attribute selector")),
+        };
+        self.position += 1;
+        self.skip_whitespace();
+
+        let value = match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                self.position += 1;
+                let start = self.position;
+                loop {
+                    match self.peek() {
+                        Some(c) if c == quote => break,
+                        Some('\\') => self.position += 1 + escape_len(&self.rest()[1..]),
+                        Some(c) => self.position += c.len_utf8(),
+                        None => return Err(self.error("unterminated attribute value")),
+                    }
+                }
+                self.position += 1;
+                unescape(&self.input[start..self.position - 1])
+            }
+            _ => self.name("attribute value")?,
+        };
+        self.skip_whitespace();
+        let case = match self.peek() {
+            Some('i' | 'I') => AttributeCase::Insensitive,
+            Some('s' | 'S') => AttributeCase::Sensitive,
+            _ => AttributeCase::Default,
+        };
+        if case != AttributeCase::Default {
+            self.position += 1;
+            self.skip_whitespace();
+        }
+        if self.peek() != Some(']') {
+            return Err(self.error("expected ]"));
+        }
+        self.position += 1;
+
+        Ok(match operator {
+            '~' => Selector::AttributeContains(name, value, case),
+            '^' => Selector::AttributePrefix(name, value, case),
+            '$' => Selector::AttributeSuffix(name, value, case),
+            '*' => Selector::AttributeSubstring(name, value, case),
+            '|' => Selector::AttributeDash(name, value, case),
+            _ => Selector::AttributeValue(name, value, case),
+        })
+    }
+
+    // tag#id.class[attribute]:pseudo-class::pseudo-element
+    fn compound(&mut self) -> Result<Selector, Error> {
+        let start = self.position;
+        let mut items = Vec::new();
+        loop {
+            match self.peek() {
+                Some('*') => {
+                    self.position += 1;
+                    items.push(Selector::Universal);
+                }
+                Some('&') => {
+                    self.position += 1;
+                    items.push(Selector::Parent);
+                }
+                Some('#') => {
+                    self.position += 1;
+                    items.push(Selector::Id(self.name("id")?));
+                }
+                Some('.') => {
+                    self.position += 1;
+                    items.push(Selector::Class(self.name("class")?));
+                }
+                Some('[') => {
+                    self.position += 1;
+                    items.push(self.attribute()?);
+                }
+                // pseudo classes and elements apply to everything before them
+                Some(':') if self.rest().starts_with("::") => {
+                    self.position += 2;
+                    let name = self.pseudo()?;
+                    items = Vec::from([Selector::PseudoElement(Box::new(compound(items)), name)]);
+                }
+                Some(':') => {
+                    self.position += 1;
+                    let base = Box::new(compound(items));
+                    let rest = self.rest();
+                    items = Vec::from([match (pseudo_function(rest), nth_kind(rest)) {
+                        (Some((function, len)), _) => {
+                            self.position += len;
+                            let argument = self.argument(&function)?;
+                            Selector::Functional(base, function, Box::new(argument))
+                        }
+                        (_, Some((kind, len))) => {
+                            self.position += len;
+                            self.nth(base, kind)?
+                        }
+                        _ => Selector::PseudoClass(base, self.pseudo()?),
+                    }]);
+                }
+                Some(c) if (is_name_char(c) || c == '\\') && items.is_empty() => {
+                    items.push(Selector::Tag(self.name("tag")?));
+                }
+                _ => break,
+            }
+        }
+        match self.position == start {
+            true => Err(self.error("expected a selector")),
+            false => Ok(compound(items)),
+        }
+    }
+
+    fn combinator(&mut self) -> Option<Combinator> {
+        let whitespace = self.skip_whitespace();
+        let combinator = match self.peek() {
+            Some('>') => Combinator::Child,
+            Some('+') => Combinator::AdjacentSibling,
+            Some('~') => Combinator::GeneralSibling,
+            Some(',') | Some('{') | Some(')') | None => return None,
+            _ if whitespace => return Some(Combinator::Descendant),
+            _ => return None,
+        };
+        self.position += 1;
+        self.skip_whitespace();
+        Some(combinator)
+    }
+
+    // compound selectors joined by combinators, read left to right
+    fn complex(&mut self) -> Result<Selector, Error> {
+        self.skip_whitespace();
+        let mut selector = self.compound()?;
+        while let Some(combinator) = self.combinator() {
+            let relative = self.compound()?;
+            selector = Selector::Combinator(Box::new(selector), combinator, Box::new(relative));
+        }
+        Ok(selector)
+    }
+
+    // a comma separated list, stops at { or the end of input
+    fn selector(&mut self) -> Result<Selector, Error> {
+        let mut items = Vec::from([self.complex()?]);
+        while self.peek() == Some(',') {
+            self.position += 1;
+            items.push(self.complex()?);
+        }
+        Ok(match items.len() {
+            1 => items.remove(0),
+            _ => Selector::Group(items),
+        })
+    }
+}
+
+// (ids, classes, elements) added up
+fn add(a: (u32, u32, u32), b: (u32, u32, u32)) -> (u32, u32, u32) {
+    (a.0 + b.0, a.1 + b.1, a.2 + b.2)
+}
+
+// a selector list counts as its most specific item
+fn largest(items: &[Selector]) -> (u32, u32, u32) {
+    items
+        .iter()
+        .map(Selector::specificity)
+        .max()
+        .unwrap_or_default()
+}
+
+fn argument_list(argument: &Selector) -> &[Selector] {
+    match argument {
+        Selector::Group(items) => items,
+        argument => core::slice::from_ref(argument),
+    }
+}
+
+impl Selector {
+    // (id, class, element) counts as in the selectors spec, for a group the most specific item
+    pub fn specificity(&self) -> (u32, u32, u32) {
+        match self {
+            // an unresolved & has no selector of its own yet
+            Selector::Universal | Selector::Parent => (0, 0, 0),
+            Selector::Tag(_) => (0, 0, 1),
+            Selector::Id(_) => (1, 0, 0),
+            Selector::Class(_)
+            | Selector::Attribute(_)
+            | Selector::AttributeValue(..)
+            | Selector::AttributeContains(..)
+            | Selector::AttributePrefix(..)
+            | Selector::AttributeSuffix(..)
+            | Selector::AttributeSubstring(..)
+            | Selector::AttributeDash(..) => (0, 1, 0),
+            Selector::PseudoClass(base, class) => {
+                // pseudo elements from css 2 can be written with a single colon
+                let legacy = matches!(
+                    class.to_ascii_lowercase().as_str(),
+                    "before" | "after" | "first-line" | "first-letter"
+                );
+                add(
+                    base.specificity(),
+                    if legacy { (0, 0, 1) } else { (0, 1, 0) },
+                )
+            }
+            Selector::PseudoElement(base, _) => add(base.specificity(), (0, 0, 1)),
+            Selector::Functional(base, PseudoFunction::Where, _) => base.specificity(),
+            Selector::Functional(base, _, argument) => {
+                add(base.specificity(), largest(argument_list(argument)))
+            }
+            Selector::Nth { base, of, .. } => {
+                let of = of
+                    .as_deref()
+                    .map_or((0, 0, 0), |of| largest(argument_list(of)));
+                add(add(base.specificity(), (0, 1, 0)), of)
+            }
+            Selector::Combinator(base, _, relative) => {
+                add(base.specificity(), relative.specificity())
+            }
+            Selector::Relative(_, selector) => selector.specificity(),
+            Selector::Chain(items) => items
+                .iter()
+                .fold((0, 0, 0), |total, item| add(total, item.specificity())),
+            Selector::Group(items) => largest(items),
+        }
+    }
+
+    // orders selectors by which one wins when both match, the same as comparing specificities
+    pub fn cmp_specificity(&self, other: &Selector) -> Ordering {
+        self.specificity().cmp(&other.specificity())
+    }
+
+    pub fn parse(selector: &str) -> Result<Selector, Error> {
+        let mut parser = SelectorParser::new(selector);
+        let parsed = parser.selector()?;
+        parser.skip_whitespace();
+        match parser.rest().is_empty() {
+            true => Ok(parsed),
+            false => Err(parser.error("unexpected character")),
+        }
+    }
+}
+
+// rules accept a selector string as well as the structured form
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SelectorInput {
+    Text(String),
+    Structured(Selector),
+}
+
+pub(crate) fn deserialize_selector<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Selector, D::Error> {
+    match SelectorInput::deserialize(deserializer)? {
+        SelectorInput::Text(text) => {
+            Selector::parse(&text).map_err(|e| serde::de::Error::custom(e.to_string()))
+        }
+        SelectorInput::Structured(selector) => Ok(selector),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::css::{AttributeCase, Combinator, NthKind, PseudoFunction, Rule, Selector};
+    use crate::error::Error;
+
+    fn tag(name: &str) -> Selector {
+        Selector::Tag(name.to_string())
+    }
+
+    #[test]
+    fn combinators_and_compounds() {
+        let selector = Selector::parse("body > .card[data-x] :hover").unwrap();
+
+        assert_eq!(
+            selector,
+            Selector::Combinator(
+                Box::new(Selector::Combinator(
+                    Box::new(tag("body")),
+                    Combinator::Child,
+                    Box::new(Selector::Chain(vec![
+                        Selector::Class("card".to_string()),
+                        Selector::Attribute("data-x".to_string()),
+                    ])),
+                )),
+                Combinator::Descendant,
+                Box::new(Selector::PseudoClass(
+                    Box::new(Selector::Chain(vec![])),
+                    "hover".to_string()
+                )),
+            )
+        );
+        assert_eq!(selector.to_string(), "body>.card[data-x] :hover");
+    }
+
+    #[test]
+    fn round_trips() {
+        for selector in [
+            "a.button:not(.primary)::before",
+            "ul>li+li~li",
+            "input[type=\"text\"],textarea[class~=\"wide\"]",
+            "a[href^=\"https\"][href$=\".pdf\" i][title*=\"x\"][lang|=\"en\" s]",
+            "#main *",
+            "tr:nth-child(2n+1) td",
+            "li:nth-last-of-type(-n+3)",
+            "&.active:hover",
+            ".dark &+&::after",
+            ":root",
+            "::selection",
+            "*:hover",
+            "a:not(:visited)",
+        ] {
+            assert_eq!(Selector::parse(selector).unwrap().to_string(), selector);
+        }
+        assert_eq!(
+            Selector::parse("h1 , h2").unwrap(),
+            Selector::Group(vec![tag("h1"), tag("h2")])
+        );
+    }
+
+    #[test]
+    fn selector_arguments() {
+        let selector = Selector::parse(".card:NOT(.hidden, [aria-hidden])").unwrap();
+
+        assert_eq!(
+            selector,
+            Selector::Functional(
+                Box::new(Selector::Class("card".to_string())),
+                PseudoFunction::Not,
+                Box::new(Selector::Group(vec![
+                    Selector::Class("hidden".to_string()),
+                    Selector::Attribute("aria-hidden".to_string()),
+                ])),
+            )
+        );
+        assert_eq!(selector.to_string(), ".card:not(.hidden,[aria-hidden])");
+        for selector in [
+            "li:has(>a.active)",
+            "section:has(h2,+p)",
+            "*:is(h1,h2) *:where(a:hover)",
+            "a:not(*:is(.x .y))",
+        ] {
+            assert_eq!(Selector::parse(selector).unwrap().to_string(), selector);
+        }
+        assert_eq!(
+            Selector::parse("a:not(> b)"),
+            Err(Error::Css("expected a selector at byte 6".to_string()))
+        );
+    }
+
+    #[test]
+    fn nth_formulas() {
+        assert_eq!(
+            Selector::parse("li:nth-child( 2n + 1 of .item, .entry )").unwrap(),
+            Selector::Nth {
+                base: Box::new(tag("li")),
+                kind: NthKind::Child,
+                a: 2,
+                b: 1,
+                of: Some(Box::new(Selector::Group(vec![
+                    Selector::Class("item".to_string()),
+                    Selector::Class("entry".to_string()),
+                ]))),
+            }
+        );
+        for (selector, expected) in [
+            ("p:nth-of-type(even)", "p:nth-of-type(2n)"),
+            ("p:NTH-CHILD(odd)", "p:nth-child(2n+1)"),
+            ("p:nth-last-child(-1n-0)", "p:nth-last-child(-n)"),
+            ("p:nth-child(+5)", "p:nth-child(5)"),
+            ("p:nth-child(n of a b)", "p:nth-child(n of a b)"),
+        ] {
+            assert_eq!(Selector::parse(selector).unwrap().to_string(), expected);
+        }
+        assert_eq!(
+            Selector::parse("p:nth-child(2n1)"),
+            Err(Error::Css("invalid nth formula at byte 12".to_string()))
+        );
+        assert_eq!(
+            Selector::parse("p:nth-of-type(n of a)"),
+            Err(Error::Css(
+                "of is only allowed in nth-child and nth-last-child at byte 19".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn specificity() {
+        for (selector, expected) in [
+            ("*", (0, 0, 0)),
+            ("li", (0, 0, 1)),
+            ("ul li:first-child a::before", (0, 1, 4)),
+            ("#nav .item[href]:hover", (1, 3, 0)),
+            ("a:before", (0, 0, 2)),
+            (":where(#x) p", (0, 0, 1)),
+            ("div:not(.a, #b)", (1, 0, 1)),
+            ("li:nth-child(2n of .x, #y)", (1, 1, 1)),
+            ("section:has(> h2.title)", (0, 1, 2)),
+            ("h1, #main, .a.b", (1, 0, 0)),
+        ] {
+            assert_eq!(
+                Selector::parse(selector).unwrap().specificity(),
+                expected,
+                "{}",
+                selector
+            );
+        }
+    }
+
+    #[test]
+    fn sort_by_specificity() {
+        let mut selectors: Vec<Selector> = ["#a", "p", ".b.c", "p.b"]
+            .iter()
+            .map(|s| Selector::parse(s).unwrap())
+            .collect();
+        selectors.sort_by(Selector::cmp_specificity);
+
+        assert_eq!(
+            selectors.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+            vec!["p", "p.b", ".b.c", "#a"]
+        );
+    }
+
+    #[test]
+    fn escapes() {
+        let selector = Selector::parse(r#".md\:flex#\31 23"#).unwrap();
+
+        assert_eq!(
+            selector,
+            Selector::Chain(vec![
+                Selector::Class("md:flex".to_string()),
+                Selector::Id("123".to_string()),
+            ])
+        );
+        assert_eq!(selector.to_string(), r#".md\:flex#\31 23"#);
+        assert_eq!(
+            Selector::parse(r#"[data-x="a\"b"]"#).unwrap().to_string(),
+            r#"[data-x="a\"b"]"#
+        );
+        assert_eq!(
+            Selector::Class("a\"b c".to_string()).to_string(),
+            r#".a\"b\ c"#
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Selector::parse("a[href!=x]"),
+            Err(Error::Css(
+                "unsupported attribute selector at byte 6".to_string()
+            ))
+        );
+        assert_eq!(
+            Selector::parse("a >"),
+            Err(Error::Css("expected a selector at byte 3".to_string()))
+        );
+        assert_eq!(
+            Selector::parse("a)"),
+            Err(Error::Css("unexpected character at byte 1".to_string()))
+        );
+    }
+
+    #[test]
+    fn attribute_case_flags() {
+        assert_eq!(
+            Selector::parse("[type=email I]").unwrap(),
+            Selector::AttributeValue(
+                "type".to_string(),
+                "email".to_string(),
+                AttributeCase::Insensitive
+            )
+        );
+
+        // the flag can be left out of the structured form
+        let rule: Rule = serde_json::from_str(
+            r#"{"selector": {"Chain": [{"Tag": "input"}, {"AttributeValue": ["type", "email"]}, {"AttributeValue": ["name", "x", "Sensitive"]}]}, "declarations": []}"#,
+        )
+        .unwrap();
+        assert_eq!(rule.to_string(), "input[type=\"email\"][name=\"x\" s]{}");
+    }
+
+    #[test]
+    fn rules_accept_selector_strings() {
+        let rule: Rule =
+            serde_json::from_str(r#"{"selector": "nav a:hover", "declarations": []}"#).unwrap();
+
+        assert_eq!(rule.to_string(), "nav a:hover{}");
+    }
+}
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::de::DeserializeOwned;
 
 use garnish_lang::compiler::lex::lex;
 use garnish_lang::compiler::parse::parse;
@@ -8,19 +8,24 @@ use garnish_lang::{EmptyContext, GarnishData, GarnishRuntime};
 use serde_garnish::GarnishDataDeserializer;
 
 use crate::css::RuleSet;
+use crate::error::Error;
 use crate::html::*;
 
-pub fn make_html_from_garnish(input: &str) -> Result<Node, String> {
-    let tokens = lex(input)?;
-    let parsed = parse(&tokens)?;
+fn make_from_garnish<T: DeserializeOwned>(input: &str) -> Result<T, Error> {
+    let tokens = lex(input).map_err(|e| Error::Garnish(e.into()))?;
+    let parsed = parse(&tokens).map_err(|e| Error::Garnish(e.into()))?;
     let mut data = SimpleGarnishData::new();
-    build_with_data(parsed.get_root(), parsed.get_nodes().clone(), &mut data)?;
+    build_with_data(parsed.get_root(), parsed.get_nodes().clone(), &mut data)
+        .map_err(|e| Error::Garnish(e.into()))?;
     let mut runtime = SimpleGarnishRuntime::new(data);
-    runtime.get_data_mut().push_value_stack(0)?;
+    runtime
+        .get_data_mut()
+        .push_value_stack(0)
+        .map_err(|e| Error::Garnish(e.into()))?;
 
     loop {
         match runtime.execute_current_instruction::<EmptyContext>(None) {
-            Err(e) => Err(e)?,
+            Err(e) => Err(Error::Garnish(e.into()))?,
             Ok(data) => match data.get_state() {
                 SimpleRuntimeState::Running => (),
                 SimpleRuntimeState::End => break,
@@ -30,44 +35,32 @@ pub fn make_html_from_garnish(input: &str) -> Result<Node, String> {
 
     let mut deserializer = GarnishDataDeserializer::new(runtime.get_data_mut());
 
-    let result = Node::deserialize(&mut deserializer).map_err(|e| e.to_string())?;
+    let result = T::deserialize(&mut deserializer).map_err(|e| match e.message() {
+        Some(m) => Error::Deserialize(m.clone()),
+        None => Error::Deserialize(e.to_string()),
+    })?;
 
     return Ok(result);
 }
 
-pub fn make_css_from_garnish(input: &str) -> Result<RuleSet, String> {
-    let tokens = lex(input)?;
-    let parsed = parse(&tokens)?;
-    let mut data = SimpleGarnishData::new();
-    build_with_data(parsed.get_root(), parsed.get_nodes().clone(), &mut data)?;
-    let mut runtime = SimpleGarnishRuntime::new(data);
-    runtime.get_data_mut().push_value_stack(0)?;
-
-    loop {
-        match runtime.execute_current_instruction::<EmptyContext>(None) {
-            Err(e) => Err(e)?,
-            Ok(data) => match data.get_state() {
-                SimpleRuntimeState::Running => (),
-                SimpleRuntimeState::End => break,
-            },
-        }
-    }
-
-    let mut deserializer = GarnishDataDeserializer::new(runtime.get_data_mut());
-
-    let result = RuleSet::deserialize(&mut deserializer).map_err(|e| match e.message() {
-        Some(m) => m.clone(),
-        None => e.to_string(),
-    })?;
+// scripts producing a list of nodes are read as a fragment
+pub fn make_html_from_garnish(input: &str) -> Result<Node, Error> {
+    make_from_garnish::<Node>(input).or_else(|node_error| {
+        make_from_garnish::<Vec<Node>>(input)
+            .map(Node::Fragment)
+            .map_err(|_| node_error)
+    })
+}
 
-    return Ok(result);
+pub fn make_css_from_garnish(input: &str) -> Result<RuleSet, Error> {
+    make_from_garnish(input)
 }
 
 #[cfg(test)]
 mod test {
     use crate::css::{
-        Declaration, DeclarationValue, MediaCondition, MediaConstraint, MediaFeature, MediaQuery,
-        Rule, RuleSet, Selector,
+        AttributeCase, Declaration, DeclarationValue, MediaCondition, MediaConstraint,
+        MediaFeature, MediaQuery, Rule, RuleSet, Selector,
     };
     use crate::html::Node;
     use crate::{make_css_from_garnish, make_html_from_garnish};
@@ -80,6 +73,20 @@ mod test {
         assert_eq!(output, Node::Text("This is a text node".to_string()))
     }
 
+    #[test]
+    fn make_fragment() {
+        let input = "(;Node::Text \"first\"), (;Node::Comment \"second\")";
+        let output = make_html_from_garnish(input).unwrap();
+
+        assert_eq!(
+            output,
+            Node::Fragment(vec![
+                Node::Text("first".to_string()),
+                Node::Comment("second".to_string())
+            ])
+        )
+    }
+
     #[test]
     fn make_rule_set() {
         let input = "
@@ -123,8 +130,8 @@ mod test {
         (
             ;MediaCondition::And
             (
-                (;property = \"max-width\" ;value = \"1000px\"),
-                (;property = \"orientation\" ;value = \"landscape\")
+                (;MediaCondition::Lone (;property = \"max-width\" ;value = \"1000px\")),
+                (;MediaCondition::Lone (;property = \"orientation\" ;value = \"landscape\")),
             )
         ),
     ),
@@ -160,8 +167,8 @@ mod test {
                 (
                     ;MediaCondition::Or
                     (
-                        (;property = \"max-width\" ;value = \"1000px\"),
-                        (;property = \"orientation\" ;value = \"landscape\")
+                        (;MediaCondition::Lone (;property = \"max-width\" ;value = \"1000px\")),
+                        (;MediaCondition::Lone (;property = \"orientation\" ;value = \"landscape\")),
                     )
                 ),
             )
@@ -213,21 +220,62 @@ mod test {
                     Some(MediaQuery::new(
                         MediaConstraint::Not,
                         "print".to_string(),
-                        vec![MediaCondition::Or(
-                            MediaFeature::new("max-width".to_string(), "1000px".to_string()),
-                            MediaFeature::new("orientation".to_string(), "landscape".to_string())
-                        )]
+                        vec![MediaCondition::Or(vec![
+                            MediaCondition::Lone(MediaFeature::new("max-width".to_string(), "1000px".to_string())),
+                            MediaCondition::Lone(MediaFeature::new("orientation".to_string(), "landscape".to_string()))
+                        ])]
                     ))
                 )],
                 Some(MediaQuery::new(
                     MediaConstraint::Only,
                     "screen".to_string(),
-                    vec![MediaCondition::And(
-                        MediaFeature::new("max-width".to_string(), "1000px".to_string()),
-                        MediaFeature::new("orientation".to_string(), "landscape".to_string())
-                    )]
+                    vec![MediaCondition::And(vec![
+                        MediaCondition::Lone(MediaFeature::new("max-width".to_string(), "1000px".to_string())),
+                        MediaCondition::Lone(MediaFeature::new("orientation".to_string(), "landscape".to_string()))
+                    ])]
                 ))
             )
         )
     }
+
+    #[test]
+    fn make_attribute_selectors_with_case() {
+        let input = "
+;rules = (
+    (
+        ;selector = (
+            ;Selector::Chain (
+                (;Selector::AttributeValue (\"type\", \"email\", ;AttributeCase::Insensitive)),
+                (;Selector::AttributePrefix (\"name\", \"user\")),
+            )
+        ),
+        ;declarations = (),
+    ),
+),";
+        let output = make_css_from_garnish(input).unwrap();
+
+        assert_eq!(
+            output,
+            RuleSet::new(
+                vec![Rule::new(
+                    Selector::Chain(vec![
+                        Selector::AttributeValue(
+                            "type".to_string(),
+                            "email".to_string(),
+                            AttributeCase::Insensitive
+                        ),
+                        Selector::AttributePrefix(
+                            "name".to_string(),
+                            "user".to_string(),
+                            AttributeCase::Default
+                        ),
+                    ]),
+                    vec![],
+                    vec![]
+                )],
+                vec![],
+                None
+            )
+        )
+    }
 }
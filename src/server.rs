@@ -0,0 +1,290 @@
+use std::cell::RefCell;
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::error::Error;
+use crate::html::{Attribute, Node};
+use crate::serialize::{make_css_from_garnish, make_html_from_garnish};
+
+const LIVE_RELOAD_PATH: &str = "/__livereload";
+
+// polls the server for the scripts' version and reloads the page when it changes
+const LIVE_RELOAD_SCRIPT: &str = "(function(){var v=null;setInterval(function(){\
+fetch('/__livereload').then(function(r){return r.text()}).then(function(t){\
+if(v!==null&&v!==t){location.reload()}v=t}).catch(function(){})},1000)})();";
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum ScriptKind {
+    Html,
+    Css,
+}
+
+#[derive(Debug)]
+struct Route {
+    path: String,
+    kind: ScriptKind,
+    script: PathBuf,
+    // (modified time the output was rendered from, rendered output)
+    cache: RefCell<Option<(SystemTime, Result<String, Error>)>>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DevResponse {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl DevResponse {
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        self.content_type
+    }
+
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+}
+
+#[derive(Debug)]
+pub struct DevServer {
+    address: String,
+    routes: Vec<Route>,
+}
+
+impl DevServer {
+    pub fn new(address: String) -> Self {
+        Self {
+            address,
+            routes: vec![],
+        }
+    }
+
+    fn add_route(&mut self, path: String, kind: ScriptKind, script: PathBuf) {
+        self.routes.push(Route {
+            path,
+            kind,
+            script,
+            cache: RefCell::new(None),
+        });
+    }
+
+    // garnish script producing a Node, served as html with the live reload snippet added
+    pub fn add_page(&mut self, path: String, script: PathBuf) {
+        self.add_route(path, ScriptKind::Html, script);
+    }
+
+    // garnish script producing a RuleSet, served as css
+    pub fn add_stylesheet(&mut self, path: String, script: PathBuf) {
+        self.add_route(path, ScriptKind::Css, script);
+    }
+
+    // changes whenever any registered script is modified
+    fn version(&self) -> String {
+        let latest = self
+            .routes
+            .iter()
+            .filter_map(|r| fs::metadata(&r.script).and_then(|m| m.modified()).ok())
+            .max()
+            .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+
+        latest.to_string()
+    }
+
+    fn render(route: &Route) -> Result<String, Error> {
+        let modified = fs::metadata(&route.script)
+            .and_then(|m| m.modified())
+            .map_err(|e| Error::io(route.script.display().to_string(), e))?;
+
+        // scripts are only recompiled when they change on disk
+        if let Some((rendered_from, output)) = route.cache.borrow().as_ref() {
+            if *rendered_from == modified {
+                return output.clone();
+            }
+        }
+
+        let output = fs::read_to_string(&route.script)
+            .map_err(|e| Error::io(route.script.display().to_string(), e))
+            .and_then(|source| match route.kind {
+                ScriptKind::Html => make_html_from_garnish(&source)
+                    .map(|node| with_live_reload(node).to_string()),
+                ScriptKind::Css => make_css_from_garnish(&source).map(|set| set.to_string()),
+            });
+
+        *route.cache.borrow_mut() = Some((modified, output.clone()));
+        output
+    }
+
+    pub fn respond(&self, url: &str) -> DevResponse {
+        let path = url.split(['?', '#']).next().unwrap_or(url);
+
+        if path == LIVE_RELOAD_PATH {
+            return DevResponse {
+                status: 200,
+                content_type: "text/plain; charset=utf-8",
+                body: self.version(),
+            };
+        }
+
+        let route = match self.routes.iter().find(|r| r.path == path) {
+            Some(route) => route,
+            None => {
+                return DevResponse {
+                    status: 404,
+                    content_type: "text/plain; charset=utf-8",
+                    body: format!("no script registered for {}", path),
+                }
+            }
+        };
+
+        match (Self::render(route), route.kind) {
+            (Ok(body), ScriptKind::Html) => DevResponse {
+                status: 200,
+                content_type: "text/html; charset=utf-8",
+                body,
+            },
+            (Ok(body), ScriptKind::Css) => DevResponse {
+                status: 200,
+                content_type: "text/css; charset=utf-8",
+                body,
+            },
+            // errors are shown as a page that keeps polling so fixing the script reloads it
+            (Err(error), _) => DevResponse {
+                status: 500,
+                content_type: "text/html; charset=utf-8",
+                body: with_live_reload(Node::element(
+                    "pre".to_string(),
+                    vec![],
+                    vec![Node::text(error.to_string())],
+                ))
+                .to_string(),
+            },
+        }
+    }
+
+    // blocks, serving requests until the process is stopped
+    pub fn run(self) -> Result<(), Error> {
+        let server = tiny_http::Server::http(&self.address)
+            .map_err(|e| Error::io(self.address.clone(), std::io::Error::other(e)))?;
+
+        for request in server.incoming_requests() {
+            let response = self.respond(request.url());
+            let header = tiny_http::Header::from_bytes(&b"Content-Type"[..], response.content_type)
+                .map_err(|_| {
+                    Error::io(
+                        response.content_type,
+                        std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid header"),
+                    )
+                })?;
+
+            // a client going away mid response should not stop the server
+            let _ = request.respond(
+                tiny_http::Response::from_string(response.body)
+                    .with_status_code(response.status)
+                    .with_header(header),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn live_reload_node() -> Node {
+    Node::element(
+        "script".to_string(),
+        vec![Attribute::new(
+            "data-garnish".to_string(),
+            "live-reload".to_string(),
+        )],
+        vec![Node::text(LIVE_RELOAD_SCRIPT.to_string())],
+    )
+}
+
+fn body_children(node: &mut Node) -> Option<&mut Vec<Node>> {
+    match node {
+        Node::Element { tag, children, .. } => match tag.eq_ignore_ascii_case("body") {
+            true => Some(children),
+            false => children.iter_mut().find_map(body_children),
+        },
+        Node::Fragment(children) => children.iter_mut().find_map(body_children),
+        _ => None,
+    }
+}
+
+// appends the snippet to the body, or wraps fragments without one
+fn with_live_reload(node: Node) -> Node {
+    let mut node = node;
+    match body_children(&mut node) {
+        Some(children) => {
+            children.push(live_reload_node());
+            node
+        }
+        None => Node::element(
+            "body".to_string(),
+            vec![],
+            vec![node, live_reload_node()],
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::html::Node;
+    use crate::server::{with_live_reload, DevServer};
+
+    #[test]
+    fn live_reload_added_to_body() {
+        let page = Node::element(
+            "html".to_string(),
+            vec![],
+            vec![Node::element("body".to_string(), vec![], vec![])],
+        );
+
+        assert!(with_live_reload(page)
+            .to_string()
+            .starts_with("<html><body><script data-garnish=\"live-reload\">"));
+    }
+
+    #[test]
+    fn live_reload_wraps_fragments() {
+        let fragment = Node::element("main".to_string(), vec![], vec![]);
+
+        assert!(with_live_reload(fragment)
+            .to_string()
+            .starts_with("<body><main></main><script"));
+    }
+
+    #[test]
+    fn unknown_path() {
+        let server = DevServer::new("127.0.0.1:0".to_string());
+        let response = server.respond("/missing?x=1");
+
+        assert_eq!(response.status(), 404);
+        assert_eq!(response.body(), "no script registered for /missing");
+    }
+
+    #[test]
+    fn missing_script_is_an_error_page() {
+        let mut server = DevServer::new("127.0.0.1:0".to_string());
+        server.add_page("/".to_string(), "/nonexistent/index.garnish".into());
+
+        let response = server.respond("/");
+        assert_eq!(response.status(), 500);
+        assert!(response
+            .body()
+            .starts_with("<body><pre>io error: /nonexistent/index.garnish:"));
+    }
+
+    #[test]
+    fn version_is_stable_without_changes() {
+        let server = DevServer::new("127.0.0.1:0".to_string());
+
+        assert_eq!(server.respond("/__livereload").body(), "0");
+    }
+}
@@ -0,0 +1,77 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::html::Node;
+
+impl Node {
+    pub fn slot(name: String) -> Self {
+        Self::Slot(name)
+    }
+
+    // names of the slots in the tree, in document order
+    pub fn slot_names(&self) -> Vec<&str> {
+        self.iter()
+            .filter_map(|n| match n {
+                Node::Slot(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+// slots without content are left in place so they can be filled later, slots inside the filled
+// content are not filled again
+pub fn fill_slots(node: &mut Node, content: &BTreeMap<String, Node>) {
+    match node {
+        Node::Slot(name) => {
+            if let Some(filling) = content.get(name.as_str()) {
+                *node = filling.clone();
+            }
+        }
+        node => {
+            if let Some(children) = node.children_mut() {
+                children.iter_mut().for_each(|c| fill_slots(c, content));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::collections::BTreeMap;
+
+    use crate::html::Node;
+    use crate::slots::fill_slots;
+
+    #[test]
+    fn layout_and_partials() {
+        let mut layout = crate::html! {
+            body {
+                header { (Node::slot("nav".to_string())) }
+                main { (Node::slot("content".to_string())) }
+                (Node::fragment(vec![Node::slot("footer".to_string())]))
+            }
+        };
+        let mut content = BTreeMap::new();
+        content.insert(
+            "nav".to_string(),
+            crate::html! { nav { (Node::slot("content".to_string())) } },
+        );
+        content.insert(
+            "content".to_string(),
+            Node::fragment(vec![crate::html! { h1 { "Title" } }]),
+        );
+
+        assert_eq!(layout.slot_names(), vec!["nav", "content", "footer"]);
+
+        fill_slots(&mut layout, &content);
+
+        assert_eq!(layout.slot_names(), vec!["content", "footer"]);
+        assert_eq!(
+            layout.to_string(),
+            "<body><header><nav></nav></header><main><h1>Title</h1></main></body>"
+        );
+        assert_eq!(layout.estimated_len(), layout.to_string().len());
+    }
+}
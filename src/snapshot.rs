@@ -0,0 +1,379 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::css::{Rule, RuleSet, Selector};
+use crate::html::{is_void_element, Node};
+
+// copy of the tree with every element's attributes sorted by name
+pub fn normalize_node(node: &Node) -> Node {
+    match node {
+        Node::Element {
+            tag,
+            attributes,
+            children,
+            self_closing,
+        } => {
+            let mut attributes = attributes.clone();
+            attributes.sort_by(|a, b| a.name().cmp(b.name()));
+
+            Node::Element {
+                tag: tag.clone(),
+                attributes,
+                children: children.iter().map(normalize_node).collect(),
+                self_closing: *self_closing,
+            }
+        }
+        Node::Fragment(children) => Node::Fragment(children.iter().map(normalize_node).collect()),
+        _ => node.clone(),
+    }
+}
+
+fn normalize_rule(rule: &Rule) -> Rule {
+    let mut sub_rules = rule
+        .sub_rules()
+        .iter()
+        .map(normalize_rule)
+        .collect::<Vec<Rule>>();
+    sub_rules.sort_by(|a, b| a.selector().cmp(b.selector()));
+
+    Rule::new(
+        rule.selector().clone(),
+        rule.declarations().to_vec(),
+        sub_rules,
+    )
+}
+
+// copy of the rule set with rules and nested sets in a stable order
+// declaration order is kept since later declarations override earlier ones
+pub fn normalize_rule_set(set: &RuleSet) -> RuleSet {
+    let mut rules = set
+        .rules()
+        .iter()
+        .map(normalize_rule)
+        .collect::<Vec<Rule>>();
+    rules.sort_by(|a, b| a.selector().cmp(b.selector()));
+
+    let mut sub_sets = set
+        .sub_sets()
+        .iter()
+        .map(normalize_rule_set)
+        .collect::<Vec<RuleSet>>();
+    sub_sets.sort_by(|a, b| a.media_query().cmp(&b.media_query()));
+
+    set.at_rules().iter().cloned().fold(
+        RuleSet::new(rules, sub_sets, set.media_query().cloned()),
+        RuleSet::at_rule,
+    )
+}
+
+fn indent(output: &mut String, depth: usize) {
+    for _ in 0..depth {
+        output.push_str("  ");
+    }
+}
+
+fn pretty_node_into(node: &Node, depth: usize, output: &mut String) {
+    match node {
+        Node::Element {
+            tag,
+            attributes,
+            children,
+            self_closing,
+        } => {
+            indent(output, depth);
+            output.push('<');
+            output.push_str(tag);
+            for attribute in attributes.iter().filter(|a| !a.is_omitted()) {
+                output.push(' ');
+                output.push_str(&attribute.to_string());
+            }
+
+            // void and self closing elements have nothing to close, same as when serialized
+            if is_void_element(tag) {
+                output.push_str(">\n");
+                return;
+            }
+            if *self_closing && children.is_empty() {
+                output.push_str("/>\n");
+                return;
+            }
+            output.push('>');
+
+            match children.is_empty() {
+                true => (),
+                false => {
+                    output.push('\n');
+                    for child in children {
+                        pretty_node_into(child, depth + 1, output);
+                    }
+                    indent(output, depth);
+                }
+            }
+
+            output.push_str("</");
+            output.push_str(tag);
+            output.push_str(">\n");
+        }
+        // siblings are written at the depth of the fragment
+        Node::Fragment(children) => {
+            for child in children {
+                pretty_node_into(child, depth, output);
+            }
+        }
+        _ => {
+            indent(output, depth);
+            output.push_str(&node.to_string());
+            output.push('\n');
+        }
+    }
+}
+
+// normalized, indented markup with one node per line, meant for comparing not serving
+pub fn pretty_node(node: &Node) -> String {
+    let mut output = String::new();
+    pretty_node_into(&normalize_node(node), 0, &mut output);
+    output
+}
+
+fn pretty_rule_into(rule: &Rule, selector: &Selector, depth: usize, output: &mut String) {
+    indent(output, depth);
+    output.push_str(&selector.to_string());
+    output.push_str(" {\n");
+    for declaration in rule.declarations() {
+        indent(output, depth + 1);
+        output.push_str(declaration.property());
+        output.push_str(": ");
+        output.push_str(&declaration.value().to_string());
+        output.push_str(";\n");
+    }
+    indent(output, depth);
+    output.push_str("}\n");
+
+    for sub_rule in rule.sub_rules() {
+        pretty_rule_into(
+            sub_rule,
+            &sub_rule.selector().nested_in(selector),
+            depth,
+            output,
+        );
+    }
+}
+
+fn pretty_rule_set_into(set: &RuleSet, depth: usize, output: &mut String) {
+    let depth = match set.media_query() {
+        Some(query) => {
+            indent(output, depth);
+            output.push_str(&query.to_string());
+            output.push_str(" {\n");
+            depth + 1
+        }
+        None => depth,
+    };
+
+    for rule in set.rules() {
+        pretty_rule_into(rule, rule.selector(), depth, output);
+    }
+
+    for sub_set in set.sub_sets() {
+        pretty_rule_set_into(sub_set, depth, output);
+    }
+
+    if set.media_query().is_some() {
+        indent(output, depth - 1);
+        output.push_str("}\n");
+    }
+}
+
+// normalized, indented css with one declaration per line, meant for comparing not serving
+pub fn pretty_rule_set(set: &RuleSet) -> String {
+    let mut output = String::new();
+    pretty_rule_set_into(&normalize_rule_set(set), 0, &mut output);
+    output
+}
+
+// line based diff of expected against actual, None when they are equal
+// lines only in expected are prefixed with "-", lines only in actual with "+"
+pub fn diff_lines(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+
+    let expected = expected.lines().collect::<Vec<&str>>();
+    let actual = actual.lines().collect::<Vec<&str>>();
+
+    // longest common subsequence lengths of the remaining lines
+    let mut lengths = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lengths[i][j] = match expected[i] == actual[j] {
+                true => lengths[i + 1][j + 1] + 1,
+                false => lengths[i + 1][j].max(lengths[i][j + 1]),
+            };
+        }
+    }
+
+    let mut output = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            output.push_str(&format!("  {}\n", expected[i]));
+            i += 1;
+            j += 1;
+        } else if j < actual.len()
+            && (i == expected.len() || lengths[i][j + 1] >= lengths[i + 1][j])
+        {
+            output.push_str(&format!("+ {}\n", actual[j]));
+            j += 1;
+        } else {
+            output.push_str(&format!("- {}\n", expected[i]));
+            i += 1;
+        }
+    }
+
+    // inputs that only differ in trailing newlines produce no marked lines
+    match output.lines().any(|l| !l.starts_with("  ")) {
+        true => Some(output),
+        false => Some(String::from("- (trailing newline differs)\n")),
+    }
+}
+
+fn assert_snapshot(actual: &str, expected: &str) {
+    if let Some(diff) = diff_lines(expected, actual) {
+        panic!("snapshot mismatch (- expected, + actual):\n{}", diff);
+    }
+}
+
+// compares the pretty form of the node against an expected pretty snapshot, panicking with a diff on mismatch
+pub fn assert_html_snapshot(node: &Node, expected: &str) {
+    assert_snapshot(&pretty_node(node), expected);
+}
+
+// compares the pretty form of the rule set against an expected pretty snapshot, panicking with a diff on mismatch
+pub fn assert_css_snapshot(set: &RuleSet, expected: &str) {
+    assert_snapshot(&pretty_rule_set(set), expected);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::css::{Declaration, DeclarationValue, Rule, RuleSet, Selector};
+    use crate::html::{Attribute, Node};
+    use crate::snapshot::{
+        assert_css_snapshot, assert_html_snapshot, diff_lines, normalize_node, pretty_node,
+        pretty_rule_set,
+    };
+
+    fn declaration(property: &str, value: &str) -> Declaration {
+        Declaration::new(
+            property.to_string(),
+            DeclarationValue::Basic(value.to_string()),
+        )
+    }
+
+    #[test]
+    fn normalize_sorts_attributes() {
+        let node = Node::element(
+            "div".to_string(),
+            vec![
+                Attribute::new("id".to_string(), "main".to_string()),
+                Attribute::new("class".to_string(), "wide".to_string()),
+            ],
+            vec![],
+        );
+
+        assert_eq!(
+            normalize_node(&node).to_string(),
+            "<div class=\"wide\" id=\"main\"></div>"
+        );
+    }
+
+    #[test]
+    fn pretty_node_indents_children() {
+        let node = Node::element(
+            "body".to_string(),
+            vec![],
+            vec![
+                Node::element(
+                    "h1".to_string(),
+                    vec![],
+                    vec![Node::text("Heading".to_string())],
+                ),
+                Node::element("hr".to_string(), vec![], vec![]),
+            ],
+        );
+
+        assert_eq!(
+            pretty_node(&node),
+            "<body>\n  <h1>\n    Heading\n  </h1>\n  <hr>\n</body>\n"
+        );
+    }
+
+    #[test]
+    fn pretty_rule_set_sorts_rules_and_keeps_declarations() {
+        let set = RuleSet::new(
+            vec![
+                Rule::new(
+                    Selector::Class("b".to_string()),
+                    vec![declaration("color", "red"), declaration("color", "blue")],
+                    vec![],
+                ),
+                Rule::new(
+                    Selector::Class("a".to_string()),
+                    vec![declaration("margin", "0")],
+                    vec![],
+                ),
+            ],
+            vec![],
+            None,
+        );
+
+        assert_eq!(
+            pretty_rule_set(&set),
+            ".a {\n  margin: 0;\n}\n.b {\n  color: red;\n  color: blue;\n}\n"
+        );
+    }
+
+    #[test]
+    fn diff_of_equal_input_is_none() {
+        assert_eq!(diff_lines("a\nb\n", "a\nb\n"), None);
+    }
+
+    #[test]
+    fn diff_marks_changed_lines() {
+        assert_eq!(
+            diff_lines("a\nb\nc\n", "a\nx\nc\n"),
+            Some("  a\n+ x\n- b\n  c\n".to_string())
+        );
+    }
+
+    #[test]
+    fn snapshot_ignores_attribute_order() {
+        let node = Node::element(
+            "a".to_string(),
+            vec![
+                Attribute::new("href".to_string(), "/".to_string()),
+                Attribute::new("class".to_string(), "home".to_string()),
+            ],
+            vec![],
+        );
+
+        assert_html_snapshot(&node, "<a class=\"home\" href=\"/\"></a>\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "snapshot mismatch")]
+    fn css_snapshot_mismatch_panics() {
+        let set = RuleSet::new(
+            vec![Rule::new(
+                Selector::Tag("p".to_string()),
+                vec![declaration("color", "red")],
+                vec![],
+            )],
+            vec![],
+            None,
+        );
+
+        assert_css_snapshot(&set, "p {\n  color: blue;\n}\n");
+    }
+}
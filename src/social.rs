@@ -0,0 +1,198 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::head::Head;
+use crate::html::{Attribute, Node};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum CardType {
+    #[default]
+    Summary,
+    SummaryLargeImage,
+    App,
+    Player,
+}
+
+impl CardType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CardType::Summary => "summary",
+            CardType::SummaryLargeImage => "summary_large_image",
+            CardType::App => "app",
+            CardType::Player => "player",
+        }
+    }
+}
+
+// open graph and twitter card metadata for link previews
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct SocialMeta {
+    title: String,
+    description: Option<String>,
+    image: Option<String>,
+    image_alt: Option<String>,
+    url: Option<String>,
+    site_name: Option<String>,
+    og_type: Option<String>,
+    card: CardType,
+    twitter_site: Option<String>,
+}
+
+fn meta(attribute: &str, name: &str, content: &str) -> Node {
+    Node::element(
+        "meta".to_string(),
+        vec![
+            Attribute::new(attribute.to_string(), name.to_string()),
+            Attribute::new("content".to_string(), content.to_string()),
+        ],
+        vec![],
+    )
+}
+
+impl SocialMeta {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    // should be an absolute url, crawlers do not resolve relative ones
+    pub fn image(mut self, image: impl Into<String>) -> Self {
+        self.image = Some(image.into());
+        self
+    }
+
+    pub fn image_alt(mut self, alt: impl Into<String>) -> Self {
+        self.image_alt = Some(alt.into());
+        self
+    }
+
+    // canonical url of the page
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    pub fn site_name(mut self, site_name: impl Into<String>) -> Self {
+        self.site_name = Some(site_name.into());
+        self
+    }
+
+    // og:type, website when not set
+    pub fn og_type(mut self, og_type: impl Into<String>) -> Self {
+        self.og_type = Some(og_type.into());
+        self
+    }
+
+    pub fn card(mut self, card: CardType) -> Self {
+        self.card = card;
+        self
+    }
+
+    // @handle of the site's twitter account
+    pub fn twitter_site(mut self, handle: impl Into<String>) -> Self {
+        self.twitter_site = Some(handle.into());
+        self
+    }
+
+    // og:* use the property attribute and twitter:* the name attribute, as each spec expects
+    pub fn into_nodes(self) -> Vec<Node> {
+        let mut nodes = vec![
+            meta("property", "og:title", &self.title),
+            meta(
+                "property",
+                "og:type",
+                self.og_type.as_deref().unwrap_or("website"),
+            ),
+        ];
+        let og = [
+            ("og:description", &self.description),
+            ("og:image", &self.image),
+            ("og:image:alt", &self.image_alt),
+            ("og:url", &self.url),
+            ("og:site_name", &self.site_name),
+        ];
+        for (name, value) in og {
+            if let Some(value) = value {
+                nodes.push(meta("property", name, value));
+            }
+        }
+
+        nodes.push(meta("name", "twitter:card", self.card.as_str()));
+        nodes.push(meta("name", "twitter:title", &self.title));
+        let twitter = [
+            ("twitter:description", &self.description),
+            ("twitter:image", &self.image),
+            ("twitter:image:alt", &self.image_alt),
+            ("twitter:site", &self.twitter_site),
+        ];
+        for (name, value) in twitter {
+            if let Some(value) = value {
+                nodes.push(meta("name", name, value));
+            }
+        }
+
+        if let Some(description) = &self.description {
+            nodes.push(meta("name", "description", description));
+        }
+        if let Some(url) = &self.url {
+            nodes.push(Node::element(
+                "link".to_string(),
+                vec![
+                    Attribute::new("rel".to_string(), "canonical".to_string()),
+                    Attribute::new("href".to_string(), url.clone()),
+                ],
+                vec![],
+            ));
+        }
+        nodes
+    }
+
+    // replaces social metadata already in the head
+    pub fn apply(self, head: &mut Head) {
+        self.into_nodes().into_iter().for_each(|n| head.add(n));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::head::Head;
+    use crate::html::Node;
+    use crate::social::{CardType, SocialMeta};
+
+    #[test]
+    fn minimal() {
+        let nodes = SocialMeta::new("Home").into_nodes();
+
+        assert_eq!(
+            Node::fragment(nodes).to_string(),
+            "<meta property=\"og:title\" content=\"Home\"><meta property=\"og:type\" content=\"website\"><meta name=\"twitter:card\" content=\"summary\"><meta name=\"twitter:title\" content=\"Home\">"
+        );
+    }
+
+    #[test]
+    fn full_set_into_head() {
+        let mut head = Head::new();
+        head.add_meta("description".to_string(), "Old".to_string());
+        SocialMeta::new("Post \"1\"")
+            .description("About things")
+            .image("https://x.test/a.png")
+            .url("https://x.test/post")
+            .og_type("article")
+            .card(CardType::SummaryLargeImage)
+            .twitter_site("@x")
+            .apply(&mut head);
+
+        assert_eq!(
+            head.into_node().to_string(),
+            "<head><meta name=\"description\" content=\"About things\"><meta property=\"og:title\" content=\"Post &quot;1&quot;\"><meta property=\"og:type\" content=\"article\"><meta property=\"og:description\" content=\"About things\"><meta property=\"og:image\" content=\"https://x.test/a.png\"><meta property=\"og:url\" content=\"https://x.test/post\"><meta name=\"twitter:card\" content=\"summary_large_image\"><meta name=\"twitter:title\" content=\"Post &quot;1&quot;\"><meta name=\"twitter:description\" content=\"About things\"><meta name=\"twitter:image\" content=\"https://x.test/a.png\"><meta name=\"twitter:site\" content=\"@x\"><link rel=\"canonical\" href=\"https://x.test/post\"></head>"
+        );
+    }
+}
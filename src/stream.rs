@@ -0,0 +1,37 @@
+use std::fmt;
+use std::io;
+
+// fmt::Write over an io::Write, holding on to the io error since fmt::Error can not carry it
+pub(crate) struct IoWriter<'a, W: io::Write> {
+    inner: &'a mut W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+// output is written in many small pieces, callers should pass a buffered writer for files and sockets
+pub(crate) fn write_io<W, F>(output: &mut W, render: F) -> io::Result<()>
+where
+    W: io::Write,
+    F: FnOnce(&mut IoWriter<'_, W>) -> fmt::Result,
+{
+    let mut writer = IoWriter {
+        inner: output,
+        error: None,
+    };
+
+    match render(&mut writer) {
+        Ok(()) => Ok(()),
+        Err(_) => Err(writer
+            .error
+            .take()
+            .unwrap_or_else(|| io::Error::other("formatting error"))),
+    }
+}
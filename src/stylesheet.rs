@@ -0,0 +1,1443 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write;
+use serde::{Deserialize, Serialize};
+
+use crate::at_rule::{
+    layer_order_len, write_layer_order, AtRule, AtRuleBlock, ContainerQuery, FontFace, FontSource,
+    Keyframe, KeyframeSelector, Keyframes, MarginBox, PagePseudoClass, PageRule, PageSelector,
+};
+use crate::color::Color;
+use crate::css::{
+    css_escaped_len, unescape, write_css_escaped, CssFormat, Declaration, DeclarationValue,
+    MediaCondition, MediaConstraint, MediaFeature, MediaQuery, Rule, RuleSet, Selector, Separator,
+};
+use crate::error::Error;
+use crate::values::Unit;
+
+// a declaration (up to ;) or a block with its prelude (selector or at-rule) and contents
+enum Item<'a> {
+    Declaration(&'a str),
+    Block(&'a str, &'a str),
+}
+
+fn error(message: String) -> Error {
+    Error::Css(message)
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut output = String::with_capacity(css.len());
+    let mut rest = css;
+    let mut quote = None;
+    while let Some(c) = rest.chars().next() {
+        match quote {
+            None if rest.starts_with("/*") => {
+                rest = rest[2..].find("*/").map_or("", |end| &rest[end + 4..]);
+                continue;
+            }
+            None if c == '"' || c == '\'' => quote = Some(c),
+            Some(q) if c == q => quote = None,
+            Some(_) if c == '\\' => {
+                // the escaped character is copied along with the backslash
+                let len = rest[1..].chars().next().map_or(0, char::len_utf8);
+                output.push_str(&rest[..1 + len]);
+                rest = &rest[1 + len..];
+                continue;
+            }
+            _ => (),
+        }
+        output.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    output
+}
+
+// positions of the separator outside of strings, parentheses and brackets
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let (mut parts, mut start, mut depth, mut quote) = (Vec::new(), 0, 0, None);
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match (quote, c) {
+            (Some(_), '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => (),
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[') => depth += 1,
+            (None, ')' | ']') => depth -= 1,
+            (None, c)
+                if depth == 0 && (c == separator || separator == ' ' && c.is_whitespace()) =>
+            {
+                parts.push(&text[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => (),
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+fn items(css: &str) -> Result<Vec<Item<'_>>, Error> {
+    let mut items = Vec::new();
+    let (mut start, mut depth, mut block_start, mut quote) = (0, 0, 0, None);
+    let mut escaped = false;
+    for (i, c) in css.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match (quote, c) {
+            (Some(_), '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => (),
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '{') => {
+                if depth == 0 {
+                    block_start = i;
+                }
+                depth += 1;
+            }
+            (None, '}') if depth == 0 => {
+                return Err(error(format!("unexpected }} at byte {}", i)));
+            }
+            (None, '}') => {
+                depth -= 1;
+                if depth == 0 {
+                    items.push(Item::Block(
+                        css[start..block_start].trim(),
+                        &css[block_start + 1..i],
+                    ));
+                    start = i + 1;
+                }
+            }
+            (None, ';') if depth == 0 => {
+                items.push(Item::Declaration(css[start..i].trim()));
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    if depth > 0 {
+        return Err(error(format!("unclosed block at byte {}", block_start)));
+    }
+    if !css[start..].trim().is_empty() {
+        items.push(Item::Declaration(css[start..].trim()));
+    }
+    items.retain(|item| !matches!(item, Item::Declaration("")));
+    Ok(items)
+}
+
+// comma lists of space lists of single values
+pub(crate) fn value(text: &str) -> Result<DeclarationValue, Error> {
+    for separator in [Separator::Comma, Separator::Space] {
+        let parts: Vec<&str> = split_top_level(text, separator.as_char())
+            .into_iter()
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .collect();
+        if parts.len() > 1 {
+            return Ok(DeclarationValue::List(
+                parts.into_iter().map(value).collect::<Result<_, _>>()?,
+                separator,
+            ));
+        }
+    }
+    single_value(text)
+}
+
+fn single_value(text: &str) -> Result<DeclarationValue, Error> {
+    let quoted = text.len() >= 2
+        && (text.starts_with('"') && text.ends_with('"')
+            || text.starts_with('\'') && text.ends_with('\''));
+    match text.find('(') {
+        // quotes are added back when the value is written
+        _ if quoted && text.contains(' ') => {
+            Ok(DeclarationValue::Basic(unescape(&text[1..text.len() - 1])))
+        }
+        Some(3) if !quoted && text[..4].eq_ignore_ascii_case("url(") && text.ends_with(')') => {
+            Ok(DeclarationValue::Url(unquote(&text[4..text.len() - 1])))
+        }
+        Some(3) if !quoted && text[..4].eq_ignore_ascii_case("var(") && text.ends_with(')') => {
+            var(&text[4..text.len() - 1])
+        }
+        Some(_) if is_color_function(text) => Ok(DeclarationValue::Color(Color::parse(text)?)),
+        Some(open) if !quoted && open > 0 && text.ends_with(')') => {
+            let arguments = &text[open + 1..text.len() - 1];
+            Ok(DeclarationValue::Function(
+                text[..open].to_string(),
+                split_top_level(arguments, ',')
+                    .into_iter()
+                    .map(|a| a.trim().to_string())
+                    .collect(),
+            ))
+        }
+        _ => Ok(typed_value(text)),
+    }
+}
+
+// the quotes are optional inside url(), local() and format()
+fn unquote(text: &str) -> String {
+    let text = text.trim();
+    match text.len() >= 2
+        && (text.starts_with('"') && text.ends_with('"')
+            || text.starts_with('\'') && text.ends_with('\''))
+    {
+        true => unescape(&text[1..text.len() - 1]),
+        false => unescape(text),
+    }
+}
+
+// var(--name) or var(--name, fallback) where the fallback may hold commas
+fn var(arguments: &str) -> Result<DeclarationValue, Error> {
+    let name = split_top_level(arguments, ',')[0];
+    let fallback = arguments[name.len()..].strip_prefix(',').map(str::trim);
+    Ok(DeclarationValue::Var(
+        unescape(name.trim()),
+        match fallback {
+            Some(fallback) => Some(Box::new(value(fallback)?)),
+            None => None,
+        },
+    ))
+}
+
+fn is_color_function(text: &str) -> bool {
+    ["rgb(", "rgba(", "hsl(", "hsla("]
+        .iter()
+        .any(|f| text.len() > f.len() && text[..f.len()].eq_ignore_ascii_case(f))
+}
+
+// numbers, percentages, dimensions, hex colors and keywords, anything else is kept as text
+pub(crate) fn typed_value(text: &str) -> DeclarationValue {
+    if text.starts_with('#') {
+        if let Ok(color) = Color::from_hex(text) {
+            return DeclarationValue::Color(color);
+        }
+    }
+    let number_end = text
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '+' | '-')))
+        .unwrap_or(text.len());
+    if let Ok(number) = text[..number_end].parse::<f64>() {
+        match &text[number_end..] {
+            "" => return DeclarationValue::number(number),
+            "%" => return DeclarationValue::percentage(number),
+            unit => {
+                if let Some(unit) = Unit::from_name(unit) {
+                    return DeclarationValue::length(number, unit);
+                }
+            }
+        }
+    }
+
+    let identifier = text.trim_start_matches('-');
+    match identifier.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && identifier
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+    {
+        true => DeclarationValue::keyword(text),
+        false => DeclarationValue::Basic(text.to_string()),
+    }
+}
+
+fn declaration(text: &str) -> Result<Declaration, Error> {
+    let (property, value_text) = text
+        .split_once(':')
+        .ok_or_else(|| error(format!("expected a declaration, found {}", text)))?;
+    let (property, value_text) = (property.trim(), value_text.trim());
+    if property.is_empty() || value_text.is_empty() {
+        return Err(error(format!("incomplete declaration {}", text)));
+    }
+    Ok(Declaration::new(property.to_string(), value(value_text)?))
+}
+
+fn media_feature(text: &str) -> Result<MediaFeature, Error> {
+    let inner = text
+        .trim()
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .ok_or_else(|| error(format!("expected a media feature, found {}", text)))?;
+    let (property, value) = inner
+        .split_once(':')
+        .ok_or_else(|| error(format!("media feature {} has no value", text.trim())))?;
+    Ok(MediaFeature::new(
+        property.trim().to_string(),
+        value.trim().to_string(),
+    ))
+}
+
+// the top level parts of a condition, parenthesized groups and the words between them
+fn condition_tokens(text: &str) -> Result<Vec<&str>, Error> {
+    let mut tokens = Vec::new();
+    let (mut depth, mut start) = (0, None);
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => {
+                if depth == 0 {
+                    if let Some(start) = start {
+                        tokens.push(&text[start..i]);
+                    }
+                    start = Some(i);
+                }
+                depth += 1;
+            }
+            ')' if depth == 0 => return Err(error(format!("unexpected ) in {}", text.trim()))),
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    tokens.extend(start.take().map(|start| &text[start..=i]));
+                }
+            }
+            c if depth == 0 && c.is_whitespace() => {
+                tokens.extend(start.take().map(|start| &text[start..i]));
+            }
+            _ if depth == 0 && start.is_none() => start = Some(i),
+            _ => (),
+        }
+    }
+    if depth > 0 {
+        return Err(error(format!("unclosed ( in {}", text.trim())));
+    }
+    tokens.extend(start.map(|start| &text[start..]));
+    Ok(tokens)
+}
+
+// not (condition), or (condition) joined by only and or only or
+fn media_condition(text: &str) -> Result<MediaCondition, Error> {
+    let invalid = || error(format!("invalid media condition {}", text.trim()));
+    match condition_tokens(text)?.as_slice() {
+        [not, operand] if not.eq_ignore_ascii_case("not") => {
+            Ok(MediaCondition::negate(media_in_parens(operand)?))
+        }
+        [first, rest @ ..] if rest.len() % 2 == 0 => {
+            let operator = rest.first().map(|operator| operator.to_ascii_lowercase());
+            let mut conditions = vec![media_in_parens(first)?];
+            for pair in rest.chunks(2) {
+                if Some(pair[0].to_ascii_lowercase()) != operator {
+                    return Err(invalid());
+                }
+                conditions.push(media_in_parens(pair[1])?);
+            }
+            match operator.as_deref() {
+                None => Ok(conditions.remove(0)),
+                Some("and") => Ok(MediaCondition::And(conditions)),
+                Some("or") => Ok(MediaCondition::Or(conditions)),
+                Some(_) => Err(invalid()),
+            }
+        }
+        _ => Err(invalid()),
+    }
+}
+
+// a feature, or a nested condition when the parentheses hold another group or a not
+fn media_in_parens(text: &str) -> Result<MediaCondition, Error> {
+    let inner = text
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .map(str::trim)
+        .ok_or_else(|| error(format!("expected a media feature, found {}", text)))?;
+    if !inner.starts_with('(') && !inner.to_ascii_lowercase().starts_with("not ") {
+        return media_feature(text).map(MediaCondition::Lone);
+    }
+    Ok(match media_condition(inner)? {
+        // keep parentheses that would otherwise not be written
+        condition @ (MediaCondition::Lone(_) | MediaCondition::Group(_)) => {
+            MediaCondition::group(condition)
+        }
+        condition => condition,
+    })
+}
+
+// the conditions of a query are joined by and
+fn media_conditions(text: &str) -> Result<Vec<MediaCondition>, Error> {
+    Ok(match text.trim() {
+        "" => vec![],
+        text => match media_condition(text)? {
+            MediaCondition::And(conditions) => conditions,
+            condition => vec![condition],
+        },
+    })
+}
+
+// [only|not] type [and condition], or only a condition which applies to all types
+fn media_query(prelude: &str) -> Result<MediaQuery, Error> {
+    if split_top_level(prelude, ',').len() > 1 {
+        return Err(error(format!(
+            "media query lists are not supported: {}",
+            prelude
+        )));
+    }
+    let lower = prelude.to_ascii_lowercase();
+    let (constraint, rest) = match lower.split_once(' ') {
+        Some(("only", _)) => (MediaConstraint::Only, prelude[5..].trim_start()),
+        Some(("not", _)) => (MediaConstraint::Not, prelude[4..].trim_start()),
+        _ => (MediaConstraint::None, prelude),
+    };
+    let (media_type, features) = match rest.starts_with('(') {
+        true => (None, rest),
+        false => {
+            let (media_type, features) = rest.split_once(' ').unwrap_or((rest, ""));
+            let features = features.trim_start();
+            match features.get(..4).map(str::to_ascii_lowercase).as_deref() {
+                _ if features.is_empty() => (Some(media_type), ""),
+                Some("and ") => (Some(media_type), &features[4..]),
+                _ => return Err(error(format!("unexpected {} in media query", features))),
+            }
+        }
+    };
+
+    let features = media_conditions(features)?;
+    Ok(match media_type {
+        Some(media_type) => MediaQuery::new(constraint, media_type.to_string(), features),
+        None => MediaQuery::without_type(constraint, features),
+    })
+}
+
+// [name] condition
+fn container_query(prelude: &str) -> Result<ContainerQuery, Error> {
+    let (name, conditions) = match prelude.starts_with('(') {
+        true => (None, prelude),
+        false => {
+            let (name, conditions) = prelude.split_once(' ').unwrap_or((prelude, ""));
+            (Some(unescape(name)), conditions.trim())
+        }
+    };
+    Ok(ContainerQuery::new(name, media_conditions(conditions)?))
+}
+
+// nested selectors are combined with each of their parents, & stands for the parent
+fn nested_selector(parents: &[String], prelude: &str) -> Vec<String> {
+    let mut selectors = Vec::new();
+    for parent in parents {
+        for child in split_top_level(prelude, ',') {
+            let child = child.trim();
+            selectors.push(match child.contains('&') {
+                true => child.replace('&', parent),
+                false => format!("{} {}", parent, child),
+            });
+        }
+    }
+    selectors
+}
+
+// a rule and the rules nested in it, flattened in the order they apply
+fn rule(selectors: Vec<String>, body: &str, rules: &mut Vec<Rule>) -> Result<(), Error> {
+    let selector = Selector::parse(&selectors.join(","))?;
+    let mut declarations = Vec::new();
+    let mut nested = Vec::new();
+    for item in items(body)? {
+        match item {
+            Item::Declaration(text) => declarations.push(declaration(text)?),
+            Item::Block(prelude, _) if prelude.starts_with('@') => {
+                return Err(error(format!("{} inside a rule is not supported", prelude)))
+            }
+            Item::Block(prelude, body) => nested.push((nested_selector(&selectors, prelude), body)),
+        }
+    }
+
+    rules.push(Rule::new(selector, declarations, vec![]));
+    for (selectors, body) in nested {
+        rule(selectors, body, rules)?;
+    }
+    Ok(())
+}
+
+fn keyframe_selector(text: &str) -> Result<KeyframeSelector, Error> {
+    let text = text.trim();
+    match text.to_ascii_lowercase().as_str() {
+        "from" => return Ok(KeyframeSelector::From),
+        "to" => return Ok(KeyframeSelector::To),
+        _ => (),
+    }
+    text.strip_suffix('%')
+        .and_then(|number| number.parse::<f64>().ok())
+        .map(KeyframeSelector::percentage)
+        .ok_or_else(|| error(format!("invalid keyframe selector {}", text)))
+}
+
+fn keyframes(name: &str, body: &str) -> Result<Keyframes, Error> {
+    let frames = items(body)?
+        .into_iter()
+        .map(|item| match item {
+            Item::Block(prelude, body) => Ok(Keyframe::new(
+                split_top_level(prelude, ',')
+                    .into_iter()
+                    .map(keyframe_selector)
+                    .collect::<Result<_, _>>()?,
+                items(body)?
+                    .into_iter()
+                    .map(|item| match item {
+                        Item::Declaration(text) => declaration(text),
+                        Item::Block(prelude, _) => {
+                            Err(error(format!("unexpected {} in a keyframe", prelude)))
+                        }
+                    })
+                    .collect::<Result<_, _>>()?,
+            )),
+            Item::Declaration(text) => Err(error(format!("unexpected {} in @keyframes", text))),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Keyframes::new(unescape(name), frames))
+}
+
+// url(...) [format(...)] or local(...)
+fn font_source(text: &str) -> Result<FontSource, Error> {
+    let invalid = || error(format!("invalid font source {}", text.trim()));
+    let function = |text: &str, name: &str| {
+        let lower = text.to_ascii_lowercase();
+        match lower.starts_with(name) && lower[name.len()..].starts_with('(') && text.ends_with(')')
+        {
+            true => Some(unquote(&text[name.len() + 1..text.len() - 1])),
+            false => None,
+        }
+    };
+
+    let parts: Vec<&str> = split_top_level(text, ' ')
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect();
+    match parts.as_slice() {
+        [source] => match (function(source, "local"), function(source, "url")) {
+            (Some(name), _) => Ok(FontSource::Local(name)),
+            (_, Some(url)) => Ok(FontSource::url(url)),
+            _ => Err(invalid()),
+        },
+        [source, hint] => match (function(source, "url"), function(hint, "format")) {
+            (Some(url), Some(format)) => Ok(FontSource::url_with_format(url, format)),
+            _ => Err(invalid()),
+        },
+        _ => Err(invalid()),
+    }
+}
+
+fn font_face(body: &str) -> Result<FontFace, Error> {
+    let (mut family, mut sources, mut descriptors) = (None, Vec::new(), Vec::new());
+    let (mut weight, mut style, mut display) = (None, None, None);
+    for item in items(body)? {
+        let text = match item {
+            Item::Declaration(text) => text,
+            Item::Block(prelude, _) => {
+                return Err(error(format!("unexpected {} in @font-face", prelude)))
+            }
+        };
+        let declaration = declaration(text)?;
+        let value_text = text.split_once(':').map_or("", |(_, value)| value.trim());
+        match declaration.property().to_ascii_lowercase().as_str() {
+            "font-family" => family = Some(unquote(value_text)),
+            "src" => {
+                sources = split_top_level(value_text, ',')
+                    .into_iter()
+                    .map(font_source)
+                    .collect::<Result<_, _>>()?
+            }
+            "font-weight" => weight = Some(declaration.value().clone()),
+            "font-style" => style = Some(declaration.value().clone()),
+            "font-display" => display = Some(declaration.value().clone()),
+            _ => descriptors.push(declaration),
+        }
+    }
+
+    let family = family.ok_or_else(|| error("@font-face without font-family".to_string()))?;
+    let mut font_face = FontFace::new(family, sources);
+    if let Some(weight) = weight {
+        font_face = font_face.weight(weight);
+    }
+    if let Some(style) = style {
+        font_face = font_face.style(style);
+    }
+    if let Some(display) = display {
+        font_face = font_face.display(display);
+    }
+    Ok(descriptors
+        .into_iter()
+        .fold(font_face, FontFace::descriptor))
+}
+
+fn page_selector(text: &str) -> Result<PageSelector, Error> {
+    let mut parts = text.split(':');
+    let name = parts.next().filter(|name| !name.is_empty()).map(unescape);
+    let pseudo_classes = parts
+        .map(|pseudo| match pseudo.to_ascii_lowercase().as_str() {
+            "first" => Ok(PagePseudoClass::First),
+            "left" => Ok(PagePseudoClass::Left),
+            "right" => Ok(PagePseudoClass::Right),
+            "last" => Ok(PagePseudoClass::Last),
+            "blank" => Ok(PagePseudoClass::Blank),
+            _ => Err(error(format!("invalid page selector {}", text))),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(PageSelector::new(name, pseudo_classes))
+}
+
+fn page_rule(prelude: &str, body: &str) -> Result<PageRule, Error> {
+    let selectors = split_top_level(prelude, ',')
+        .into_iter()
+        .map(str::trim)
+        .filter(|selector| !selector.is_empty())
+        .map(page_selector)
+        .collect::<Result<_, _>>()?;
+    let mut declarations = Vec::new();
+    let mut margin_boxes = Vec::new();
+    for item in items(body)? {
+        match item {
+            Item::Declaration(text) => declarations.push(declaration(text)?),
+            Item::Block(name, body) => {
+                let margin_box = name
+                    .strip_prefix('@')
+                    .and_then(MarginBox::from_name)
+                    .ok_or_else(|| error(format!("unexpected {} in @page", name)))?;
+                let box_declarations = items(body)?
+                    .into_iter()
+                    .map(|item| match item {
+                        Item::Declaration(text) => declaration(text),
+                        Item::Block(prelude, _) => Err(error(format!(
+                            "unexpected {} in @{}",
+                            prelude,
+                            margin_box.as_str()
+                        ))),
+                    })
+                    .collect::<Result<_, _>>()?;
+                margin_boxes.push((margin_box, box_declarations));
+            }
+        }
+    }
+    Ok(margin_boxes.into_iter().fold(
+        PageRule::new(selectors, declarations),
+        |page, (margin_box, declarations)| page.margin_box(margin_box, declarations),
+    ))
+}
+
+// at-rules without a dedicated type, the block holds declarations unless it has nested blocks
+fn generic_at_rule(prelude: &str, body: Option<&str>) -> Result<AtRule, Error> {
+    let (name, rest) = prelude[1..]
+        .split_once(char::is_whitespace)
+        .unwrap_or((&prelude[1..], ""));
+    let block = match body {
+        Some(body) => {
+            let items = items(body)?;
+            Some(
+                if items
+                    .iter()
+                    .all(|item| matches!(item, Item::Declaration(_)))
+                {
+                    AtRuleBlock::Declarations(
+                        items
+                            .into_iter()
+                            .filter_map(|item| match item {
+                                Item::Declaration(text) => Some(declaration(text)),
+                                Item::Block(..) => None,
+                            })
+                            .collect::<Result<_, _>>()?,
+                    )
+                } else {
+                    AtRuleBlock::Rules(rule_set_from_items(items, None)?)
+                },
+            )
+        }
+        None => None,
+    };
+    Ok(AtRule::generic(unescape(name), rest.trim(), block))
+}
+
+// consecutive rules are kept together, media blocks become sub sets in between so the order of
+// the stylesheet is kept
+fn rule_set(css: &str, query: Option<MediaQuery>) -> Result<RuleSet, Error> {
+    rule_set_from_items(items(css)?, query)
+}
+
+fn rule_set_from_items(items: Vec<Item<'_>>, query: Option<MediaQuery>) -> Result<RuleSet, Error> {
+    let mut sub_sets = Vec::new();
+    let mut rules = Vec::new();
+    for item in items {
+        match item {
+            Item::Declaration(text) if text.to_ascii_lowercase().starts_with("@charset") => (),
+            Item::Declaration(text) if text.to_ascii_lowercase().starts_with("@layer ") => {
+                if !rules.is_empty() {
+                    sub_sets.push(RuleSet::new(core::mem::take(&mut rules), vec![], None));
+                }
+                let names = split_top_level(&text[7..], ',')
+                    .into_iter()
+                    .map(|name| unescape(name.trim()));
+                sub_sets
+                    .push(RuleSet::new(vec![], vec![], None).at_rule(AtRule::layer_order(names)));
+            }
+            Item::Declaration(text)
+                if text.starts_with('@') && !text.to_ascii_lowercase().starts_with("@import") =>
+            {
+                if !rules.is_empty() {
+                    sub_sets.push(RuleSet::new(core::mem::take(&mut rules), vec![], None));
+                }
+                sub_sets
+                    .push(RuleSet::new(vec![], vec![], None).at_rule(generic_at_rule(text, None)?));
+            }
+            Item::Declaration(text) => {
+                return Err(error(format!("unexpected {} outside of a rule", text)))
+            }
+            Item::Block(prelude, body) if prelude.starts_with('@') => {
+                let (name, condition) = prelude.split_once(' ').unwrap_or((prelude, ""));
+                let set = match name.to_ascii_lowercase().as_str() {
+                    "@media" => rule_set(body, Some(media_query(condition.trim())?))?,
+                    "@keyframes" => RuleSet::new(vec![], vec![], None)
+                        .at_rule(keyframes(condition.trim(), body)?),
+                    "@font-face" => RuleSet::new(vec![], vec![], None).at_rule(font_face(body)?),
+                    "@page" => RuleSet::new(vec![], vec![], None)
+                        .at_rule(page_rule(condition.trim(), body)?),
+                    "@container" => RuleSet::new(vec![], vec![], None).at_rule(AtRule::container(
+                        container_query(condition.trim())?,
+                        rule_set(body, None)?,
+                    )),
+                    "@layer" => RuleSet::new(vec![], vec![], None).at_rule(AtRule::Layer(
+                        Some(condition.trim())
+                            .filter(|name| !name.is_empty())
+                            .map(unescape),
+                        rule_set(body, None)?,
+                    )),
+                    _ => RuleSet::new(vec![], vec![], None)
+                        .at_rule(generic_at_rule(prelude, Some(body))?),
+                };
+                if !rules.is_empty() {
+                    sub_sets.push(RuleSet::new(core::mem::take(&mut rules), vec![], None));
+                }
+                sub_sets.push(set);
+            }
+            Item::Block(prelude, body) => {
+                let selectors = split_top_level(prelude, ',')
+                    .into_iter()
+                    .map(|s| s.trim().to_string())
+                    .collect();
+                rule(selectors, body, &mut rules)?;
+            }
+        }
+    }
+
+    Ok(match sub_sets.is_empty() {
+        true => RuleSet::new(rules, vec![], query),
+        false => {
+            if !rules.is_empty() {
+                sub_sets.push(RuleSet::new(rules, vec![], None));
+            }
+            RuleSet::new(vec![], sub_sets, query)
+        }
+    })
+}
+
+impl RuleSet {
+    // comments and @charset are dropped, nested rules are flattened into plain rules
+    pub fn parse(css: &str) -> Result<RuleSet, Error> {
+        rule_set(&strip_comments(css), None)
+    }
+}
+
+impl AtRule {
+    // a single at-rule, @media is not one since it becomes a sub set
+    pub fn parse(css: &str) -> Result<AtRule, Error> {
+        let css = strip_comments(css);
+        let not_single = || error(format!("{} is not a single at-rule", css.trim()));
+        let items = items(&css)?;
+        match items.as_slice() {
+            [Item::Declaration(text) | Item::Block(text, _)] if text.starts_with('@') => (),
+            _ => return Err(not_single()),
+        }
+        match rule_set_from_items(items, None)?.sub_sets() {
+            [set] if set.rules().is_empty() && set.sub_sets().is_empty() => {
+                match (set.media_query(), set.at_rules()) {
+                    (None, [at_rule]) => Ok(at_rule.clone()),
+                    _ => Err(not_single()),
+                }
+            }
+            _ => Err(not_single()),
+        }
+    }
+}
+
+// @import "url" [media];
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Import {
+    url: String,
+    #[serde(default)]
+    media: Option<String>,
+}
+
+impl Import {
+    pub fn new(url: String) -> Self {
+        Self { url, media: None }
+    }
+
+    // a media query list, e.g. print or screen and (min-width:600px)
+    pub fn media(mut self, media: impl Into<String>) -> Self {
+        self.media = Some(media.into());
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn media_query(&self) -> Option<&str> {
+        self.media.as_deref()
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        8 + css_escaped_len(&self.url, true) + self.media.as_ref().map_or(0, |m| m.len() + 1) + 1
+    }
+
+    fn render<W: Write>(&self, output: &mut W) -> fmt::Result {
+        output.write_str("@import ")?;
+        write_css_escaped(output, &self.url, true)?;
+        if let Some(media) = &self.media {
+            output.write_char(' ')?;
+            output.write_str(media)?;
+        }
+        output.write_char(';')
+    }
+}
+
+impl fmt::Display for Import {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
+    }
+}
+
+// a whole sheet, written as @charset, the @layer order, @import statements and then rule sets
+// as css requires
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Stylesheet {
+    #[serde(default)]
+    charset: Option<String>,
+    #[serde(default)]
+    layer_order: Vec<String>,
+    #[serde(default)]
+    imports: Vec<Import>,
+    #[serde(default)]
+    rule_sets: Vec<RuleSet>,
+}
+
+impl Stylesheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn charset(mut self, charset: impl Into<String>) -> Self {
+        self.charset = Some(charset.into());
+        self
+    }
+
+    // @layer statement written before the imports so imported layers can be placed too
+    pub fn layer_order<S: Into<String>>(mut self, names: impl IntoIterator<Item = S>) -> Self {
+        self.layer_order.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn import(mut self, import: Import) -> Self {
+        self.imports.push(import);
+        self
+    }
+
+    pub fn rule_set(mut self, set: RuleSet) -> Self {
+        self.rule_sets.push(set);
+        self
+    }
+
+    // at-rules like @page or @font-face that are not tied to any rule
+    pub fn at_rule(self, at_rule: impl Into<AtRule>) -> Self {
+        self.rule_set(RuleSet::new(vec![], vec![], None).at_rule(at_rule))
+    }
+
+    pub fn charset_name(&self) -> Option<&str> {
+        self.charset.as_deref()
+    }
+
+    pub fn imports(&self) -> &[Import] {
+        &self.imports
+    }
+
+    pub fn rule_sets(&self) -> &[RuleSet] {
+        &self.rule_sets
+    }
+
+    // @import and @charset are kept, the rest is read like RuleSet::parse
+    pub fn parse(css: &str) -> Result<Stylesheet, Error> {
+        let css = strip_comments(css);
+        let mut items = items(&css)?.into_iter().peekable();
+        let mut sheet = Stylesheet::new();
+        while let Some(Item::Declaration(text)) = items.peek() {
+            let (name, rest) = text.split_once(' ').unwrap_or((text, ""));
+            let rest = rest.trim();
+            match name.to_ascii_lowercase().as_str() {
+                "@charset" => sheet.charset = Some(unquote(rest)),
+                "@layer" if sheet.imports.is_empty() => sheet.layer_order.extend(
+                    split_top_level(rest, ',')
+                        .into_iter()
+                        .map(|name| unescape(name.trim())),
+                ),
+                "@import" => {
+                    let parts = split_top_level(rest, ' ');
+                    let url = match parts[0].get(..4) {
+                        Some(start) if start.eq_ignore_ascii_case("url(") => {
+                            unquote(&parts[0][4..parts[0].len() - 1])
+                        }
+                        _ => unquote(parts[0]),
+                    };
+                    let import = Import::new(url);
+                    sheet.imports.push(match rest[parts[0].len()..].trim() {
+                        "" => import,
+                        media => import.media(media),
+                    });
+                }
+                _ => break,
+            }
+            items.next();
+        }
+
+        let set = rule_set_from_items(items.collect(), None)?;
+        if !set.rules().is_empty() || !set.sub_sets().is_empty() {
+            sheet.rule_sets.push(set);
+        }
+        Ok(sheet)
+    }
+
+    pub fn estimated_len(&self) -> usize {
+        self.charset
+            .as_ref()
+            .map_or(0, |charset| 10 + css_escaped_len(charset, true))
+            + match self.layer_order.is_empty() {
+                true => 0,
+                false => layer_order_len(&self.layer_order),
+            }
+            + self
+                .imports
+                .iter()
+                .map(Import::estimated_len)
+                .sum::<usize>()
+            + self
+                .rule_sets
+                .iter()
+                .map(RuleSet::estimated_len)
+                .sum::<usize>()
+    }
+
+    pub fn write_to<W: Write>(&self, output: &mut W) -> fmt::Result {
+        self.write_formatted(output, &CssFormat::compact())
+    }
+
+    pub fn write_formatted<W: Write>(&self, output: &mut W, format: &CssFormat) -> fmt::Result {
+        if let Some(charset) = &self.charset {
+            output.write_str("@charset ")?;
+            write_css_escaped(output, charset, true)?;
+            output.write_str(format.pick(";", ";\n"))?;
+        }
+        if !self.layer_order.is_empty() {
+            write_layer_order(output, &self.layer_order, format, 0)?;
+        }
+        for import in &self.imports {
+            import.render(output)?;
+            output.write_str(format.pick("", "\n"))?;
+        }
+        for set in &self.rule_sets {
+            set.write_formatted(output, format)?;
+        }
+        Ok(())
+    }
+
+    pub fn to_string_formatted(&self, format: &CssFormat) -> String {
+        let mut output = String::with_capacity(self.estimated_len());
+        // writing to a String can not fail
+        let _ = self.write_formatted(&mut output, format);
+        output
+    }
+}
+
+impl fmt::Display for Stylesheet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_to(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::at_rule::{
+        AtRule, AtRuleBlock, FontFace, FontSource, Keyframe, KeyframeSelector, Keyframes,
+        MarginBox, PagePseudoClass, PageRule, PageSelector,
+    };
+    use crate::color::Color;
+    use crate::css::{
+        CssFormat, Declaration, DeclarationValue, MediaCondition, MediaConstraint, MediaQuery,
+        RuleSet,
+    };
+    use crate::error::Error;
+    use crate::stylesheet::{Import, Stylesheet};
+    use crate::values::Unit;
+
+    #[test]
+    fn rules_and_media() {
+        let css = "/* legacy */ @charset \"utf-8\";
+            body { color: blue; font-family: \"Times \\\"New\\\" Roman\" }
+            @media only screen and (max-width: 600px) { .nav { display: none; } }
+            a:hover, a:focus { color: rgb(0, 0, 0); }";
+        let set = RuleSet::parse(css).unwrap();
+
+        assert_eq!(
+            set.to_string(),
+            "body{color:blue;font-family:\"Times \\\"New\\\" Roman\";}@media only screen and (max-width:600px){.nav{display:none;}}a:hover,a:focus{color:#000;}"
+        );
+        assert_eq!(RuleSet::parse(&set.to_string()).unwrap(), set);
+    }
+
+    #[test]
+    fn nesting_is_flattened() {
+        let set = RuleSet::parse(
+            ".card, .panel { padding: 0; h2 { margin: 0; } &.active { color: red; } &:hover > a { color: blue; } }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            set.to_string(),
+            ".card,.panel{padding:0;}.card h2,.panel h2{margin:0;}.card.active,.panel.active{color:red;}.card:hover>a,.panel:hover>a{color:blue;}"
+        );
+    }
+
+    #[test]
+    fn media_without_type() {
+        let set = RuleSet::parse(
+            "@media (min-width: 40em) and (orientation: landscape) { p { margin: 0 } }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            set.to_string(),
+            "@media (min-width:40em) and (orientation:landscape){p{margin:0;}}"
+        );
+    }
+
+    #[test]
+    fn typed_values() {
+        let set = RuleSet::parse(
+            "p { width: 50%; margin: -1.5rem; line-height: 1.2; display: -webkit-box; color: #FFFFFF; z-index: 0010 }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            set.rules()[0]
+                .declarations()
+                .iter()
+                .map(|d| d.value().clone())
+                .collect::<Vec<_>>(),
+            vec![
+                DeclarationValue::percentage(50.0),
+                DeclarationValue::length(-1.5, Unit::Rem),
+                DeclarationValue::number(1.2),
+                DeclarationValue::keyword("-webkit-box"),
+                DeclarationValue::Color(Color::rgb(255, 255, 255)),
+                DeclarationValue::number(10),
+            ]
+        );
+        assert_eq!(
+            set.to_string(),
+            "p{width:50%;margin:-1.5rem;line-height:1.2;display:-webkit-box;color:#fff;z-index:10;}"
+        );
+    }
+
+    #[test]
+    fn lists() {
+        let set = RuleSet::parse(
+            "p { margin: 0 auto; font-family: \"Fira Sans\", sans-serif; transition: opacity .2s,\n transform .2s ease-in }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            set.rules()[0]
+                .declarations()
+                .iter()
+                .map(|d| d.value().clone())
+                .collect::<Vec<_>>(),
+            vec![
+                DeclarationValue::space_list(vec![
+                    DeclarationValue::number(0),
+                    DeclarationValue::keyword("auto"),
+                ]),
+                DeclarationValue::comma_list(vec![
+                    DeclarationValue::Basic("Fira Sans".to_string()),
+                    DeclarationValue::keyword("sans-serif"),
+                ]),
+                DeclarationValue::comma_list(vec![
+                    DeclarationValue::space_list(vec![
+                        DeclarationValue::keyword("opacity"),
+                        DeclarationValue::length(0.2, Unit::S),
+                    ]),
+                    DeclarationValue::space_list(vec![
+                        DeclarationValue::keyword("transform"),
+                        DeclarationValue::length(0.2, Unit::S),
+                        DeclarationValue::keyword("ease-in"),
+                    ]),
+                ]),
+            ]
+        );
+        assert_eq!(
+            set.to_string(),
+            "p{margin:0 auto;font-family:\"Fira Sans\",sans-serif;transition:opacity 0.2s,transform 0.2s ease-in;}"
+        );
+    }
+
+    #[test]
+    fn urls() {
+        let set = RuleSet::parse(
+            "a { background: url(img/a.png) no-repeat, URL( 'b,c.png' ); cursor: url(\"x\\\".cur\") }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            set.rules()[0].declarations()[0].value(),
+            &DeclarationValue::comma_list(vec![
+                DeclarationValue::space_list(vec![
+                    DeclarationValue::url("img/a.png"),
+                    DeclarationValue::keyword("no-repeat"),
+                ]),
+                DeclarationValue::url("b,c.png"),
+            ])
+        );
+        assert_eq!(
+            set.to_string(),
+            "a{background:url(\"img/a.png\") no-repeat,url(\"b,c.png\");cursor:url(\"x\\\".cur\");}"
+        );
+    }
+
+    #[test]
+    fn custom_properties() {
+        let set = RuleSet::parse(
+            ":root { --brand: #fa0; --stack: Inter, sans-serif } p { color: var(--brand); font-family: var(--stack, Arial, serif) }",
+        )
+        .unwrap();
+
+        assert!(set.rules()[0].declarations()[0].is_custom_property());
+        assert_eq!(
+            set.rules()[1].declarations()[1].value(),
+            &DeclarationValue::var_or(
+                "stack",
+                DeclarationValue::comma_list(vec![
+                    DeclarationValue::keyword("Arial"),
+                    DeclarationValue::keyword("serif"),
+                ])
+            )
+        );
+        assert_eq!(
+            set.to_string(),
+            ":root{--brand:#fa0;--stack:Inter,sans-serif;}p{color:var(--brand);font-family:var(--stack,Arial,serif);}"
+        );
+    }
+
+    #[test]
+    fn keyframes() {
+        let css = "p { color: red } @keyframes pulse { from, 50% { opacity: 1 } to { opacity: .5 } } a { color: blue }";
+        let set = RuleSet::parse(css).unwrap();
+
+        assert_eq!(
+            set.sub_sets()[1].at_rules(),
+            &[AtRule::from(Keyframes::new(
+                "pulse".to_string(),
+                vec![
+                    Keyframe::new(
+                        vec![KeyframeSelector::From, KeyframeSelector::percentage(50)],
+                        vec![Declaration::new(
+                            "opacity".to_string(),
+                            DeclarationValue::number(1)
+                        )]
+                    ),
+                    Keyframe::new(
+                        vec![KeyframeSelector::To],
+                        vec![Declaration::new(
+                            "opacity".to_string(),
+                            DeclarationValue::number(0.5)
+                        )]
+                    ),
+                ]
+            ))]
+        );
+        assert_eq!(
+            set.to_string(),
+            "p{color:red;}@keyframes pulse{from,50%{opacity:1;}to{opacity:0.5;}}a{color:blue;}"
+        );
+        assert_eq!(
+            RuleSet::parse("@keyframes pulse { half { opacity: 1 } }"),
+            Err(Error::Css("invalid keyframe selector half".to_string()))
+        );
+    }
+
+    #[test]
+    fn font_faces() {
+        let css = "@font-face { font-family: 'Fira Sans'; src: local(Fira), url(fira.woff2) format('woff2'); font-weight: 100 900; font-display: swap; unicode-range: U+0-FF }";
+        let set = RuleSet::parse(css).unwrap();
+
+        assert_eq!(
+            set.sub_sets()[0].at_rules(),
+            &[AtRule::from(
+                FontFace::new(
+                    "Fira Sans".to_string(),
+                    vec![
+                        FontSource::local("Fira"),
+                        FontSource::url_with_format("fira.woff2", "woff2"),
+                    ]
+                )
+                .weight(DeclarationValue::space_list(vec![
+                    DeclarationValue::number(100),
+                    DeclarationValue::number(900),
+                ]))
+                .display(DeclarationValue::keyword("swap"))
+                .descriptor(Declaration::new(
+                    "unicode-range".to_string(),
+                    DeclarationValue::Basic("U+0-FF".to_string())
+                ))
+            )]
+        );
+        assert_eq!(
+            RuleSet::parse("@font-face { src: url(a.woff) }"),
+            Err(Error::Css("@font-face without font-family".to_string()))
+        );
+        assert_eq!(
+            RuleSet::parse("@font-face { font-family: a; src: a.woff }"),
+            Err(Error::Css("invalid font source a.woff".to_string()))
+        );
+    }
+
+    #[test]
+    fn pages() {
+        let css = "@page { margin: 2cm } @page :first, chapter:right { margin-top: 4cm; @top-center { content: 'Title' } @bottom-right { content: counter(page) } } p { orphans: 3 }";
+        let set = RuleSet::parse(css).unwrap();
+        assert_eq!(
+            set.to_string(),
+            "@page{margin:2cm;}@page :first,chapter:right{margin-top:4cm;@top-center{content:'Title';}@bottom-right{content:counter(page);}}p{orphans:3;}"
+        );
+        assert_eq!(RuleSet::parse(&set.to_string()), Ok(set));
+        assert_eq!(
+            RuleSet::parse("@page :middle { margin: 0 }"),
+            Err(Error::Css("invalid page selector :middle".to_string()))
+        );
+        assert_eq!(
+            RuleSet::parse("@page { @middle { margin: 0 } }"),
+            Err(Error::Css("unexpected @middle in @page".to_string()))
+        );
+
+        let sheet = Stylesheet::new()
+            .at_rule(
+                PageRule::new(
+                    vec![PageSelector::pseudo(PagePseudoClass::Left)],
+                    vec![Declaration::new(
+                        "margin-left".to_string(),
+                        DeclarationValue::length(3, Unit::Cm),
+                    )],
+                )
+                .margin_box(MarginBox::LeftMiddle, vec![]),
+            )
+            .rule_set(RuleSet::parse("h1 { break-before: page }").unwrap());
+        assert_eq!(
+            sheet.to_string(),
+            "@page :left{margin-left:3cm;@left-middle{}}h1{break-before:page;}"
+        );
+        assert_eq!(sheet.estimated_len(), sheet.to_string().len());
+    }
+
+    #[test]
+    fn stylesheets() {
+        let sheet = Stylesheet::new()
+            .rule_set(RuleSet::parse("p { color: red }").unwrap())
+            .import(Import::new("print.css".to_string()).media("print"))
+            .charset("UTF-8")
+            .import(Import::new("base \"v2\".css".to_string()))
+            .layer_order(["base", "print"]);
+
+        assert_eq!(
+            sheet.to_string(),
+            "@charset \"UTF-8\";@layer base,print;@import \"print.css\" print;@import \"base \\\"v2\\\".css\";p{color:red;}"
+        );
+        assert_eq!(sheet.estimated_len(), sheet.to_string().len());
+        assert_eq!(Stylesheet::parse(&sheet.to_string()), Ok(sheet));
+
+        let parsed = Stylesheet::parse(
+            "@charset 'utf-8'; @import url(a.css); @import url('b.css') screen and (min-width: 600px); a { color: blue }",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.imports(),
+            &[
+                Import::new("a.css".to_string()),
+                Import::new("b.css".to_string()).media("screen and (min-width: 600px)"),
+            ]
+        );
+        assert_eq!(
+            parsed.to_string_formatted(&CssFormat::expanded()),
+            "@charset \"utf-8\";\n@import \"a.css\";\n@import \"b.css\" screen and (min-width: 600px);\na {\n  color: blue;\n}\n"
+        );
+        assert_eq!(
+            Stylesheet::parse("a { color: blue } @import 'late.css';"),
+            Err(Error::Css(
+                "unexpected @import 'late.css' outside of a rule".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn layers() {
+        let css = "@layer reset, ui; @layer ui { @media print { a { color: black } } } @layer { p { margin: 0 } }";
+        let set = RuleSet::parse(css).unwrap();
+
+        assert_eq!(
+            set.sub_sets()[0].at_rules(),
+            &[AtRule::layer_order(["reset", "ui"])]
+        );
+        assert_eq!(
+            set.to_string(),
+            "@layer reset,ui;@layer ui{@media print{a{color:black;}}}@layer{p{margin:0;}}"
+        );
+    }
+
+    #[test]
+    fn escaped_layer_names() {
+        let css = "@layer \\31 st, ui; @layer \\31 st { p { margin: 0 } }";
+        let set = RuleSet::parse(css).unwrap();
+
+        assert_eq!(
+            set.sub_sets()[0].at_rules(),
+            &[AtRule::layer_order(["1st", "ui"])]
+        );
+        assert_eq!(
+            set.sub_sets()[1].at_rules(),
+            &[AtRule::layer(
+                "1st",
+                RuleSet::parse("p { margin: 0 }").unwrap()
+            )]
+        );
+        assert_eq!(
+            set.to_string(),
+            "@layer \\31 st,ui;@layer \\31 st{p{margin:0;}}"
+        );
+        assert_eq!(RuleSet::parse(&set.to_string()).unwrap(), set);
+        assert_eq!(
+            Stylesheet::parse("@layer \\31 st;").unwrap().to_string(),
+            "@layer \\31 st;"
+        );
+    }
+
+    #[test]
+    fn container_queries() {
+        let css = ".card { container: card / inline-size } @container card (min-width: 400px) and (max-width: 60em) { .title { font-size: 2rem } } @container (min-width: 10px) { a { color: red } }";
+
+        assert_eq!(
+            RuleSet::parse(css).unwrap().to_string(),
+            ".card{container:card / inline-size;}@container card (min-width:400px) and (max-width:60em){.title{font-size:2rem;}}@container (min-width:10px){a{color:red;}}"
+        );
+        assert_eq!(
+            RuleSet::parse("@container card (width > 400px) { a { color: red } }"),
+            Err(Error::Css(
+                "media feature (width > 400px) has no value".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn generic_at_rules() {
+        let css = "@namespace svg url(http://www.w3.org/2000/svg); @property --angle { syntax: '<angle>'; inherits: false; initial-value: 0deg } p { color: red } @scope (.card) to (.content) { img { border: 0 } }";
+        let set = RuleSet::parse(css).unwrap();
+
+        assert_eq!(
+            set.sub_sets()[1].at_rules(),
+            &[AtRule::generic(
+                "property",
+                "--angle",
+                Some(AtRuleBlock::Declarations(vec![
+                    Declaration::new(
+                        "syntax".to_string(),
+                        DeclarationValue::Basic("'<angle>'".to_string())
+                    ),
+                    Declaration::new("inherits".to_string(), DeclarationValue::keyword("false")),
+                    Declaration::new(
+                        "initial-value".to_string(),
+                        DeclarationValue::length(0, Unit::Deg)
+                    ),
+                ]))
+            )]
+        );
+        assert_eq!(
+            set.to_string(),
+            "@namespace svg url(http://www.w3.org/2000/svg);@property --angle{syntax:'<angle>';inherits:false;initial-value:0deg;}p{color:red;}@scope (.card) to (.content){img{border:0;}}"
+        );
+        assert_eq!(RuleSet::parse(&set.to_string()), Ok(set));
+        assert_eq!(
+            AtRule::parse("@starting-style { .a { opacity: 0 } }")
+                .unwrap()
+                .to_string(),
+            "@starting-style{.a{opacity:0;}}"
+        );
+        assert_eq!(
+            AtRule::parse("@layer a, b"),
+            Ok(AtRule::layer_order(["a", "b"]))
+        );
+        assert_eq!(
+            AtRule::parse("@media print { a { color: red } }"),
+            Err(Error::Css(
+                "@media print { a { color: red } } is not a single at-rule".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn nested_media_conditions() {
+        let css = "@media screen and (min-width: 40em) and ((hover: hover) and (pointer: fine) or (not (orientation: portrait))) { a { color: red } } @media (max-width: 20em) or ((color: 1)) { p { margin: 0 } }";
+        assert_eq!(
+            RuleSet::parse(css),
+            Err(Error::Css(
+                "invalid media condition (hover: hover) and (pointer: fine) or (not (orientation: portrait))".to_string()
+            ))
+        );
+
+        let css = "@media screen and (min-width: 40em) and (((hover: hover) and (pointer: fine)) or (not (orientation: portrait))) { a { color: red } } @media (max-width: 20em) or ((color: 1)) { p { margin: 0 } }";
+        let set = RuleSet::parse(css).unwrap();
+        assert_eq!(
+            set.sub_sets()[0].media_query(),
+            Some(&MediaQuery::new(
+                MediaConstraint::None,
+                "screen".to_string(),
+                vec![
+                    MediaCondition::feature("min-width", "40em"),
+                    MediaCondition::or(vec![
+                        MediaCondition::and(vec![
+                            MediaCondition::feature("hover", "hover"),
+                            MediaCondition::feature("pointer", "fine"),
+                        ]),
+                        MediaCondition::negate(MediaCondition::feature("orientation", "portrait")),
+                    ]),
+                ],
+            ))
+        );
+        assert_eq!(
+            set.to_string(),
+            "@media screen and (min-width:40em) and (((hover:hover) and (pointer:fine)) or (not (orientation:portrait))){a{color:red;}}@media (max-width:20em) or ((color:1)){p{margin:0;}}"
+        );
+        assert_eq!(RuleSet::parse(&set.to_string()), Ok(set));
+        assert_eq!(
+            RuleSet::parse("@media screen and (a: b) (c: d) { }"),
+            Err(Error::Css(
+                "invalid media condition (a: b) (c: d)".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            RuleSet::parse("p { color: rgb(1, 2); }"),
+            Err(Error::Css("invalid color rgb(1, 2)".to_string()))
+        );
+        assert_eq!(
+            RuleSet::parse("p { color: red; "),
+            Err(Error::Css("unclosed block at byte 2".to_string()))
+        );
+    }
+}
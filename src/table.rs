@@ -0,0 +1,128 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::builder::Element;
+use crate::html::Node;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Table {
+    caption: Option<String>,
+    headers: Vec<Node>,
+    rows: Vec<Vec<Node>>,
+    column_classes: Vec<Option<String>>,
+    class: Option<String>,
+}
+
+impl Table {
+    pub fn from_rows<H: Into<Node>, C: Into<Node>>(
+        headers: impl IntoIterator<Item = H>,
+        rows: impl IntoIterator<Item = Vec<C>>,
+    ) -> Self {
+        Self {
+            caption: None,
+            headers: headers.into_iter().map(Into::into).collect(),
+            rows: rows
+                .into_iter()
+                .map(|row| row.into_iter().map(Into::into).collect())
+                .collect(),
+            column_classes: Vec::new(),
+            class: None,
+        }
+    }
+
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    pub fn class(mut self, class: impl Into<String>) -> Self {
+        self.class = Some(class.into());
+        self
+    }
+
+    // set on the header and every cell of the column
+    pub fn column_class(mut self, column: usize, class: impl Into<String>) -> Self {
+        if self.column_classes.len() <= column {
+            self.column_classes.resize(column + 1, None);
+        }
+        self.column_classes[column] = Some(class.into());
+        self
+    }
+
+    fn cell(&self, tag: &str, column: usize, content: Node) -> Node {
+        let cell = match self.column_classes.get(column) {
+            Some(Some(class)) => Element::new(tag).class(class.as_str()),
+            _ => Element::new(tag),
+        };
+        cell.child(content).build()
+    }
+
+    pub fn build(mut self) -> Node {
+        let mut table = Element::new("table");
+        if let Some(class) = self.class.take() {
+            table = table.class(class);
+        }
+        if let Some(caption) = self.caption.take() {
+            table = table.child(Element::new("caption").text(caption));
+        }
+
+        let headers = core::mem::take(&mut self.headers);
+        if !headers.is_empty() {
+            let row = headers
+                .into_iter()
+                .enumerate()
+                .map(|(column, header)| self.cell("th", column, header));
+            table = table.child(Element::new("thead").child(Element::new("tr").children(row)));
+        }
+
+        let rows = core::mem::take(&mut self.rows);
+        let body = rows.into_iter().map(|row| {
+            let cells = row
+                .into_iter()
+                .enumerate()
+                .map(|(column, cell)| self.cell("td", column, cell));
+            Element::new("tr").children(cells)
+        });
+        table.child(Element::new("tbody").children(body)).build()
+    }
+}
+
+impl From<Table> for Node {
+    fn from(table: Table) -> Self {
+        table.build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::html::Node;
+    use crate::table::Table;
+
+    #[test]
+    fn report() {
+        let rows = vec![
+            vec!["Widget".to_string(), "3".to_string()],
+            vec!["<Gadget>".to_string(), "12".to_string()],
+        ];
+        let table = Table::from_rows(["Item", "Count"], rows)
+            .caption("Stock")
+            .class("report")
+            .column_class(1, "number")
+            .build();
+
+        assert_eq!(
+            table.to_string(),
+            "<table class=\"report\"><caption>Stock</caption><thead><tr><th>Item</th><th class=\"number\">Count</th></tr></thead><tbody><tr><td>Widget</td><td class=\"number\">3</td></tr><tr><td>&lt;Gadget&gt;</td><td class=\"number\">12</td></tr></tbody></table>"
+        );
+    }
+
+    #[test]
+    fn without_headers() {
+        let table = Table::from_rows(Vec::<Node>::new(), vec![vec![Node::text("x".to_string())]]);
+
+        assert_eq!(
+            table.build().to_string(),
+            "<table><tbody><tr><td>x</td></tr></tbody></table>"
+        );
+    }
+}
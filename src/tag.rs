@@ -0,0 +1,149 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::html::{is_void_element, Attribute, Node};
+use crate::name::Name;
+
+macro_rules! tags {
+    ($($variant:ident => $name:literal,)*) => {
+        // elements defined by the html standard, anything else is Custom
+        #[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+        pub enum Tag {
+            $($variant,)*
+            Custom(String),
+        }
+
+        impl Tag {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Tag::$variant => $name,)*
+                    Tag::Custom(name) => name.as_str(),
+                }
+            }
+
+            // case insensitive like html, names that are not standard elements become Custom
+            pub fn from_name(name: &str) -> Self {
+                match name.to_ascii_lowercase().as_str() {
+                    $($name => Tag::$variant,)*
+                    _ => Tag::Custom(String::from(name)),
+                }
+            }
+        }
+    };
+}
+
+#[rustfmt::skip]
+tags! {
+    A => "a", Abbr => "abbr", Address => "address", Area => "area", Article => "article",
+    Aside => "aside", Audio => "audio", B => "b", Base => "base", Bdi => "bdi", Bdo => "bdo",
+    Blockquote => "blockquote", Body => "body", Br => "br", Button => "button", Canvas => "canvas",
+    Caption => "caption", Cite => "cite", Code => "code", Col => "col", Colgroup => "colgroup",
+    Data => "data", Datalist => "datalist", Dd => "dd", Del => "del", Details => "details",
+    Dfn => "dfn", Dialog => "dialog", Div => "div", Dl => "dl", Dt => "dt", Em => "em",
+    Embed => "embed", Fieldset => "fieldset", Figcaption => "figcaption", Figure => "figure",
+    Footer => "footer", Form => "form", H1 => "h1", H2 => "h2", H3 => "h3", H4 => "h4", H5 => "h5",
+    H6 => "h6", Head => "head", Header => "header", Hgroup => "hgroup", Hr => "hr", Html => "html",
+    I => "i", Iframe => "iframe", Img => "img", Input => "input", Ins => "ins", Kbd => "kbd",
+    Label => "label", Legend => "legend", Li => "li", Link => "link", Main => "main", Map => "map",
+    Mark => "mark", Math => "math", Menu => "menu", Meta => "meta", Meter => "meter", Nav => "nav",
+    Noscript => "noscript", Object => "object", Ol => "ol", Optgroup => "optgroup",
+    Option => "option", Output => "output", P => "p", Picture => "picture", Pre => "pre",
+    Progress => "progress", Q => "q", Rp => "rp", Rt => "rt", Ruby => "ruby", S => "s",
+    Samp => "samp", Script => "script", Search => "search", Section => "section",
+    Select => "select", Slot => "slot", Small => "small", Source => "source", Span => "span",
+    Strong => "strong", Style => "style", Sub => "sub", Summary => "summary", Sup => "sup",
+    Svg => "svg", Table => "table", Tbody => "tbody", Td => "td", Template => "template",
+    Textarea => "textarea", Tfoot => "tfoot", Th => "th", Thead => "thead", Time => "time",
+    Title => "title", Tr => "tr", Track => "track", U => "u", Ul => "ul", Var => "var",
+    Video => "video", Wbr => "wbr",
+}
+
+impl Tag {
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Tag::Custom(_))
+    }
+
+    pub fn is_void(&self) -> bool {
+        !self.is_custom() && is_void_element(self.as_str())
+    }
+
+    // content is written without escaping
+    pub fn is_raw_text(&self) -> bool {
+        matches!(self, Tag::Script | Tag::Style)
+    }
+}
+
+impl FromStr for Tag {
+    type Err = core::convert::Infallible;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Ok(Tag::from_name(name))
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<Tag> for Name {
+    fn from(tag: Tag) -> Self {
+        match tag {
+            Tag::Custom(name) => Name::new(name),
+            tag => Name::from(tag.as_str()),
+        }
+    }
+}
+
+impl Node {
+    pub fn tagged(tag: Tag, attributes: Vec<Attribute>, children: Vec<Node>) -> Self {
+        Node::Element {
+            tag: tag.into(),
+            attributes,
+            children,
+            self_closing: false,
+        }
+    }
+
+    // None for nodes that are not elements
+    pub fn tag_kind(&self) -> Option<Tag> {
+        self.tag().map(Tag::from_name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::html::Node;
+    use crate::tag::Tag;
+
+    #[test]
+    fn names() {
+        assert_eq!(Tag::from_name("SPAN"), Tag::Span);
+        assert_eq!(Tag::from_name("spam"), Tag::Custom("spam".to_string()));
+        assert_eq!("h1".parse::<Tag>(), Ok(Tag::H1));
+        assert_eq!(Tag::Option.to_string(), "option");
+        assert!(Tag::Img.is_void());
+        assert!(!Tag::Custom("img-like".to_string()).is_void());
+        assert!(Tag::Script.is_raw_text());
+    }
+
+    #[test]
+    fn tagged_nodes() {
+        let node = Node::tagged(
+            Tag::Ul,
+            vec![],
+            vec![Node::tagged(
+                Tag::Custom("x-item".to_string()),
+                vec![],
+                vec![],
+            )],
+        );
+
+        assert_eq!(node.to_string(), "<ul><x-item></x-item></ul>");
+        assert_eq!(node.tag_kind(), Some(Tag::Ul));
+        assert!(node.tag().is_some_and(|t| t == "ul"));
+    }
+}
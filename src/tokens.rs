@@ -0,0 +1,437 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::css::{AttributeCase, Declaration, DeclarationValue, Rule, RuleSet, Selector};
+use crate::selector::implicit_base;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TokenKind {
+    Color,
+    Spacing,
+    FontFamily,
+    FontSize,
+    Radius,
+}
+
+impl TokenKind {
+    fn prefix(&self) -> &'static str {
+        match self {
+            TokenKind::Color => "color",
+            TokenKind::Spacing => "spacing",
+            TokenKind::FontFamily => "font-family",
+            TokenKind::FontSize => "font-size",
+            TokenKind::Radius => "radius",
+        }
+    }
+
+    // (utility class prefix, property) pairs generated for each token of this kind
+    fn utilities(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            TokenKind::Color => &[("text", "color"), ("bg", "background-color")],
+            TokenKind::Spacing => &[("p", "padding"), ("m", "margin"), ("gap", "gap")],
+            TokenKind::FontFamily => &[("font", "font-family")],
+            TokenKind::FontSize => &[("text-size", "font-size")],
+            TokenKind::Radius => &[("rounded", "border-radius")],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Token {
+    name: String,
+    value: DeclarationValue,
+}
+
+impl Token {
+    pub fn new(name: String, value: DeclarationValue) -> Self {
+        Self { name, value }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Default, Deserialize, Serialize)]
+pub struct Tokens {
+    #[serde(default)]
+    colors: Vec<Token>,
+    #[serde(default)]
+    spacing: Vec<Token>,
+    #[serde(default)]
+    font_families: Vec<Token>,
+    #[serde(default)]
+    font_sizes: Vec<Token>,
+    #[serde(default)]
+    radii: Vec<Token>,
+}
+
+const KINDS: [TokenKind; 5] = [
+    TokenKind::Color,
+    TokenKind::Spacing,
+    TokenKind::FontFamily,
+    TokenKind::FontSize,
+    TokenKind::Radius,
+];
+
+impl Tokens {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn tokens(&self, kind: TokenKind) -> &Vec<Token> {
+        match kind {
+            TokenKind::Color => &self.colors,
+            TokenKind::Spacing => &self.spacing,
+            TokenKind::FontFamily => &self.font_families,
+            TokenKind::FontSize => &self.font_sizes,
+            TokenKind::Radius => &self.radii,
+        }
+    }
+
+    fn tokens_mut(&mut self, kind: TokenKind) -> &mut Vec<Token> {
+        match kind {
+            TokenKind::Color => &mut self.colors,
+            TokenKind::Spacing => &mut self.spacing,
+            TokenKind::FontFamily => &mut self.font_families,
+            TokenKind::FontSize => &mut self.font_sizes,
+            TokenKind::Radius => &mut self.radii,
+        }
+    }
+
+    // replaces the value of an existing token with the same name
+    pub fn insert(&mut self, kind: TokenKind, name: String, value: DeclarationValue) {
+        let tokens = self.tokens_mut(kind);
+        match tokens.iter_mut().find(|t| t.name == name) {
+            Some(token) => token.value = value,
+            None => tokens.push(Token::new(name, value)),
+        }
+    }
+
+    pub fn get(&self, kind: TokenKind, name: &str) -> Option<&DeclarationValue> {
+        self.tokens(kind)
+            .iter()
+            .find(|t| t.name == name)
+            .map(|t| &t.value)
+    }
+
+    pub fn custom_property(kind: TokenKind, name: &str) -> String {
+        format!("--{}-{}", kind.prefix(), name)
+    }
+
+    // var() reference to a defined token, None if the token doesn't exist
+    pub fn var(&self, kind: TokenKind, name: &str) -> Option<DeclarationValue> {
+        self.get(kind, name)
+            .map(|_| DeclarationValue::var(Self::custom_property(kind, name)))
+    }
+
+    // :root rule defining every token as a custom property
+    pub fn root_rule(&self) -> Rule {
+        Rule::new(
+            Selector::PseudoClass(Box::new(implicit_base()), "root".to_string()),
+            self.declarations_differing_from(&Tokens::new()),
+            vec![],
+        )
+    }
+
+    // custom property declarations for tokens that are missing from or have a different value in base
+    fn declarations_differing_from(&self, base: &Tokens) -> Vec<Declaration> {
+        KINDS
+            .iter()
+            .flat_map(|kind| {
+                self.tokens(*kind)
+                    .iter()
+                    .filter(move |token| base.get(*kind, &token.name) != Some(&token.value))
+                    .map(move |token| {
+                        Declaration::new(
+                            Self::custom_property(*kind, &token.name),
+                            token.value.clone(),
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    // copy of these tokens with every token from overrides inserted on top
+    pub fn merged(&self, overrides: &Tokens) -> Tokens {
+        let mut merged = self.clone();
+        for kind in KINDS {
+            for token in overrides.tokens(kind) {
+                merged.insert(kind, token.name.clone(), token.value.clone());
+            }
+        }
+        merged
+    }
+
+    // single purpose classes referencing the tokens (e.g. .text-primary{color:var(--color-primary);})
+    pub fn utility_rules(&self) -> Vec<Rule> {
+        KINDS
+            .iter()
+            .flat_map(|kind| {
+                self.tokens(*kind).iter().flat_map(move |token| {
+                    kind.utilities().iter().map(move |(class, property)| {
+                        Rule::new(
+                            Selector::Class(format!("{}-{}", class, token.name)),
+                            vec![Declaration::new(
+                                property.to_string(),
+                                DeclarationValue::var(Self::custom_property(*kind, &token.name)),
+                            )],
+                            vec![],
+                        )
+                    })
+                })
+            })
+            .collect()
+    }
+
+    pub fn to_rule_set(&self, include_utilities: bool) -> RuleSet {
+        let mut rules = vec![self.root_rule()];
+        if include_utilities {
+            rules.extend(self.utility_rules());
+        }
+
+        RuleSet::new(rules, vec![], None)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Theme {
+    name: String,
+    tokens: Tokens,
+}
+
+impl Theme {
+    pub fn new(name: String, tokens: Tokens) -> Self {
+        Self { name, tokens }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Themes {
+    default: Theme,
+    alternates: Vec<Theme>,
+}
+
+impl Themes {
+    // alternate themes only need to define the tokens that differ from the default theme
+    pub fn new(default: Theme) -> Self {
+        Self {
+            default,
+            alternates: vec![],
+        }
+    }
+
+    pub fn add(&mut self, theme: Theme) {
+        self.alternates.push(theme);
+    }
+
+    // one complete stylesheet per theme (theme name, stylesheet), default theme first
+    pub fn separate_stylesheets(&self, base: &RuleSet) -> Vec<(String, RuleSet)> {
+        let mut sheets = vec![(
+            self.default.name.clone(),
+            RuleSet::new(
+                vec![self.default.tokens.root_rule()],
+                vec![base.clone()],
+                None,
+            ),
+        )];
+
+        for theme in &self.alternates {
+            let tokens = self.default.tokens.merged(&theme.tokens);
+            sheets.push((
+                theme.name.clone(),
+                RuleSet::new(vec![tokens.root_rule()], vec![base.clone()], None),
+            ));
+        }
+
+        sheets
+    }
+
+    // single stylesheet where alternate themes override tokens under [data-theme="name"]
+    pub fn scoped_stylesheet(&self, base: &RuleSet) -> RuleSet {
+        let mut rules = vec![self.default.tokens.root_rule()];
+
+        for theme in &self.alternates {
+            let declarations = theme
+                .tokens
+                .declarations_differing_from(&self.default.tokens);
+            if declarations.is_empty() {
+                continue;
+            }
+
+            rules.push(Rule::new(
+                Selector::AttributeValue(
+                    "data-theme".to_string(),
+                    theme.name.clone(),
+                    AttributeCase::Default,
+                ),
+                declarations,
+                vec![],
+            ));
+        }
+
+        RuleSet::new(rules, vec![base.clone()], None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::css::{Declaration, DeclarationValue, Rule, RuleSet, Selector};
+    use crate::tokens::{Theme, Themes, TokenKind, Tokens};
+
+    fn make_tokens() -> Tokens {
+        let mut tokens = Tokens::new();
+        tokens.insert(
+            TokenKind::Color,
+            "primary".to_string(),
+            DeclarationValue::Basic("#fa0".to_string()),
+        );
+        tokens.insert(
+            TokenKind::Spacing,
+            "sm".to_string(),
+            DeclarationValue::Basic("4px".to_string()),
+        );
+        tokens.insert(
+            TokenKind::Radius,
+            "md".to_string(),
+            DeclarationValue::Basic("6px".to_string()),
+        );
+        tokens
+    }
+
+    #[test]
+    fn root_rule() {
+        let tokens = make_tokens();
+
+        assert_eq!(
+            tokens.root_rule().to_string(),
+            ":root{--color-primary:#fa0;--spacing-sm:4px;--radius-md:6px;}"
+        );
+    }
+
+    #[test]
+    fn insert_replaces_existing() {
+        let mut tokens = make_tokens();
+        tokens.insert(
+            TokenKind::Color,
+            "primary".to_string(),
+            DeclarationValue::Basic("#000".to_string()),
+        );
+
+        assert_eq!(
+            tokens.root_rule().to_string(),
+            ":root{--color-primary:#000;--spacing-sm:4px;--radius-md:6px;}"
+        );
+    }
+
+    #[test]
+    fn var_reference() {
+        let tokens = make_tokens();
+
+        assert_eq!(
+            tokens.var(TokenKind::Color, "primary").unwrap().to_string(),
+            "var(--color-primary)"
+        );
+        assert_eq!(tokens.var(TokenKind::Color, "secondary"), None);
+    }
+
+    #[test]
+    fn rule_set_with_utilities() {
+        let tokens = make_tokens();
+
+        assert_eq!(
+            tokens.to_rule_set(true).to_string(),
+            ":root{--color-primary:#fa0;--spacing-sm:4px;--radius-md:6px;}\
+            .text-primary{color:var(--color-primary);}\
+            .bg-primary{background-color:var(--color-primary);}\
+            .p-sm{padding:var(--spacing-sm);}\
+            .m-sm{margin:var(--spacing-sm);}\
+            .gap-sm{gap:var(--spacing-sm);}\
+            .rounded-md{border-radius:var(--radius-md);}"
+        );
+    }
+
+    fn color_tokens(pairs: &[(&str, &str)]) -> Tokens {
+        let mut tokens = Tokens::new();
+        for (name, value) in pairs {
+            tokens.insert(
+                TokenKind::Color,
+                name.to_string(),
+                DeclarationValue::Basic(value.to_string()),
+            );
+        }
+        tokens
+    }
+
+    fn make_themes() -> Themes {
+        let mut themes = Themes::new(Theme::new(
+            "light".to_string(),
+            color_tokens(&[("bg", "white"), ("fg", "black")]),
+        ));
+        themes.add(Theme::new(
+            "dark".to_string(),
+            color_tokens(&[("bg", "black"), ("fg", "white")]),
+        ));
+        themes.add(Theme::new(
+            "contrast".to_string(),
+            color_tokens(&[("bg", "white"), ("fg", "navy")]),
+        ));
+        themes
+    }
+
+    fn base() -> RuleSet {
+        RuleSet::new(
+            vec![Rule::new(
+                Selector::Tag("body".to_string()),
+                vec![Declaration::new(
+                    "color".to_string(),
+                    DeclarationValue::var("--color-fg"),
+                )],
+                vec![],
+            )],
+            vec![],
+            None,
+        )
+    }
+
+    #[test]
+    fn separate_theme_stylesheets() {
+        let sheets = make_themes()
+            .separate_stylesheets(&base())
+            .into_iter()
+            .map(|(name, sheet)| (name, sheet.to_string()))
+            .collect::<Vec<(String, String)>>();
+
+        assert_eq!(
+            sheets,
+            vec![
+                (
+                    "light".to_string(),
+                    ":root{--color-bg:white;--color-fg:black;}body{color:var(--color-fg);}"
+                        .to_string()
+                ),
+                (
+                    "dark".to_string(),
+                    ":root{--color-bg:black;--color-fg:white;}body{color:var(--color-fg);}"
+                        .to_string()
+                ),
+                (
+                    "contrast".to_string(),
+                    ":root{--color-bg:white;--color-fg:navy;}body{color:var(--color-fg);}"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn scoped_theme_stylesheet() {
+        assert_eq!(
+            make_themes().scoped_stylesheet(&base()).to_string(),
+            ":root{--color-bg:white;--color-fg:black;}\
+            [data-theme=\"dark\"]{--color-bg:black;--color-fg:white;}\
+            [data-theme=\"contrast\"]{--color-fg:navy;}\
+            body{color:var(--color-fg);}"
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::css::{Declaration, DeclarationValue};
+use crate::values::Float;
+
+// angles are in degrees
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum TransformFunction {
+    Translate(DeclarationValue, DeclarationValue), // lengths or percentages
+    Rotate(Float),
+    Scale(Float, Float),
+    Skew(Float, Float),
+    Matrix([Float; 6]),
+}
+
+fn degrees(angle: &Float) -> String {
+    format!("{}deg", angle)
+}
+
+impl TransformFunction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TransformFunction::Translate(..) => "translate",
+            TransformFunction::Rotate(_) => "rotate",
+            TransformFunction::Scale(..) => "scale",
+            TransformFunction::Skew(..) => "skew",
+            TransformFunction::Matrix(_) => "matrix",
+        }
+    }
+
+    // the second argument is left out where it defaults to the first or to zero
+    fn arguments(&self) -> Vec<String> {
+        match self {
+            TransformFunction::Translate(x, y) => match y {
+                DeclarationValue::Length(value, _)
+                | DeclarationValue::Percentage(value)
+                | DeclarationValue::Number(value)
+                    if value.get() == 0.0 =>
+                {
+                    vec![x.to_string()]
+                }
+                y => vec![x.to_string(), y.to_string()],
+            },
+            TransformFunction::Rotate(angle) => vec![degrees(angle)],
+            TransformFunction::Scale(x, y) if x == y => vec![x.to_string()],
+            TransformFunction::Scale(x, y) => vec![x.to_string(), y.to_string()],
+            TransformFunction::Skew(x, y) if y.get() == 0.0 => vec![degrees(x)],
+            TransformFunction::Skew(x, y) => vec![degrees(x), degrees(y)],
+            TransformFunction::Matrix(values) => values.iter().map(Float::to_string).collect(),
+        }
+    }
+
+    pub fn to_value(&self) -> DeclarationValue {
+        DeclarationValue::Function(self.name().to_string(), self.arguments())
+    }
+}
+
+// functions of the transform property in the order they are written,
+// e.g. Transform::new().translate(px(10), percentage(50)).rotate(45)
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct Transform {
+    functions: Vec<TransformFunction>,
+}
+
+impl Transform {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn function(mut self, function: TransformFunction) -> Self {
+        self.functions.push(function);
+        self
+    }
+
+    pub fn translate(self, x: DeclarationValue, y: DeclarationValue) -> Self {
+        self.function(TransformFunction::Translate(x, y))
+    }
+
+    pub fn rotate(self, degrees: impl Into<Float>) -> Self {
+        self.function(TransformFunction::Rotate(degrees.into()))
+    }
+
+    pub fn scale(self, scale: impl Into<Float>) -> Self {
+        let scale = scale.into();
+        self.function(TransformFunction::Scale(scale, scale))
+    }
+
+    pub fn scale_xy(self, x: impl Into<Float>, y: impl Into<Float>) -> Self {
+        self.function(TransformFunction::Scale(x.into(), y.into()))
+    }
+
+    pub fn skew(self, x: impl Into<Float>, y: impl Into<Float>) -> Self {
+        self.function(TransformFunction::Skew(x.into(), y.into()))
+    }
+
+    pub fn matrix(self, values: [f64; 6]) -> Self {
+        self.function(TransformFunction::Matrix(values.map(Float::new)))
+    }
+
+    pub fn functions(&self) -> &[TransformFunction] {
+        &self.functions
+    }
+
+    // none without any functions
+    pub fn to_value(&self) -> DeclarationValue {
+        match self.functions.as_slice() {
+            [] => DeclarationValue::keyword("none"),
+            [function] => function.to_value(),
+            functions => DeclarationValue::space_list(
+                functions.iter().map(TransformFunction::to_value).collect(),
+            ),
+        }
+    }
+
+    pub fn declaration(&self) -> Declaration {
+        Declaration::new("transform".to_string(), self.to_value())
+    }
+}
+
+impl From<Transform> for DeclarationValue {
+    fn from(transform: Transform) -> Self {
+        transform.to_value()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use crate::css::{px, DeclarationValue, RuleSet};
+    use crate::transform::Transform;
+
+    #[test]
+    fn functions() {
+        let transform = Transform::new()
+            .translate(px(10), DeclarationValue::percentage(-50))
+            .rotate(45)
+            .scale(1.5)
+            .skew(10, 0);
+        let declaration = transform.declaration();
+
+        assert_eq!(
+            declaration.to_string(),
+            "transform:translate(10px,-50%) rotate(45deg) scale(1.5) skew(10deg);"
+        );
+        assert_eq!(declaration.estimated_len(), declaration.to_string().len());
+        let parsed = RuleSet::parse(&["a{", &declaration.to_string(), "}"].concat()).unwrap();
+        assert_eq!(parsed.rules()[0].declarations(), vec![declaration]);
+    }
+
+    #[test]
+    fn defaults() {
+        assert_eq!(
+            Transform::new()
+                .translate(px(4), px(0))
+                .scale_xy(1, -1)
+                .to_value()
+                .to_string(),
+            "translate(4px) scale(1,-1)"
+        );
+        assert_eq!(
+            Transform::new()
+                .matrix([1.0, 0.0, 0.0, 1.0, 20.0, 0.5])
+                .to_value()
+                .to_string(),
+            "matrix(1,0,0,1,20,0.5)"
+        );
+        assert_eq!(
+            Transform::new().declaration().to_string(),
+            "transform:none;"
+        );
+    }
+}
@@ -0,0 +1,856 @@
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::at_rule::{AtRule, AtRuleBlock};
+use crate::color::Color;
+use crate::css::{Declaration, DeclarationValue, Rule, RuleSet, Selector};
+use crate::html::{is_void_element, Attribute, Node};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ValidationError {
+    code: &'static str,
+    message: String,
+    location: String,
+}
+
+impl ValidationError {
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    // path of tags from the root to the offending element (e.g. table>td),
+    // for css the selector of the rule or the at-rule holding the declaration
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+// sorted list of the elements defined by the html standard
+#[rustfmt::skip]
+const HTML_ELEMENTS: &[&str] = &[
+    "a", "abbr", "address", "area", "article", "aside", "audio", "b", "base", "bdi", "bdo",
+    "blockquote", "body", "br", "button", "canvas", "caption", "cite", "code", "col", "colgroup",
+    "data", "datalist", "dd", "del", "details", "dfn", "dialog", "div", "dl", "dt", "em", "embed",
+    "fieldset", "figcaption", "figure", "footer", "form", "h1", "h2", "h3", "h4", "h5", "h6",
+    "head", "header", "hgroup", "hr", "html", "i", "iframe", "img", "input", "ins", "kbd", "label",
+    "legend", "li", "link", "main", "map", "mark", "math", "menu", "meta", "meter", "nav",
+    "noscript", "object", "ol", "optgroup", "option", "output", "p", "picture", "pre", "progress",
+    "q", "rp", "rt", "ruby", "s", "samp", "script", "search", "section", "select", "slot", "small",
+    "source", "span", "strong", "style", "sub", "summary", "sup", "svg", "table", "tbody", "td",
+    "template", "textarea", "tfoot", "th", "thead", "time", "title", "tr", "track", "u", "ul",
+    "var", "video", "wbr",
+];
+
+#[rustfmt::skip]
+const GLOBAL_ATTRIBUTES: &[&str] = &[
+    "accesskey", "autocapitalize", "autofocus", "class", "contenteditable", "dir", "draggable",
+    "enterkeyhint", "hidden", "id", "inert", "inputmode", "is", "itemid", "itemprop", "itemref",
+    "itemscope", "itemtype", "lang", "nonce", "popover", "role", "slot", "spellcheck", "style",
+    "tabindex", "title", "translate",
+];
+
+// attributes allowed on top of the global ones, elements not listed only allow global attributes
+#[rustfmt::skip]
+const ELEMENT_ATTRIBUTES: &[(&str, &[&str])] = &[
+    ("a", &["download", "href", "hreflang", "ping", "referrerpolicy", "rel", "target", "type"]),
+    ("area", &[
+        "alt", "coords", "download", "href", "ping", "referrerpolicy", "rel", "shape", "target",
+    ]),
+    ("audio", &["autoplay", "controls", "crossorigin", "loop", "muted", "preload", "src"]),
+    ("base", &["href", "target"]),
+    ("blockquote", &["cite"]),
+    ("button", &[
+        "disabled", "form", "formaction", "formenctype", "formmethod", "formnovalidate",
+        "formtarget", "name", "popovertarget", "popovertargetaction", "type", "value",
+    ]),
+    ("canvas", &["height", "width"]),
+    ("col", &["span"]),
+    ("colgroup", &["span"]),
+    ("data", &["value"]),
+    ("del", &["cite", "datetime"]),
+    ("details", &["name", "open"]),
+    ("dialog", &["open"]),
+    ("embed", &["height", "src", "type", "width"]),
+    ("fieldset", &["disabled", "form", "name"]),
+    ("form", &[
+        "accept-charset", "action", "autocomplete", "enctype", "method", "name", "novalidate",
+        "rel", "target",
+    ]),
+    ("html", &["xmlns"]),
+    ("iframe", &[
+        "allow", "allowfullscreen", "height", "loading", "name", "referrerpolicy", "sandbox", "src",
+        "srcdoc", "width",
+    ]),
+    ("img", &[
+        "alt", "crossorigin", "decoding", "fetchpriority", "height", "ismap", "loading",
+        "referrerpolicy", "sizes", "src", "srcset", "usemap", "width",
+    ]),
+    ("input", &[
+        "accept", "alt", "autocomplete", "checked", "dirname", "disabled", "form", "formaction",
+        "formenctype", "formmethod", "formnovalidate", "formtarget", "height", "list", "max",
+        "maxlength", "min", "minlength", "multiple", "name", "pattern", "placeholder",
+        "popovertarget", "popovertargetaction", "readonly", "required", "size", "src", "step",
+        "type", "value", "width",
+    ]),
+    ("ins", &["cite", "datetime"]),
+    ("label", &["for"]),
+    ("li", &["value"]),
+    ("link", &[
+        "as", "blocking", "crossorigin", "disabled", "fetchpriority", "href", "hreflang",
+        "imagesizes", "imagesrcset", "integrity", "media", "referrerpolicy", "rel", "sizes", "type",
+    ]),
+    ("map", &["name"]),
+    ("meta", &["charset", "content", "http-equiv", "itemprop", "media", "name", "property"]),
+    ("meter", &["high", "low", "max", "min", "optimum", "value"]),
+    ("object", &["data", "form", "height", "name", "type", "width"]),
+    ("ol", &["reversed", "start", "type"]),
+    ("optgroup", &["disabled", "label"]),
+    ("option", &["disabled", "label", "selected", "value"]),
+    ("output", &["for", "form", "name"]),
+    ("progress", &["max", "value"]),
+    ("q", &["cite"]),
+    ("script", &[
+        "async", "blocking", "crossorigin", "defer", "fetchpriority", "integrity", "nomodule",
+        "referrerpolicy", "src", "type",
+    ]),
+    ("select", &["autocomplete", "disabled", "form", "multiple", "name", "required", "size"]),
+    ("slot", &["name"]),
+    ("source", &["height", "media", "sizes", "src", "srcset", "type", "width"]),
+    ("style", &["blocking", "media"]),
+    ("td", &["colspan", "headers", "rowspan"]),
+    ("template", &["shadowrootclonable", "shadowrootdelegatesfocus", "shadowrootmode"]),
+    ("textarea", &[
+        "autocomplete", "cols", "dirname", "disabled", "form", "maxlength", "minlength", "name",
+        "placeholder", "readonly", "required", "rows", "wrap",
+    ]),
+    ("th", &["abbr", "colspan", "headers", "rowspan", "scope"]),
+    ("time", &["datetime"]),
+    ("track", &["default", "kind", "label", "src", "srclang"]),
+    ("video", &[
+        "autoplay", "controls", "crossorigin", "height", "loop", "muted", "playsinline", "poster",
+        "preload", "src", "width",
+    ]),
+];
+
+// custom elements need a dash and have to start with a lowercase letter
+fn is_custom_element(tag: &str) -> bool {
+    tag.starts_with(|c: char| c.is_ascii_lowercase()) && tag.contains('-')
+}
+
+// characters that would end or break the name when rendered
+fn is_legal_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.chars().any(|c| {
+            c.is_whitespace() || c.is_control() || matches!(c, '"' | '\'' | '<' | '>' | '/' | '=')
+        })
+}
+
+fn is_allowed_attribute(tag: &str, name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    GLOBAL_ATTRIBUTES.contains(&name.as_str())
+        || name.starts_with("data-")
+        || name.starts_with("aria-")
+        || name.starts_with("on")
+        || ELEMENT_ATTRIBUTES
+            .iter()
+            .find(|(element, _)| *element == tag)
+            .is_some_and(|(_, attributes)| attributes.contains(&name.as_str()))
+}
+
+// the element a child has to be directly inside, if any
+fn required_parents(tag: &str) -> Option<&'static [&'static str]> {
+    match tag {
+        "li" => Some(&["ul", "ol", "menu"]),
+        "tr" => Some(&["table", "thead", "tbody", "tfoot"]),
+        "td" | "th" => Some(&["tr"]),
+        "caption" | "colgroup" | "thead" | "tbody" | "tfoot" => Some(&["table"]),
+        "col" => Some(&["colgroup"]),
+        _ => None,
+    }
+}
+
+fn allowed_children(tag: &str) -> Option<&'static [&'static str]> {
+    match tag {
+        "table" => Some(&[
+            "caption", "colgroup", "thead", "tbody", "tfoot", "tr", "script", "template",
+        ]),
+        "thead" | "tbody" | "tfoot" => Some(&["tr", "script", "template"]),
+        "tr" => Some(&["td", "th", "script", "template"]),
+        "colgroup" => Some(&["col", "template"]),
+        _ => None,
+    }
+}
+
+struct Validation {
+    errors: Vec<ValidationError>,
+    ids: BTreeSet<String>,
+}
+
+impl Validation {
+    fn add(&mut self, code: &'static str, message: String, location: &str) {
+        self.errors.push(ValidationError {
+            code,
+            message,
+            location: location.to_string(),
+        });
+    }
+
+    // parent is the closest element, fragments are not part of the rendered page
+    fn visit(&mut self, node: &Node, parent: Option<&str>, parent_location: &str, foreign: bool) {
+        let (tag, attributes, children) = match node {
+            Node::Element {
+                tag,
+                attributes,
+                children,
+                ..
+            } => (tag.to_ascii_lowercase(), attributes, children),
+            Node::Fragment(children) => {
+                children
+                    .iter()
+                    .for_each(|c| self.visit(c, parent, parent_location, foreign));
+                return;
+            }
+            Node::Text(text) => {
+                if let Some(parent) = parent.filter(|p| allowed_children(p).is_some()) {
+                    if !text.trim().is_empty() {
+                        self.add(
+                            "text-not-allowed",
+                            format!("text is not allowed directly inside <{}>", parent),
+                            parent_location,
+                        );
+                    }
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        let location = match parent_location.is_empty() {
+            true => tag.clone(),
+            false => format!("{}>{}", parent_location, tag),
+        };
+
+        if !is_legal_name(&tag) {
+            self.add(
+                "tag-name",
+                format!("<{}> is not a legal tag name", tag),
+                &location,
+            );
+        } else if !foreign
+            && !is_custom_element(&tag)
+            && HTML_ELEMENTS.binary_search(&tag.as_str()).is_err()
+        {
+            self.add(
+                "unknown-element",
+                format!("<{}> is not an html element", tag),
+                &location,
+            );
+        }
+
+        // svg, mathml and custom elements define their own attributes
+        let known = !foreign
+            && !matches!(tag.as_str(), "svg" | "math")
+            && HTML_ELEMENTS.binary_search(&tag.as_str()).is_ok();
+        for attribute in attributes.iter().map(Attribute::name) {
+            if !is_legal_name(attribute) {
+                self.add(
+                    "attribute-name",
+                    format!("\"{}\" is not a legal attribute name", attribute),
+                    &location,
+                );
+            } else if known && !is_allowed_attribute(&tag, attribute) {
+                self.add(
+                    "attribute-not-allowed",
+                    format!("{} is not a valid attribute of <{}>", attribute, tag),
+                    &location,
+                );
+            }
+        }
+
+        // ids built from data easily collide, only the first element is found by getElementById
+        let id = attributes
+            .iter()
+            .find(|a| a.name().eq_ignore_ascii_case("id"))
+            .and_then(Attribute::value);
+        if let Some(id) = id {
+            if !self.ids.insert(id.to_string()) {
+                self.add(
+                    "duplicate-id",
+                    format!("id \"{}\" is used by more than one element", id),
+                    &location,
+                );
+            }
+        }
+
+        if !foreign && is_void_element(&tag) && !children.is_empty() {
+            self.add(
+                "void-children",
+                format!("<{}> is a void element and can not have children", tag),
+                &location,
+            );
+        }
+
+        if let Some(parents) = required_parents(&tag) {
+            if !parent.is_some_and(|p| parents.contains(&p)) {
+                self.add(
+                    "misplaced-element",
+                    format!("<{}> must be inside one of <{}>", tag, parents.join(">, <")),
+                    &location,
+                );
+            }
+        }
+
+        if let (Some(allowed), Some(parent)) = (parent.and_then(allowed_children), parent) {
+            if required_parents(&tag).is_none() && !allowed.contains(&tag.as_str()) {
+                self.add(
+                    "misplaced-element",
+                    format!("<{}> is not allowed inside <{}>", tag, parent),
+                    &location,
+                );
+            }
+        }
+
+        // foreignObject content is html again
+        let foreign = match tag.as_str() {
+            "svg" | "math" => true,
+            "foreignobject" => false,
+            _ => foreign,
+        };
+        for child in children {
+            self.visit(child, Some(&tag), &location, foreign);
+        }
+    }
+}
+
+// structural problems browsers would silently repair, in document order
+pub fn validate(root: &Node) -> Vec<ValidationError> {
+    let mut validation = Validation {
+        errors: Vec::new(),
+        ids: BTreeSet::new(),
+    };
+    validation.visit(root, None, "", false);
+    validation.errors
+}
+
+// sorted list of the standard css properties
+#[rustfmt::skip]
+const CSS_PROPERTIES: &[&str] = &[
+    "accent-color", "align-content", "align-items", "align-self", "all", "anchor-name", "animation",
+    "animation-composition", "animation-delay", "animation-direction", "animation-duration",
+    "animation-fill-mode", "animation-iteration-count", "animation-name", "animation-play-state",
+    "animation-range", "animation-timeline", "animation-timing-function", "appearance",
+    "aspect-ratio", "backdrop-filter", "backface-visibility", "background", "background-attachment",
+    "background-blend-mode", "background-clip", "background-color", "background-image",
+    "background-origin", "background-position", "background-position-x", "background-position-y",
+    "background-repeat", "background-size", "block-size", "border", "border-block",
+    "border-block-color", "border-block-end", "border-block-end-color", "border-block-end-style",
+    "border-block-end-width", "border-block-start", "border-block-start-color",
+    "border-block-start-style", "border-block-start-width", "border-block-style",
+    "border-block-width", "border-bottom", "border-bottom-color", "border-bottom-left-radius",
+    "border-bottom-right-radius", "border-bottom-style", "border-bottom-width", "border-collapse",
+    "border-color", "border-end-end-radius", "border-end-start-radius", "border-image",
+    "border-image-outset", "border-image-repeat", "border-image-slice", "border-image-source",
+    "border-image-width", "border-inline", "border-inline-color", "border-inline-end",
+    "border-inline-end-color", "border-inline-end-style", "border-inline-end-width",
+    "border-inline-start", "border-inline-start-color", "border-inline-start-style",
+    "border-inline-start-width", "border-inline-style", "border-inline-width", "border-left",
+    "border-left-color", "border-left-style", "border-left-width", "border-radius", "border-right",
+    "border-right-color", "border-right-style", "border-right-width", "border-spacing",
+    "border-start-end-radius", "border-start-start-radius", "border-style", "border-top",
+    "border-top-color", "border-top-left-radius", "border-top-right-radius", "border-top-style",
+    "border-top-width", "border-width", "bottom", "box-decoration-break", "box-shadow",
+    "box-sizing", "break-after", "break-before", "break-inside", "caption-side", "caret-color",
+    "clear", "clip", "clip-path", "clip-rule", "color", "color-scheme", "column-count",
+    "column-fill", "column-gap", "column-rule", "column-rule-color", "column-rule-style",
+    "column-rule-width", "column-span", "column-width", "columns", "contain",
+    "contain-intrinsic-block-size", "contain-intrinsic-height", "contain-intrinsic-inline-size",
+    "contain-intrinsic-size", "contain-intrinsic-width", "container", "container-name",
+    "container-type", "content", "content-visibility", "counter-increment", "counter-reset",
+    "counter-set", "cursor", "direction", "display", "empty-cells", "field-sizing", "fill",
+    "fill-opacity", "fill-rule", "filter", "flex", "flex-basis", "flex-direction", "flex-flow",
+    "flex-grow", "flex-shrink", "flex-wrap", "float", "flood-color", "flood-opacity", "font",
+    "font-family", "font-feature-settings", "font-kerning", "font-language-override",
+    "font-optical-sizing", "font-palette", "font-size", "font-size-adjust", "font-stretch",
+    "font-style", "font-synthesis", "font-variant", "font-variant-alternates", "font-variant-caps",
+    "font-variant-east-asian", "font-variant-ligatures", "font-variant-numeric",
+    "font-variant-position", "font-variation-settings", "font-weight", "forced-color-adjust", "gap",
+    "grid", "grid-area", "grid-auto-columns", "grid-auto-flow", "grid-auto-rows", "grid-column",
+    "grid-column-end", "grid-column-start", "grid-row", "grid-row-end", "grid-row-start",
+    "grid-template", "grid-template-areas", "grid-template-columns", "grid-template-rows",
+    "hanging-punctuation", "height", "hyphenate-character", "hyphens", "image-orientation",
+    "image-rendering", "initial-letter", "inline-size", "inset", "inset-block", "inset-block-end",
+    "inset-block-start", "inset-inline", "inset-inline-end", "inset-inline-start", "isolation",
+    "justify-content", "justify-items", "justify-self", "left", "letter-spacing", "lighting-color",
+    "line-break", "line-clamp", "line-height", "list-style", "list-style-image",
+    "list-style-position", "list-style-type", "margin", "margin-block", "margin-block-end",
+    "margin-block-start", "margin-bottom", "margin-inline", "margin-inline-end",
+    "margin-inline-start", "margin-left", "margin-right", "margin-top", "marker", "marker-end",
+    "marker-mid", "marker-start", "mask", "mask-border", "mask-clip", "mask-composite",
+    "mask-image", "mask-mode", "mask-origin", "mask-position", "mask-repeat", "mask-size",
+    "mask-type", "math-depth", "math-style", "max-block-size", "max-height", "max-inline-size",
+    "max-width", "min-block-size", "min-height", "min-inline-size", "min-width", "mix-blend-mode",
+    "object-fit", "object-position", "offset", "offset-anchor", "offset-distance", "offset-path",
+    "offset-position", "offset-rotate", "opacity", "order", "orphans", "outline", "outline-color",
+    "outline-offset", "outline-style", "outline-width", "overflow", "overflow-anchor",
+    "overflow-block", "overflow-clip-margin", "overflow-inline", "overflow-wrap", "overflow-x",
+    "overflow-y", "overscroll-behavior", "overscroll-behavior-block", "overscroll-behavior-inline",
+    "overscroll-behavior-x", "overscroll-behavior-y", "padding", "padding-block",
+    "padding-block-end", "padding-block-start", "padding-bottom", "padding-inline",
+    "padding-inline-end", "padding-inline-start", "padding-left", "padding-right", "padding-top",
+    "page", "page-break-after", "page-break-before", "page-break-inside", "paint-order",
+    "perspective", "perspective-origin", "place-content", "place-items", "place-self",
+    "pointer-events", "position", "position-anchor", "position-area", "position-try",
+    "position-try-fallbacks", "print-color-adjust", "quotes", "r", "resize", "right", "rotate",
+    "row-gap", "ruby-align", "ruby-position", "rx", "ry", "scale", "scroll-behavior",
+    "scroll-margin", "scroll-margin-block", "scroll-margin-block-end", "scroll-margin-block-start",
+    "scroll-margin-bottom", "scroll-margin-inline", "scroll-margin-inline-end",
+    "scroll-margin-inline-start", "scroll-margin-left", "scroll-margin-right", "scroll-margin-top",
+    "scroll-padding", "scroll-padding-block", "scroll-padding-block-end",
+    "scroll-padding-block-start", "scroll-padding-bottom", "scroll-padding-inline",
+    "scroll-padding-inline-end", "scroll-padding-inline-start", "scroll-padding-left",
+    "scroll-padding-right", "scroll-padding-top", "scroll-snap-align", "scroll-snap-stop",
+    "scroll-snap-type", "scroll-timeline", "scrollbar-color", "scrollbar-gutter", "scrollbar-width",
+    "shape-image-threshold", "shape-margin", "shape-outside", "shape-rendering", "size",
+    "stop-color", "stop-opacity", "stroke", "stroke-dasharray", "stroke-dashoffset",
+    "stroke-linecap", "stroke-linejoin", "stroke-miterlimit", "stroke-opacity", "stroke-width",
+    "tab-size", "table-layout", "text-align", "text-align-last", "text-anchor",
+    "text-combine-upright", "text-decoration", "text-decoration-color", "text-decoration-line",
+    "text-decoration-skip-ink", "text-decoration-style", "text-decoration-thickness",
+    "text-emphasis", "text-emphasis-color", "text-emphasis-position", "text-emphasis-style",
+    "text-indent", "text-justify", "text-orientation", "text-overflow", "text-rendering",
+    "text-shadow", "text-size-adjust", "text-transform", "text-underline-offset",
+    "text-underline-position", "text-wrap", "text-wrap-mode", "text-wrap-style", "top",
+    "touch-action", "transform", "transform-box", "transform-origin", "transform-style",
+    "transition", "transition-behavior", "transition-delay", "transition-duration",
+    "transition-property", "transition-timing-function", "translate", "unicode-bidi", "user-select",
+    "vector-effect", "vertical-align", "view-timeline", "view-transition-name", "visibility",
+    "white-space", "white-space-collapse", "widows", "width", "will-change", "word-break",
+    "word-spacing", "writing-mode", "x", "y", "z-index", "zoom",
+];
+
+fn takes_color(property: &str) -> bool {
+    matches!(property, "color" | "fill" | "stroke")
+        || property.ends_with("-color") && property != "scrollbar-color"
+}
+
+fn takes_length(property: &str) -> bool {
+    [
+        "margin",
+        "padding",
+        "inset",
+        "scroll-margin",
+        "scroll-padding",
+    ]
+    .iter()
+    .any(|prefix| property.starts_with(prefix))
+        || property.starts_with("border")
+            && !property.starts_with("border-image")
+            && (property.ends_with("-width") || property.ends_with("-radius"))
+        || matches!(
+            property,
+            "width"
+                | "height"
+                | "min-width"
+                | "max-width"
+                | "min-height"
+                | "max-height"
+                | "block-size"
+                | "inline-size"
+                | "top"
+                | "right"
+                | "bottom"
+                | "left"
+                | "gap"
+                | "row-gap"
+                | "column-gap"
+                | "flex-basis"
+                | "font-size"
+                | "letter-spacing"
+                | "word-spacing"
+                | "text-indent"
+                | "outline-width"
+                | "outline-offset"
+        )
+}
+
+// edits between two names where swapping neighbouring letters counts as one, a common typo
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+// the known property closest to a misspelled one
+fn suggestion(property: &str) -> Option<&'static str> {
+    CSS_PROPERTIES
+        .iter()
+        .map(|known| (edit_distance(property, known), *known))
+        .filter(|(distance, _)| *distance <= 2)
+        .min()
+        .map(|(_, known)| known)
+}
+
+struct CssValidation {
+    errors: Vec<ValidationError>,
+}
+
+impl CssValidation {
+    fn add(&mut self, code: &'static str, message: String, location: &str) {
+        self.errors.push(ValidationError {
+            code,
+            message,
+            location: location.to_string(),
+        });
+    }
+
+    fn color(&mut self, property: &str, value: &DeclarationValue, location: &str) {
+        if let DeclarationValue::List(values, _) = value {
+            values
+                .iter()
+                .for_each(|value| self.color(property, value, location));
+            return;
+        }
+        let valid = match value {
+            DeclarationValue::Keyword(keyword) => {
+                Color::named(keyword).is_some()
+                    || [
+                        "currentcolor",
+                        "none",
+                        "inherit",
+                        "initial",
+                        "unset",
+                        "revert",
+                        "revert-layer",
+                    ]
+                    .iter()
+                    .any(|k| k.eq_ignore_ascii_case(keyword))
+            }
+            DeclarationValue::Basic(text) => !text.starts_with('#') || Color::parse(text).is_ok(),
+            DeclarationValue::Length(..)
+            | DeclarationValue::Percentage(_)
+            | DeclarationValue::Number(_) => false,
+            _ => true,
+        };
+        if !valid {
+            self.add(
+                "invalid-color",
+                format!("{} is not a color for {}", value, property),
+                location,
+            );
+        }
+    }
+
+    // a number other than zero without a unit is ignored by browsers
+    fn length(&mut self, property: &str, value: &DeclarationValue, location: &str) {
+        match value {
+            DeclarationValue::Number(number) if number.get() != 0.0 => self.add(
+                "missing-unit",
+                format!("{} needs a unit for {}", value, property),
+                location,
+            ),
+            DeclarationValue::Length(_, unit) if !unit.is_length() => self.add(
+                "invalid-unit",
+                format!("{} is not a length for {}", value, property),
+                location,
+            ),
+            DeclarationValue::List(values, _) => values
+                .iter()
+                .for_each(|value| self.length(property, value, location)),
+            _ => (),
+        }
+    }
+
+    fn declaration(&mut self, declaration: &Declaration, location: &str) {
+        let property = declaration.property().to_ascii_lowercase();
+        // custom properties are free to name and vendor prefixed ones aren't listed
+        if property.starts_with('-') {
+            return;
+        }
+        if CSS_PROPERTIES.binary_search(&property.as_str()).is_err() {
+            let message = match suggestion(&property) {
+                Some(known) => format!(
+                    "{} is not a known css property, did you mean {}?",
+                    property, known
+                ),
+                None => format!("{} is not a known css property", property),
+            };
+            self.add("unknown-property", message, location);
+        } else if takes_color(&property) {
+            self.color(&property, declaration.value(), location);
+        } else if takes_length(&property) {
+            self.length(&property, declaration.value(), location);
+        }
+    }
+
+    fn rule(&mut self, rule: &Rule, selector: &Selector) {
+        let location = selector.to_string();
+        for declaration in rule.declarations() {
+            self.declaration(declaration, &location);
+        }
+        for sub_rule in rule.sub_rules() {
+            self.rule(sub_rule, &sub_rule.selector().nested_in(selector));
+        }
+    }
+
+    // descriptors of @font-face, @page and unknown at-rules are not properties
+    fn set(&mut self, set: &RuleSet) {
+        for at_rule in set.at_rules() {
+            match at_rule {
+                AtRule::Keyframes(keyframes) => {
+                    let location = format!("@keyframes {}", keyframes.name());
+                    for keyframe in keyframes.keyframes() {
+                        for declaration in keyframe.declarations() {
+                            self.declaration(declaration, &location);
+                        }
+                    }
+                }
+                AtRule::Layer(_, set)
+                | AtRule::Container(_, set)
+                | AtRule::Generic {
+                    block: Some(AtRuleBlock::Rules(set)),
+                    ..
+                } => self.set(set),
+                _ => (),
+            }
+        }
+        for rule in set.rules() {
+            self.rule(rule, rule.selector());
+        }
+        for sub_set in set.sub_sets() {
+            self.set(sub_set);
+        }
+    }
+}
+
+// unknown properties and values browsers would drop, opt-in since it only knows the standard
+// properties and checks the syntax of colors and lengths
+pub fn validate_css(set: &RuleSet) -> Vec<ValidationError> {
+    let mut validation = CssValidation { errors: Vec::new() };
+    validation.set(set);
+    validation.errors
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Element;
+    use crate::css::RuleSet;
+    use crate::head::Head;
+    use crate::html::Node;
+    use crate::social::SocialMeta;
+    use crate::validate::{validate, validate_css, CSS_PROPERTIES, HTML_ELEMENTS};
+
+    fn codes(root: &Node) -> Vec<&'static str> {
+        validate(root).iter().map(|e| e.code()).collect()
+    }
+
+    #[test]
+    fn html_elements_are_sorted_and_unique() {
+        assert!(HTML_ELEMENTS.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn valid_tree() {
+        let root = crate::html! {
+            body class="page" data-id="1" {
+                ul { (vec![crate::html! { li {} }]) }
+                a href="/" onclick="go()" {}
+                my-widget size="large" {}
+                svg viewBox="0 0 1 1" { circle r="1" {} }
+            }
+        };
+
+        assert_eq!(validate(&root), vec![]);
+    }
+
+    #[test]
+    fn head_helpers() {
+        let mut head = Head::new();
+        head.set_title("Post".to_string());
+        head.add_meta("author".to_string(), "x".to_string());
+        head.add_link("stylesheet".to_string(), "/a.css".to_string());
+        head.add_script("/a.js".to_string());
+        SocialMeta::new("Post")
+            .description("About things")
+            .image("https://x.test/a.png")
+            .url("https://x.test/post")
+            .apply(&mut head);
+        head.add(crate::html! { meta itemprop="name" content="Post"; });
+
+        assert_eq!(validate(&head.into_node()), vec![]);
+    }
+
+    #[test]
+    fn names() {
+        let root = Element::new("body")
+            .attr("on click", "")
+            .child(Element::new("blink"))
+            .child(Element::new("a b"))
+            .build();
+
+        assert_eq!(
+            codes(&root),
+            vec!["attribute-name", "unknown-element", "tag-name"]
+        );
+    }
+
+    #[test]
+    fn void_children_and_attributes() {
+        let root = crate::html! { img src="/a.png" href="/" { "caption" } };
+        let errors = validate(&root);
+
+        assert_eq!(
+            errors.iter().map(|e| e.code()).collect::<Vec<_>>(),
+            vec!["attribute-not-allowed", "void-children"]
+        );
+        assert_eq!(
+            errors[0].message(),
+            "href is not a valid attribute of <img>"
+        );
+    }
+
+    #[test]
+    fn list_items() {
+        let root = crate::html! { div { li {} } };
+        let errors = validate(&root);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message(),
+            "<li> must be inside one of <ul>, <ol>, <menu>"
+        );
+        assert_eq!(errors[0].location(), "div>li");
+    }
+
+    #[test]
+    fn tables() {
+        let root = crate::html! {
+            table {
+                td {}
+                div {}
+                "loose"
+                tbody { tr { th scope="row" {} } }
+            }
+        };
+
+        assert_eq!(
+            codes(&root),
+            vec!["misplaced-element", "misplaced-element", "text-not-allowed"]
+        );
+        assert_eq!(validate(&root)[1].location(), "table>div");
+    }
+
+    #[test]
+    fn duplicate_ids() {
+        let root = Element::new("ul")
+            .id("items")
+            .child(Element::new("li").id("item-1"))
+            .child(Element::new("li").id("item-1").attr("data-x=\"1\"", ""))
+            .child(Element::new("li").attr("ID", "items"))
+            .build();
+        let errors = validate(&root);
+
+        assert_eq!(
+            codes(&root),
+            vec!["attribute-name", "duplicate-id", "duplicate-id"]
+        );
+        assert_eq!(
+            errors[1].message(),
+            "id \"item-1\" is used by more than one element"
+        );
+        assert_eq!(errors[2].location(), "ul>li");
+    }
+
+    #[test]
+    fn css_properties_are_sorted_and_unique() {
+        assert!(CSS_PROPERTIES.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn css_declarations() {
+        let set = RuleSet::parse(
+            ".a { colr: red; color: 12px; margin: 0 10; width: 2s; --gap: 4; -webkit-line-clamp: 2; COLOR: bleu; border-color: #12 } @media print { .b { paddin: 0; padding: 0 } } @keyframes fade { to { opacity: 0; widht: 1px } }",
+        )
+        .unwrap();
+        let errors = validate_css(&set);
+
+        assert_eq!(
+            errors.iter().map(|e| e.code()).collect::<Vec<_>>(),
+            vec![
+                "unknown-property",
+                "invalid-color",
+                "missing-unit",
+                "invalid-unit",
+                "invalid-color",
+                "invalid-color",
+                "unknown-property",
+                "unknown-property",
+            ]
+        );
+        assert_eq!(
+            errors[0].message(),
+            "colr is not a known css property, did you mean color?"
+        );
+        assert_eq!(errors[2].message(), "10 needs a unit for margin");
+        assert_eq!(errors[2].location(), ".a");
+        assert_eq!(errors[6].location(), ".b");
+        assert_eq!(
+            errors[7].message(),
+            "widht is not a known css property, did you mean width?"
+        );
+        assert_eq!(errors[7].location(), "@keyframes fade");
+        assert_eq!(
+            validate_css(&RuleSet::parse("p { float: lefty; zzzzzz: 1 }").unwrap())[0].message(),
+            "zzzzzz is not a known css property"
+        );
+    }
+
+    #[test]
+    fn css_lists_and_multipliers() {
+        let set = RuleSet::parse(
+            ".a { border-image-width: 2; border-image-outset: 1 1.5; border-width: 1px 2; border-color: red #ff000 blue }",
+        )
+        .unwrap();
+        let errors = validate_css(&set);
+
+        assert_eq!(
+            errors.iter().map(|e| e.code()).collect::<Vec<_>>(),
+            vec!["missing-unit", "invalid-color"]
+        );
+        assert_eq!(
+            errors[1].message(),
+            "#ff000 is not a color for border-color"
+        );
+    }
+}
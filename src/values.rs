@@ -0,0 +1,186 @@
+use alloc::string::ToString;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use serde::{Deserialize, Serialize};
+
+// counts what a value writes without keeping it
+struct Counter(usize);
+
+impl fmt::Write for Counter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+pub(crate) fn display_len<T: fmt::Display>(value: &T) -> usize {
+    let mut counter = Counter(0);
+    let _ = fmt::Write::write_fmt(&mut counter, format_args!("{}", value));
+    counter.0
+}
+
+// a number in a css value, compared by its bits so rules stay usable as keys
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Float(f64);
+
+impl Float {
+    pub fn new(value: f64) -> Self {
+        Self(value)
+    }
+
+    pub fn get(&self) -> f64 {
+        self.0
+    }
+
+    // without the zero before a decimal point, 0.5 as .5
+    pub(crate) fn write_minified<W: fmt::Write>(&self, output: &mut W) -> fmt::Result {
+        let text = self.to_string();
+        match (text.strip_prefix("0."), text.strip_prefix("-0.")) {
+            (Some(fraction), _) => write!(output, ".{}", fraction),
+            (_, Some(fraction)) => write!(output, "-.{}", fraction),
+            _ => output.write_str(&text),
+        }
+    }
+}
+
+impl From<f64> for Float {
+    fn from(value: f64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<i32> for Float {
+    fn from(value: i32) -> Self {
+        Self(value as f64)
+    }
+}
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for Float {}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Float {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Hash for Float {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state)
+    }
+}
+
+// shortest decimal form without an exponent, -0 is written as 0
+impl fmt::Display for Float {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 == 0.0 {
+            true => f.write_str("0"),
+            false => write!(f, "{}", self.0),
+        }
+    }
+}
+
+macro_rules! units {
+    ($($variant:ident => $name:literal,)*) => {
+        // units of dimensions: lengths, flex fractions, angles, times and resolutions
+        #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+        pub enum Unit {
+            $($variant,)*
+        }
+
+        impl Unit {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Unit::$variant => $name,)*
+                }
+            }
+
+            // case insensitive like css
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name.to_ascii_lowercase().as_str() {
+                    $($name => Some(Unit::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+#[rustfmt::skip]
+units! {
+    Px => "px", Em => "em", Rem => "rem", Ex => "ex", Ch => "ch", Lh => "lh", Vw => "vw",
+    Vh => "vh", Vmin => "vmin", Vmax => "vmax", Dvh => "dvh", Svh => "svh", Lvh => "lvh",
+    Cqw => "cqw", Cqh => "cqh", Cm => "cm", Mm => "mm", In => "in", Pt => "pt", Pc => "pc",
+    Q => "q", Fr => "fr", Deg => "deg", Rad => "rad", Grad => "grad", Turn => "turn", S => "s",
+    Ms => "ms", Dpi => "dpi", Dpcm => "dpcm", Dppx => "dppx",
+}
+
+impl Unit {
+    // a zero of these can be written without its unit
+    pub fn is_length(&self) -> bool {
+        !matches!(
+            self,
+            Unit::Fr
+                | Unit::Deg
+                | Unit::Rad
+                | Unit::Grad
+                | Unit::Turn
+                | Unit::S
+                | Unit::Ms
+                | Unit::Dpi
+                | Unit::Dpcm
+                | Unit::Dppx
+        )
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::values::{display_len, Float, Unit};
+    use alloc::string::String;
+
+    #[test]
+    fn floats() {
+        assert_eq!(Float::new(0.5).to_string(), "0.5");
+        assert_eq!(Float::new(-0.0).to_string(), "0");
+        assert_eq!(Float::new(12.0).to_string(), "12");
+        assert_eq!(display_len(&Float::new(-1.25)), 5);
+        assert_eq!(Float::new(f64::NAN), Float::new(f64::NAN));
+        assert!(Float::new(1.0) < Float::new(1.5));
+
+        let mut minified = String::new();
+        Float::new(-0.25).write_minified(&mut minified).unwrap();
+        Float::new(10.5).write_minified(&mut minified).unwrap();
+        assert_eq!(minified, "-.2510.5");
+    }
+
+    #[test]
+    fn units() {
+        assert_eq!(Unit::from_name("REM"), Some(Unit::Rem));
+        assert_eq!(Unit::from_name("furlong"), None);
+        assert_eq!(Unit::Vmin.to_string(), "vmin");
+        assert!(Unit::Cqw.is_length());
+        assert!(!Unit::Ms.is_length());
+    }
+}
@@ -0,0 +1,88 @@
+use alloc::string::ToString;
+use yew::virtual_dom::{ApplyAttributeAs, VList, VNode, VTag, VText};
+
+use crate::html::Node;
+
+// a From impl would overlap with yew's blanket conversion of Display types into text
+pub fn to_vnode(node: &Node) -> VNode {
+    match node {
+        Node::Text(text) => VText::new(text.to_string()).into(),
+        Node::Message { key, .. } => VText::new(key.to_string()).into(),
+        // yew has no comment nodes, an empty list renders nothing
+        Node::Comment(_) | Node::Slot(_) => VList::new().into(),
+        Node::Fragment(children) => {
+            VList::with_children(children.iter().map(to_vnode).collect(), None).into()
+        }
+        Node::Raw(html) => VNode::from_html_unchecked(html.to_string().into()),
+        #[cfg(feature = "markdown")]
+        Node::Markdown(text) => {
+            VNode::from_html_unchecked(crate::markdown::markdown_to_html(text).into())
+        }
+        Node::Element {
+            tag,
+            attributes,
+            children,
+            ..
+        } => {
+            let mut element = VTag::new(tag.to_string());
+            let map = element.attributes.get_mut_index_map();
+            for attribute in attributes {
+                map.insert(
+                    attribute.name().to_string().into(),
+                    (
+                        attribute.value().unwrap_or_default().to_string().into(),
+                        ApplyAttributeAs::Attribute,
+                    ),
+                );
+            }
+            element.add_children(children.iter().map(to_vnode));
+            element.into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use yew::virtual_dom::VNode;
+
+    use crate::html::{Attribute, Node};
+    use crate::views::to_vnode;
+
+    #[test]
+    fn element() {
+        let node = Node::element(
+            "div".to_string(),
+            vec![
+                Attribute::new("class".to_string(), "card".to_string()),
+                Attribute::toggle("hidden".to_string()),
+            ],
+            vec![Node::text("Some text".to_string())],
+        );
+
+        let element = match to_vnode(&node) {
+            VNode::VTag(element) => element,
+            other => panic!("expected a tag, got {:?}", other),
+        };
+
+        assert_eq!(element.tag(), "div");
+        assert_eq!(
+            element.attributes.iter().collect::<Vec<(&str, &str)>>(),
+            vec![("class", "card"), ("hidden", "")]
+        );
+        match element.children() {
+            Some(VNode::VList(list)) => match &list[..] {
+                [VNode::VText(text)] => assert_eq!(text.text.as_str(), "Some text"),
+                other => panic!("expected one text child, got {:?}", other),
+            },
+            other => panic!("expected children, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn raw() {
+        match to_vnode(&Node::raw("<b>bold</b>".to_string())) {
+            VNode::VRaw(raw) => assert_eq!(raw.html.as_ref(), "<b>bold</b>"),
+            other => panic!("expected raw html, got {:?}", other),
+        }
+    }
+}
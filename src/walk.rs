@@ -0,0 +1,133 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::html::Node;
+
+// depth first, in document order, starting with the node itself
+#[derive(Debug, Clone)]
+pub struct Descendants<'a> {
+    stack: Vec<&'a Node>,
+}
+
+impl<'a> Iterator for Descendants<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<&'a Node> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.children().iter().rev());
+        Some(node)
+    }
+}
+
+pub trait Visitor {
+    // returning false skips the children of the node, leave is still called
+    fn enter(&mut self, _node: &Node) -> bool {
+        true
+    }
+
+    fn leave(&mut self, _node: &Node) {}
+}
+
+impl Node {
+    pub fn iter(&self) -> Descendants<'_> {
+        Descendants { stack: vec![self] }
+    }
+
+    pub fn walk<V: Visitor>(&self, visitor: &mut V) {
+        if visitor.enter(self) {
+            for child in self.children() {
+                child.walk(visitor);
+            }
+        }
+        visitor.leave(self);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::String;
+
+    use crate::html::{Attribute, Node};
+    use crate::walk::Visitor;
+
+    fn page() -> Node {
+        Node::element(
+            "body".to_string(),
+            vec![],
+            vec![
+                Node::element(
+                    "h1".to_string(),
+                    vec![],
+                    vec![Node::text("Title".to_string())],
+                ),
+                Node::fragment(vec![
+                    Node::element(
+                        "img".to_string(),
+                        vec![Attribute::new("src".to_string(), "/a.png".to_string())],
+                        vec![],
+                    ),
+                    Node::element(
+                        "svg".to_string(),
+                        vec![],
+                        vec![Node::element(
+                            "image".to_string(),
+                            vec![Attribute::new("src".to_string(), "/b.png".to_string())],
+                            vec![],
+                        )],
+                    ),
+                ]),
+            ],
+        )
+    }
+
+    #[test]
+    fn iter_in_document_order() {
+        let tags = page()
+            .iter()
+            .map(|n| n.tag().unwrap_or("-"))
+            .collect::<String>();
+
+        assert_eq!(tags, "bodyh1--imgsvgimage");
+    }
+
+    #[test]
+    fn iter_collects_urls() {
+        let page = page();
+        let sources = page
+            .iter()
+            .flat_map(Node::attributes)
+            .filter(|a| a.name() == "src")
+            .filter_map(Attribute::value)
+            .collect::<Vec<&str>>();
+
+        assert_eq!(sources, vec!["/a.png", "/b.png"]);
+    }
+
+    #[test]
+    fn walk_skips_children() {
+        #[derive(Default)]
+        struct Tags {
+            entered: Vec<String>,
+            left: usize,
+        }
+
+        impl Visitor for Tags {
+            fn enter(&mut self, node: &Node) -> bool {
+                if let Some(tag) = node.tag() {
+                    self.entered.push(tag.to_string());
+                }
+                node.tag() != Some("svg")
+            }
+
+            fn leave(&mut self, _node: &Node) {
+                self.left += 1;
+            }
+        }
+
+        let mut tags = Tags::default();
+        page().walk(&mut tags);
+
+        assert_eq!(tags.entered, vec!["body", "h1", "img", "svg"]);
+        assert_eq!(tags.left, 6);
+    }
+}